@@ -1,17 +1,36 @@
-use std::env;
-use actix_web::{web, Responder};
+use actix_web::{web, HttpMessage, HttpRequest, Responder};
 use apistos::{web as aweb, ApiComponent};
 use apistos::api_operation;
 use apistos::web::ServiceConfig;
 use schemars::JsonSchema;
 use serde::Deserialize;
-use dto::app_response::AppResponse;
-use crate::util::create_jwt;
+use command::create_user_command::CreateUserCommand;
+use dto::app_error::AppError;
+use dto::auth::TokenPair;
+use entity::user::Permission;
+use crate::app_state::AppState;
+use crate::util::{claim_token, create_token_pair, decode_token, handle_api_result, revoke_token, token_store_unavailable, AuthenticatedUser, JwtAuth, TokenType};
 
 pub fn admin_route(cfg: &mut ServiceConfig) {
     cfg.service(
         aweb::scope("/admin")
             .route("/login", aweb::post().to(login))
+            .route("/refresh", aweb::post().to(refresh))
+            .service(
+                aweb::resource("/logout")
+                    .route(aweb::post().to(logout))
+                    .wrap(JwtAuth::default())
+            )
+            .service(
+                aweb::resource("/users")
+                    .route(aweb::post().to(create_user))
+                    .wrap(JwtAuth::require(Permission::ManageUsers))
+            )
+            .service(
+                aweb::resource("/cache-stats")
+                    .route(aweb::get().to(cache_stats))
+                    .wrap(JwtAuth::require(Permission::ViewStats))
+            )
     );
 }
 
@@ -21,21 +40,98 @@ pub struct LoginRequest {
     pub password: String,
 }
 
+#[derive(Debug, Deserialize, JsonSchema, ApiComponent)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema, ApiComponent)]
+pub struct LogoutRequest {
+    pub refresh_token: Option<String>,
+}
+
 #[api_operation(summary = "管理员登录")]
 pub async fn login(
+    data: web::Data<AppState>,
     form: web::Json<LoginRequest>,
 ) -> impl Responder {
-    dotenvy::dotenv().ok(); // 加载 .env 变量
-
-    let expected_user = env::var("ADMIN_USERNAME").unwrap_or_else(|_| "admin".into());
-    let expected_pass = env::var("ADMIN_PASSWORD").unwrap_or_else(|_| "123456".into());
-
-    if form.username == expected_user && form.password == expected_pass {
-        match create_jwt(&form.username, "admin") {
-            Ok(token) => AppResponse::ok(token),
-            Err(_) => AppResponse::message(500, "Token 创建失败"),
-        }
-    } else {
-        AppResponse::message(401, "用户名或密码错误")
+    let LoginRequest { username, password } = form.into_inner();
+
+    let result = match data.login.execute(username, password).await {
+        Ok(user) => create_token_pair(&user.id.to_string(), user.role)
+            .map_err(|_| AppError::InternalError),
+        Err(e) => Err(e),
+    };
+    handle_api_result(result).await
+}
+
+#[api_operation(summary = "刷新 Token")]
+pub async fn refresh(
+    data: web::Data<AppState>,
+    form: web::Json<RefreshRequest>,
+) -> impl Responder {
+    handle_api_result(refresh_token_pair(&data, &form.refresh_token).await).await
+}
+
+#[api_operation(summary = "退出登录")]
+pub async fn logout(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    form: web::Json<LogoutRequest>,
+) -> impl Responder {
+    let user = req.extensions().get::<AuthenticatedUser>().cloned();
+    handle_api_result(revoke_session(&data, user, form.into_inner()).await).await
+}
+
+#[api_operation(summary = "创建用户")]
+pub async fn create_user(
+    data: web::Data<AppState>,
+    payload: web::Json<CreateUserCommand>,
+) -> impl Responder {
+    handle_api_result(data.create_user.execute(payload.into_inner()).await).await
+}
+
+#[api_operation(summary = "进程内访客缓存统计")]
+pub async fn cache_stats(data: web::Data<AppState>) -> impl Responder {
+    handle_api_result(Ok(data.local_cache.stats())).await
+}
+
+async fn refresh_token_pair(data: &AppState, refresh_token: &str) -> Result<TokenPair, AppError> {
+    let claims = decode_token(refresh_token)
+        .filter(|c| c.typ == TokenType::Refresh)
+        .ok_or_else(|| AppError::Unauthorized("Invalid refresh token".into()))?;
+
+    let user_id = claims.sub.parse::<i64>()
+        .map_err(|_| AppError::Unauthorized("Invalid refresh token".into()))?;
+
+    // 轮换：旧的 refresh token 使用一次后即作废，并发的刷新请求只有一个能成功
+    if !claim_token(&data.redis, &claims.jti, claims.exp).await.map_err(|_| token_store_unavailable())? {
+        return Err(AppError::Unauthorized("Refresh token has been revoked".into()));
     }
-}
\ No newline at end of file
+
+    // 重新读取用户，使角色变更和账户删除在刷新时生效
+    let user = data.find_user_by_id.execute(user_id).await
+        .map_err(|e| match e {
+            AppError::NotFound(_) => AppError::Unauthorized("User no longer exists".into()),
+            e => e,
+        })?;
+
+    create_token_pair(&user.id.to_string(), user.role).map_err(|_| AppError::InternalError)
+}
+
+async fn revoke_session(data: &AppState, user: Option<AuthenticatedUser>, form: LogoutRequest) -> Result<(), AppError> {
+    let user = user.ok_or_else(|| AppError::Unauthorized("Invalid or missing token".into()))?;
+    revoke_token(&data.redis, &user.jti, user.exp).await
+        .map_err(|_| token_store_unavailable())?;
+
+    // 只吊销属于当前用户的 refresh token
+    if let Some(claims) = form.refresh_token.as_deref().and_then(decode_token)
+        && claims.typ == TokenType::Refresh
+        && claims.sub == user.user_id
+    {
+        revoke_token(&data.redis, &claims.jti, claims.exp).await
+            .map_err(|_| token_store_unavailable())?;
+    }
+
+    Ok(())
+}
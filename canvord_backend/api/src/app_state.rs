@@ -1,6 +1,12 @@
 use sea_orm::DatabaseConnection;
 use std::sync::Arc;
 use redis::Client;
+use search::SearchIndex;
+use media::MediaStore;
+use middleware::cache::CacheInvalidator;
+use middleware::connection::RedisConnection;
+use middleware::local_cache::LocalCache;
+use tokio::sync::Notify;
 use handler::{
     create_article_handler::CreateArticleHandler,
     delete_article_handler::DeleteArticleHandler,
@@ -9,18 +15,64 @@ use handler::{
     hide_article_handler::HideArticleHandler,
     list_article_by_title_handler::ListArticleByTitleHandler,
     list_articles_in_page_by_status_handler::ListArticlesInPageByStatusHandler,
+    list_articles_by_cursor_handler::ListArticlesByCursorHandler,
     publish_article_handler::PublishArticleHandler,
     publish_draft_handler::PublishDraftHandler,
     save_article_handler::SaveArticleHandler,
     update_article_handler::UpdateArticleHandler,
     find_publish_article_by_slug_handler::FindPublishArticleBySlugHandler,
     list_publish_article_by_title_handler::ListPublishArticleByTitleHandler,
+    list_article_revisions_handler::ListArticleRevisionsHandler,
+    find_article_revision_by_id_handler::FindArticleRevisionByIdHandler,
+    diff_article_revisions_handler::DiffArticleRevisionsHandler,
+    restore_article_revision_handler::RestoreArticleRevisionHandler,
+    list_tags_with_count_handler::ListTagsWithCountHandler,
+    list_publish_articles_in_page_by_tag_handler::ListPublishArticlesInPageByTagHandler,
+    list_publish_articles_in_page_by_category_handler::ListPublishArticlesInPageByCategoryHandler,
+    list_category_tree_handler::ListCategoryTreeHandler,
+    create_category_handler::CreateCategoryHandler,
+    update_category_handler::UpdateCategoryHandler,
+    delete_category_handler::DeleteCategoryHandler,
+    login_handler::LoginHandler,
+    create_user_handler::CreateUserHandler,
+    find_user_by_id_handler::FindUserByIdHandler,
+    search_articles_handler::SearchArticlesHandler,
+    upload_media_handler::UploadMediaHandler,
+    list_media_in_page_handler::ListMediaInPageHandler,
+    delete_media_handler::DeleteMediaHandler,
+    find_media_file_handler::FindMediaFileHandler,
+    preview_article_handler::PreviewArticleHandler,
+    list_feed_articles_handler::ListFeedArticlesHandler,
+    generate_sitemap_handler::GenerateSitemapHandler,
+    schedule_article_handler::ScheduleArticleHandler,
+    cancel_schedule_handler::CancelScheduleHandler,
+    list_scheduled_articles_handler::ListScheduledArticlesHandler,
+    publish_scheduled_articles_handler::PublishScheduledArticlesHandler,
+    prerender_article_handler::{PrerenderArticleHandler, PrerenderQueue},
+    article_changed_handler::ArticleChangedHandler,
 };
+use crate::prerender::prerender_target;
+use crate::site::SITE_URL;
 
 #[derive(Clone)]
+#[allow(dead_code)] // 部分字段只为保证 handler 中 `'static` 引用的对象不被释放
 pub struct AppState {
     pub db: Arc<DatabaseConnection>,
     pub redis_client: Client,
+    /// 带超时和熔断的共享连接，用于 token 黑名单和清除缓存
+    pub redis: Arc<RedisConnection>,
+    pub search_index: Arc<SearchIndex>,
+    pub media_store: Arc<dyn MediaStore>,
+    pub cache_invalidator: Arc<CacheInvalidator>,
+    /// 访客缓存在 Redis 之前的进程内缓存
+    pub local_cache: Arc<LocalCache>,
+    /// 定时发布有变化时唤醒后台任务
+    pub scheduler: Arc<Notify>,
+    /// 文章或分类变化后登记需要更新的预渲染页面，由后台任务处理
+    pub prerender_queue: Arc<PrerenderQueue>,
+    pub prerender_article: Arc<PrerenderArticleHandler<'static>>,
+    /// 文章变化后更新索引、缓存和预渲染页面
+    pub article_changed: Arc<ArticleChangedHandler<'static>>,
 
     pub create_article: Arc<CreateArticleHandler<'static>>,
     pub update_article: Arc<UpdateArticleHandler<'static>>,
@@ -34,30 +86,108 @@ pub struct AppState {
     pub find_publish_by_slug: Arc<FindPublishArticleBySlugHandler<'static>>,
     pub list_by_title: Arc<ListArticleByTitleHandler<'static>>,
     pub list_by_status_page: Arc<ListArticlesInPageByStatusHandler<'static>>,
+    pub list_by_cursor: Arc<ListArticlesByCursorHandler<'static>>,
     pub list_publish_by_title: Arc<ListPublishArticleByTitleHandler<'static>>,
+    pub list_revisions: Arc<ListArticleRevisionsHandler<'static>>,
+    pub find_revision_by_id: Arc<FindArticleRevisionByIdHandler<'static>>,
+    pub diff_revisions: Arc<DiffArticleRevisionsHandler<'static>>,
+    pub restore_revision: Arc<RestoreArticleRevisionHandler<'static>>,
+    pub list_tags: Arc<ListTagsWithCountHandler<'static>>,
+    pub list_publish_by_tag_page: Arc<ListPublishArticlesInPageByTagHandler<'static>>,
+    pub list_publish_by_category_page: Arc<ListPublishArticlesInPageByCategoryHandler<'static>>,
+    pub search_articles: Arc<SearchArticlesHandler<'static>>,
+    pub preview_article: Arc<PreviewArticleHandler<'static>>,
+    pub list_feed_articles: Arc<ListFeedArticlesHandler<'static>>,
+    pub generate_sitemap: Arc<GenerateSitemapHandler<'static>>,
+    pub schedule_article: Arc<ScheduleArticleHandler<'static>>,
+    pub cancel_schedule: Arc<CancelScheduleHandler<'static>>,
+    pub list_scheduled: Arc<ListScheduledArticlesHandler<'static>>,
+    pub publish_scheduled: Arc<PublishScheduledArticlesHandler<'static>>,
+
+    pub list_category_tree: Arc<ListCategoryTreeHandler<'static>>,
+    pub create_category: Arc<CreateCategoryHandler<'static>>,
+    pub update_category: Arc<UpdateCategoryHandler<'static>>,
+    pub delete_category: Arc<DeleteCategoryHandler<'static>>,
+
+    pub upload_media: Arc<UploadMediaHandler<'static>>,
+    pub list_media_page: Arc<ListMediaInPageHandler<'static>>,
+    pub delete_media: Arc<DeleteMediaHandler<'static>>,
+    pub find_media_file: Arc<FindMediaFileHandler<'static>>,
+
+    pub login: Arc<LoginHandler<'static>>,
+    pub create_user: Arc<CreateUserHandler<'static>>,
+    pub find_user_by_id: Arc<FindUserByIdHandler<'static>>,
 }
 
 impl AppState {
-    pub fn new(db: Arc<DatabaseConnection>, redis_client: Client) -> Self {
+    pub fn new(db: Arc<DatabaseConnection>, redis_client: Client, search_index: Arc<SearchIndex>, media_store: Arc<dyn MediaStore>, media_max_bytes: usize, local_cache: Arc<LocalCache>) -> Self {
         // NOTE: 用 `'static` 其实是因为 actix-web 的要求：必须线程安全 + 生命周期长。
         let db_ref: &'static DatabaseConnection = unsafe { std::mem::transmute::<&DatabaseConnection, &'static DatabaseConnection>(&*db) };
+        let index_ref: &'static SearchIndex = unsafe { std::mem::transmute::<&SearchIndex, &'static SearchIndex>(&*search_index) };
+        let store_ref: &'static dyn MediaStore = unsafe { std::mem::transmute::<&dyn MediaStore, &'static dyn MediaStore>(&*media_store) };
+        let redis = Arc::new(RedisConnection::new(redis_client.clone()));
+        let cache_invalidator = Arc::new(CacheInvalidator::with_connection(redis.clone()).with_local(local_cache.clone()));
+        let cache_ref: &'static CacheInvalidator = unsafe { std::mem::transmute::<&CacheInvalidator, &'static CacheInvalidator>(&*cache_invalidator) };
+        let scheduler = Arc::new(Notify::new());
+        let scheduler_ref: &'static Notify = unsafe { std::mem::transmute::<&Notify, &'static Notify>(&*scheduler) };
+        let prerender_article = Arc::new(PrerenderArticleHandler::new(db_ref, prerender_target()));
+        let prerender_queue = Arc::new(PrerenderQueue::default());
+        let queue_ref: &'static PrerenderQueue = unsafe { std::mem::transmute::<&PrerenderQueue, &'static PrerenderQueue>(&*prerender_queue) };
+        let article_changed = Arc::new(ArticleChangedHandler::new(index_ref, cache_ref, queue_ref));
+        let changed_ref: &'static ArticleChangedHandler<'static> = unsafe { std::mem::transmute::<&ArticleChangedHandler, &'static ArticleChangedHandler<'static>>(&*article_changed) };
 
         Self {
             db,
             redis_client,
-            create_article: Arc::new(CreateArticleHandler::new(db_ref)),
-            update_article: Arc::new(UpdateArticleHandler::new(db_ref)),
-            delete_article: Arc::new(DeleteArticleHandler::new(db_ref)),
-            hide_article: Arc::new(HideArticleHandler::new(db_ref)),
-            publish_article: Arc::new(PublishArticleHandler::new(db_ref)),
-            publish_draft: Arc::new(PublishDraftHandler::new(db_ref)),
-            save_article: Arc::new(SaveArticleHandler::new(db_ref)),
+            redis,
+            search_index,
+            media_store,
+            cache_invalidator,
+            local_cache,
+            scheduler,
+            prerender_queue,
+            prerender_article,
+            article_changed,
+            create_article: Arc::new(CreateArticleHandler::new(db_ref, changed_ref)),
+            update_article: Arc::new(UpdateArticleHandler::new(db_ref, changed_ref)),
+            delete_article: Arc::new(DeleteArticleHandler::new(db_ref, changed_ref)),
+            hide_article: Arc::new(HideArticleHandler::new(db_ref, changed_ref)),
+            publish_article: Arc::new(PublishArticleHandler::new(db_ref, changed_ref)),
+            publish_draft: Arc::new(PublishDraftHandler::new(db_ref, changed_ref)),
+            save_article: Arc::new(SaveArticleHandler::new(db_ref, changed_ref)),
             find_by_id: Arc::new(FindArticleByIdHandler::new(db_ref)),
             find_by_slug: Arc::new(FindArticleBySlugHandler::new(db_ref)),
             find_publish_by_slug: Arc::new(FindPublishArticleBySlugHandler::new(db_ref)),
             list_by_title: Arc::new(ListArticleByTitleHandler::new(db_ref)),
             list_by_status_page: Arc::new(ListArticlesInPageByStatusHandler::new(db_ref)),
+            list_by_cursor: Arc::new(ListArticlesByCursorHandler::new(db_ref)),
             list_publish_by_title: Arc::new(ListPublishArticleByTitleHandler::new(db_ref)),
+            list_revisions: Arc::new(ListArticleRevisionsHandler::new(db_ref)),
+            find_revision_by_id: Arc::new(FindArticleRevisionByIdHandler::new(db_ref)),
+            diff_revisions: Arc::new(DiffArticleRevisionsHandler::new(db_ref)),
+            restore_revision: Arc::new(RestoreArticleRevisionHandler::new(db_ref, changed_ref)),
+            list_tags: Arc::new(ListTagsWithCountHandler::new(db_ref)),
+            list_publish_by_tag_page: Arc::new(ListPublishArticlesInPageByTagHandler::new(db_ref)),
+            list_publish_by_category_page: Arc::new(ListPublishArticlesInPageByCategoryHandler::new(db_ref)),
+            search_articles: Arc::new(SearchArticlesHandler::new(db_ref, index_ref)),
+            preview_article: Arc::new(PreviewArticleHandler::new(db_ref)),
+            list_feed_articles: Arc::new(ListFeedArticlesHandler::new(db_ref)),
+            generate_sitemap: Arc::new(GenerateSitemapHandler::new(db_ref, SITE_URL.clone())),
+            schedule_article: Arc::new(ScheduleArticleHandler::new(db_ref, changed_ref, scheduler_ref)),
+            cancel_schedule: Arc::new(CancelScheduleHandler::new(db_ref, changed_ref)),
+            list_scheduled: Arc::new(ListScheduledArticlesHandler::new(db_ref)),
+            publish_scheduled: Arc::new(PublishScheduledArticlesHandler::new(db_ref, changed_ref)),
+            list_category_tree: Arc::new(ListCategoryTreeHandler::new(db_ref)),
+            create_category: Arc::new(CreateCategoryHandler::new(db_ref, cache_ref)),
+            update_category: Arc::new(UpdateCategoryHandler::new(db_ref, cache_ref, queue_ref)),
+            delete_category: Arc::new(DeleteCategoryHandler::new(db_ref, cache_ref, queue_ref)),
+            upload_media: Arc::new(UploadMediaHandler::new(db_ref, store_ref, media_max_bytes)),
+            list_media_page: Arc::new(ListMediaInPageHandler::new(db_ref, store_ref)),
+            delete_media: Arc::new(DeleteMediaHandler::new(db_ref, store_ref)),
+            find_media_file: Arc::new(FindMediaFileHandler::new(db_ref, store_ref)),
+            login: Arc::new(LoginHandler::new(db_ref)),
+            create_user: Arc::new(CreateUserHandler::new(db_ref)),
+            find_user_by_id: Arc::new(FindUserByIdHandler::new(db_ref)),
         }
     }
 }
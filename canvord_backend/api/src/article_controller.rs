@@ -1,17 +1,23 @@
 use crate::app_state::AppState;
-use crate::util::{handle_api_result, validate, JwtAuth};
-use actix_web::{web, Responder};
+use crate::media_controller::{delete_media, list_media, upload_media};
+use crate::util::{handle_api_result, validate, AuthenticatedUser, JwtAuth};
+use actix_web::{web, HttpRequest, Responder};
 use apistos::web as aweb;
 use apistos::web::ServiceConfig;
 use apistos::{api_operation, ApiComponent};
 use command::create_article_command::CreateArticleCommand;
 use command::delete_article_command::DeleteArticleCommand;
 use command::hide_article_command::HideArticleCommand;
+use command::preview_article_command::PreviewArticleCommand;
 use command::publish_article_command::PublishArticleCommand;
 use command::publish_draft_command::PublishDraftCommand;
+use command::restore_article_revision_command::RestoreArticleRevisionCommand;
 use command::save_article_command::SaveArticleCommand;
+use command::schedule_article_command::ScheduleArticleCommand;
+use command::cancel_schedule_command::CancelScheduleCommand;
 use command::update_article_command::UpdateArticleCommand;
 use entity::article::Status;
+use entity::user::Permission;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use validator::Validate;
@@ -21,35 +27,153 @@ use dto::pagination::PageResult;
 pub fn article_route(cfg: &mut ServiceConfig) {
     cfg.service(
         aweb::scope("/articles")
-            .wrap(JwtAuth)
-            .route("/create", aweb::post().to(create_article))
-            .route("/update", aweb::put().to(update_article))
-            .route("/delete", aweb::delete().to(delete_article))
-            .route("/hide", aweb::put().to(hide_article))
-            .route("/publish", aweb::put().to(publish_article))
-            .route("/publish-draft", aweb::put().to(publish_draft))
-            .route("/save-draft", aweb::put().to(save_draft))
-            .route("/id/{id}", aweb::get().to(find_article_by_id))
-            .route("/slug/{slug}", aweb::get().to(find_article_by_slug))
-            .route("/title/{title}", aweb::get().to(list_article_by_title))
-            .route("/page", aweb::get().to(list_articles))
+            .service(
+                aweb::resource("/create")
+                    .route(aweb::post().to(create_article))
+                    .wrap(JwtAuth::require(Permission::PublishArticle))
+            )
+            .service(
+                // 没有编辑权限的作者只能修改自己的草稿，由 handler 检查
+                aweb::resource("/update")
+                    .route(aweb::put().to(update_article))
+                    .wrap(JwtAuth::require(Permission::SaveDraft))
+            )
+            .service(
+                aweb::resource("/delete")
+                    .route(aweb::delete().to(delete_article))
+                    .wrap(JwtAuth::require(Permission::DeleteArticle))
+            )
+            .service(
+                aweb::resource("/hide")
+                    .route(aweb::put().to(hide_article))
+                    .wrap(JwtAuth::require(Permission::PublishArticle))
+            )
+            .service(
+                aweb::resource("/publish")
+                    .route(aweb::put().to(publish_article))
+                    .wrap(JwtAuth::require(Permission::PublishArticle))
+            )
+            .service(
+                aweb::resource("/publish-draft")
+                    .route(aweb::put().to(publish_draft))
+                    .wrap(JwtAuth::require(Permission::PublishArticle))
+            )
+            .service(
+                aweb::resource("/schedule")
+                    .route(aweb::put().to(schedule_article))
+                    .wrap(JwtAuth::require(Permission::PublishArticle))
+            )
+            .service(
+                aweb::resource("/cancel-schedule")
+                    .route(aweb::put().to(cancel_schedule))
+                    .wrap(JwtAuth::require(Permission::PublishArticle))
+            )
+            .service(
+                aweb::resource("/scheduled")
+                    .route(aweb::get().to(list_scheduled))
+                    .wrap(JwtAuth::require(Permission::ViewArticle))
+            )
+            .service(
+                aweb::resource("/save-draft")
+                    .route(aweb::put().to(save_draft))
+                    .wrap(JwtAuth::require(Permission::SaveDraft))
+            )
+            .service(
+                aweb::resource("/preview")
+                    .route(aweb::post().to(preview_article))
+                    .wrap(JwtAuth::require(Permission::SaveDraft))
+            )
+            .service(
+                aweb::resource("/id/{id}")
+                    .route(aweb::get().to(find_article_by_id))
+                    .wrap(JwtAuth::require(Permission::ViewArticle))
+            )
+            .service(
+                aweb::resource("/slug/{slug}")
+                    .route(aweb::get().to(find_article_by_slug))
+                    .wrap(JwtAuth::require(Permission::ViewArticle))
+            )
+            .service(
+                aweb::resource("/title/{title}")
+                    .route(aweb::get().to(list_article_by_title))
+                    .wrap(JwtAuth::require(Permission::ViewArticle))
+            )
+            .service(
+                aweb::resource("/page")
+                    .route(aweb::get().to(list_articles))
+                    .wrap(JwtAuth::require(Permission::ViewArticle))
+            )
+            .service(
+                aweb::resource("/search")
+                    .route(aweb::get().to(search_articles))
+                    .wrap(JwtAuth::require(Permission::ViewArticle))
+            )
+            .service(
+                aweb::resource("/media/upload")
+                    .route(aweb::post().to(upload_media))
+                    .wrap(JwtAuth::require(Permission::UploadMedia))
+            )
+            .service(
+                aweb::resource("/media/page")
+                    .route(aweb::get().to(list_media))
+                    .wrap(JwtAuth::require(Permission::UploadMedia))
+            )
+            .service(
+                aweb::resource("/media/delete")
+                    .route(aweb::delete().to(delete_media))
+                    .wrap(JwtAuth::require(Permission::ManageMedia))
+            )
+            .service(
+                aweb::resource("/revision/list/{article_id}")
+                    .route(aweb::get().to(list_revisions))
+                    .wrap(JwtAuth::require(Permission::ViewArticle))
+            )
+            .service(
+                aweb::resource("/revision/id/{id}")
+                    .route(aweb::get().to(find_revision_by_id))
+                    .wrap(JwtAuth::require(Permission::ViewArticle))
+            )
+            .service(
+                aweb::resource("/revision/diff")
+                    .route(aweb::get().to(diff_revisions))
+                    .wrap(JwtAuth::require(Permission::ViewArticle))
+            )
+            .service(
+                aweb::resource("/revision/restore")
+                    .route(aweb::put().to(restore_revision))
+                    .wrap(JwtAuth::require(Permission::EditArticle))
+            )
     );
 }
 
 #[api_operation(summary = "创建文章")]
 pub async fn create_article(
     data: web::Data<AppState>,
+    req: HttpRequest,
     payload: web::Json<CreateArticleCommand>,
 ) -> impl Responder {
-    handle_api_result(data.create_article.execute(payload.into_inner()).await).await
+    let result = match AuthenticatedUser::of(&req).and_then(|user| user.id()) {
+        Ok(author_id) => data.create_article.execute(payload.into_inner(), author_id).await,
+        Err(e) => Err(e),
+    };
+    handle_api_result(result).await
 }
 
 #[api_operation(summary = "修改文章")]
 pub async fn update_article(
     data: web::Data<AppState>,
+    req: HttpRequest,
     payload: web::Json<UpdateArticleCommand>,
 ) -> impl Responder {
-    handle_api_result(data.update_article.execute(payload.into_inner()).await).await
+    let owner = AuthenticatedUser::of(&req).and_then(|user| match user.role.has_permission(Permission::EditArticle) {
+        true => Ok(None),
+        false => user.id().map(Some),
+    });
+    let result = match owner {
+        Ok(owner) => data.update_article.execute(payload.into_inner(), owner).await,
+        Err(e) => Err(e),
+    };
+    handle_api_result(result).await
 }
 
 #[api_operation(summary = "删除文章")]
@@ -84,12 +208,48 @@ pub async fn publish_draft(
     handle_api_result(data.publish_draft.execute(payload.into_inner()).await).await
 }
 
+#[api_operation(summary = "定时发布文章（已定时的文章修改发布时间）")]
+pub async fn schedule_article(
+    data: web::Data<AppState>,
+    payload: web::Json<ScheduleArticleCommand>,
+) -> impl Responder {
+    handle_api_result(data.schedule_article.execute(payload.into_inner()).await).await
+}
+
+#[api_operation(summary = "取消定时发布")]
+pub async fn cancel_schedule(
+    data: web::Data<AppState>,
+    payload: web::Json<CancelScheduleCommand>,
+) -> impl Responder {
+    handle_api_result(data.cancel_schedule.execute(payload.into_inner()).await).await
+}
+
+#[api_operation(summary = "查询等待定时发布的文章")]
+pub async fn list_scheduled(
+    data: web::Data<AppState>,
+) -> impl Responder {
+    handle_api_result(data.list_scheduled.execute().await).await
+}
+
 #[api_operation(summary = "保存草稿")]
 pub async fn save_draft(
     data: web::Data<AppState>,
+    req: HttpRequest,
     payload: web::Json<SaveArticleCommand>,
 ) -> impl Responder {
-    handle_api_result(data.save_article.execute(payload.into_inner()).await).await
+    let result = match AuthenticatedUser::of(&req).and_then(|user| user.id()) {
+        Ok(author_id) => data.save_article.execute(payload.into_inner(), author_id).await,
+        Err(e) => Err(e),
+    };
+    handle_api_result(result).await
+}
+
+#[api_operation(summary = "预览文章渲染结果")]
+pub async fn preview_article(
+    data: web::Data<AppState>,
+    payload: web::Json<PreviewArticleCommand>,
+) -> impl Responder {
+    handle_api_result(data.preview_article.execute(payload.into_inner()).await).await
 }
 
 #[api_operation(summary = "根据ID查询文章")]
@@ -142,6 +302,69 @@ pub async fn list_articles(
     }
 }
 
+#[api_operation(summary = "全文搜索文章（含未发布）")]
+pub async fn search_articles(
+    data: web::Data<AppState>,
+    query: web::Query<SearchParams>,
+) -> impl Responder {
+    let params = query.into_inner();
+    if let Err(e) = validate(&params) {
+        return AppResponse::from_error(&e);
+    }
+
+    let handler = &data.search_articles;
+    match handler.execute(params.q, params.page, params.per, false).await {
+        Ok((hits, total)) => {
+            let page_result = PageResult {
+                total: total as usize,
+                current: params.page as usize,
+                size: params.per as usize,
+                data: hits,
+            };
+            AppResponse::ok(page_result)
+        }
+        Err(e) => AppResponse::from_error(&e),
+    }
+}
+
+#[api_operation(summary = "查询文章修订历史")]
+pub async fn list_revisions(
+    data: web::Data<AppState>,
+    article_id: web::Path<i64>,
+) -> impl Responder {
+    handle_api_result(data.list_revisions.execute(*article_id).await).await
+}
+
+#[api_operation(summary = "根据ID查询修订")]
+pub async fn find_revision_by_id(
+    data: web::Data<AppState>,
+    id: web::Path<i64>,
+) -> impl Responder {
+    handle_api_result(data.find_revision_by_id.execute(*id).await).await
+}
+
+#[api_operation(summary = "比较两个修订")]
+pub async fn diff_revisions(
+    data: web::Data<AppState>,
+    query: web::Query<RevisionDiffParams>,
+) -> impl Responder {
+    handle_api_result(data.diff_revisions.execute(query.from, query.to).await).await
+}
+
+#[api_operation(summary = "恢复修订")]
+pub async fn restore_revision(
+    data: web::Data<AppState>,
+    payload: web::Json<RestoreArticleRevisionCommand>,
+) -> impl Responder {
+    handle_api_result(data.restore_revision.execute(payload.into_inner()).await).await
+}
+
+#[derive(Debug, Deserialize, JsonSchema, ApiComponent)]
+pub struct RevisionDiffParams {
+    pub from: i64,
+    pub to: i64,
+}
+
 #[derive(Debug, Deserialize, Validate, JsonSchema, ApiComponent)]
 pub struct ArticlePageParams {
     #[validate(range(min = 1))]
@@ -149,4 +372,15 @@ pub struct ArticlePageParams {
     #[validate(range(min = 1, max = 100))]
     pub per: u64,
     pub status: Option<Status>,
+}
+
+#[derive(Debug, Deserialize, Validate, JsonSchema, ApiComponent)]
+pub struct SearchParams {
+    #[validate(length(min = 1, max = 100))]
+    pub q: String,
+    /// `page * per` 不能超过 10000
+    #[validate(range(min = 1, max = 10_000))]
+    pub page: u64,
+    #[validate(range(min = 1, max = 100))]
+    pub per: u64,
 }
\ No newline at end of file
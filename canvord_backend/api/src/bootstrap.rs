@@ -0,0 +1,39 @@
+use std::env;
+use std::io::{Error, ErrorKind};
+use canvord_service::user_mutation::UserMutation;
+use canvord_service::user_query::UserQuery;
+use entity::user::Role;
+use migration::sea_orm::Database;
+use migration::{Migrator, MigratorTrait};
+
+/// 首次部署时创建初始管理员：`canvord_backend create-admin [username] [password]`。
+///
+/// 未通过参数提供时回退到 `ADMIN_USERNAME` / `ADMIN_PASSWORD` 环境变量；
+/// 仅在用户表为空时执行，避免被用来覆盖或追加账户。
+#[actix_web::main]
+pub async fn create_admin(args: &[String]) -> std::io::Result<()> {
+    dotenvy::dotenv().ok();
+
+    let username = args.first().cloned()
+        .or_else(|| env::var("ADMIN_USERNAME").ok())
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "username is required"))?;
+    let password = args.get(1).cloned()
+        .or_else(|| env::var("ADMIN_PASSWORD").ok())
+        .filter(|p| !p.is_empty())
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "password is required"))?;
+
+    let db_url = env::var("DATABASE_URL").expect("DATABASE_URL is not set in .env file");
+    let conn = Database::connect(&db_url).await.map_err(Error::other)?;
+    Migrator::up(&conn, None).await.map_err(Error::other)?;
+
+    if UserQuery::count_users(&conn).await.map_err(Error::other)? > 0 {
+        return Err(Error::new(ErrorKind::AlreadyExists, "users already exist, bootstrap skipped"));
+    }
+
+    let user = UserMutation::create(&conn, &username, &password, Role::Admin)
+        .await
+        .map_err(Error::other)?;
+    println!("Created admin user '{}' (id {})", user.username, user.id);
+
+    Ok(())
+}
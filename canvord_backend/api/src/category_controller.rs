@@ -0,0 +1,67 @@
+use crate::app_state::AppState;
+use crate::util::{handle_api_result, JwtAuth};
+use actix_web::{web, Responder};
+use apistos::api_operation;
+use apistos::web as aweb;
+use apistos::web::ServiceConfig;
+use command::create_category_command::CreateCategoryCommand;
+use command::delete_category_command::DeleteCategoryCommand;
+use command::update_category_command::UpdateCategoryCommand;
+use entity::user::Permission;
+
+pub fn category_route(cfg: &mut ServiceConfig) {
+    cfg.service(
+        aweb::scope("/categories")
+            .service(
+                aweb::resource("/create")
+                    .route(aweb::post().to(create_category))
+                    .wrap(JwtAuth::require(Permission::ManageCategories))
+            )
+            .service(
+                aweb::resource("/update")
+                    .route(aweb::put().to(update_category))
+                    .wrap(JwtAuth::require(Permission::ManageCategories))
+            )
+            .service(
+                aweb::resource("/delete")
+                    .route(aweb::delete().to(delete_category))
+                    .wrap(JwtAuth::require(Permission::ManageCategories))
+            )
+            .service(
+                aweb::resource("/tree")
+                    .route(aweb::get().to(list_category_tree))
+                    .wrap(JwtAuth::require(Permission::ViewArticle))
+            )
+    );
+}
+
+#[api_operation(summary = "创建分类")]
+pub async fn create_category(
+    data: web::Data<AppState>,
+    payload: web::Json<CreateCategoryCommand>,
+) -> impl Responder {
+    handle_api_result(data.create_category.execute(payload.into_inner()).await).await
+}
+
+#[api_operation(summary = "修改分类")]
+pub async fn update_category(
+    data: web::Data<AppState>,
+    payload: web::Json<UpdateCategoryCommand>,
+) -> impl Responder {
+    handle_api_result(data.update_category.execute(payload.into_inner()).await).await
+}
+
+#[api_operation(summary = "删除分类")]
+pub async fn delete_category(
+    data: web::Data<AppState>,
+    payload: web::Json<DeleteCategoryCommand>,
+) -> impl Responder {
+    handle_api_result(data.delete_category.execute(payload.into_inner()).await).await
+}
+
+#[api_operation(summary = "查询分类树")]
+pub async fn list_category_tree(
+    data: web::Data<AppState>,
+) -> impl Responder {
+    handle_api_result(data.list_category_tree.execute().await).await
+}
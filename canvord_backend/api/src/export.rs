@@ -0,0 +1,244 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+use canvord_service::article_mutation::ArticleMutation;
+use canvord_service::category_query::CategoryQuery;
+use dto::category::CategoryNode;
+use migration::sea_orm::Database;
+use migration::{Migrator, MigratorTrait};
+use pages::{is_path_segment, remove_stale, ArticlePage, Listing, PageMeta, Site};
+use handler::prerender_article_handler::list_article_pages;
+use crate::site::{article_url, site_info, FEED_SIZE, ROBOTS_TXT};
+
+/// 记录上次导出结果，用于增量生成文章页
+const MANIFEST: &str = ".canvord-export";
+
+/// 将公开站点导出为静态文件，可直接托管在对象存储上：`canvord_backend export [输出目录] [--full]`。
+///
+/// 输出目录未通过参数提供时回退到 `EXPORT_DIR` 环境变量。`last_update`、分类和标签都没有变化的文章页
+/// 不会重新生成，`--full` 强制全部重新生成；首页、分类页、标签页、订阅源和 sitemap 每次都会重新生成，
+/// 不再公开的文章、分类和标签对应的目录会被删除。
+#[actix_web::main]
+pub async fn export(args: &[String]) -> std::io::Result<()> {
+    dotenvy::dotenv().ok();
+
+    let full = args.iter().any(|a| a == "--full");
+    let out_dir = args.iter().find(|a| !a.starts_with("--")).cloned()
+        .or_else(|| env::var("EXPORT_DIR").ok())
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "output directory is required"))?;
+    let out_dir = Path::new(&out_dir);
+    let page_size = env::var("EXPORT_PAGE_SIZE").ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&n: &usize| n > 0)
+        .unwrap_or(10);
+
+    let db_url = env::var("DATABASE_URL").expect("DATABASE_URL is not set in .env file");
+    let conn = Database::connect(&db_url).await.map_err(Error::other)?;
+    Migrator::up(&conn, None).await.map_err(Error::other)?;
+
+    // 与服务启动时一致，先用当前的渲染规则更新旧文章
+    crate::init_allowlist();
+    ArticleMutation::render_stale(&conn).await.map_err(Error::other)?;
+
+    let (articles, skipped) = list_article_pages(&conn).await.map_err(Error::other)?;
+    for (id, slug) in skipped {
+        println!("Skipped article {id} with unsafe slug '{slug}'");
+    }
+    let categories = CategoryNode::build_tree(CategoryQuery::list_categories(&conn).await.map_err(Error::other)?);
+
+    let site = site_info();
+    fs::create_dir_all(out_dir)?;
+
+    // 模板、渲染规则或站点信息变化后，上次的记录全部作废
+    let header = fingerprint(&(pages::EXPORT_VERSION, render::VERSION, &site.title, &site.description, &site.url, &site.image));
+    let previous = if full { HashMap::new() } else { read_manifest(out_dir, &header) };
+
+    let all: Vec<ArticlePage> = articles.iter().map(|(_, a)| a.clone()).collect();
+    let mut manifest = format!("{header}\n");
+    let (written, _) = pages::write_articles(out_dir, &all, |article, dir| {
+        let print = fingerprint(&(
+            article.last_update,
+            article.category.as_ref().map(|c| (&c.name, &c.slug)),
+            article.tags.iter().map(|t| (&t.name, &t.slug)).collect::<Vec<_>>(),
+        ));
+        manifest.push_str(&format!("{}\t{print}\n", article.slug));
+
+        let unchanged = previous.get(&article.slug) == Some(&print) && dir.join("index.html").is_file();
+        (!unchanged).then(|| pages::export_article(&site, &article.meta(&site), article))
+    })?;
+
+    let home = PageMeta {
+        title: site.title.clone(),
+        description: site.description.clone(),
+        url: format!("{}/", site.url),
+        article: None,
+    };
+    write_listing(out_dir, &site, &home, &site.title, "/", &all, page_size)?;
+    write_feeds(out_dir, &site, "/", None, &all)?;
+
+    // 分类页包含子分类下的文章
+    let mut category_slugs = HashSet::new();
+    let mut nodes: Vec<&CategoryNode> = categories.iter().collect();
+    while let Some(node) = nodes.pop() {
+        nodes.extend(node.children.iter());
+        let ids = node.subtree_ids();
+        let list: Vec<ArticlePage> = articles.iter()
+            .filter(|(id, _)| id.is_some_and(|id| ids.contains(&id)))
+            .map(|(_, a)| a.clone())
+            .collect();
+        if list.is_empty() || !is_path_segment(&node.slug) {
+            continue;
+        }
+        let base = format!("/category/{}/", node.slug);
+        let meta = listing_meta(&site, &node.name, &node.description, &base);
+        write_listing(out_dir, &site, &meta, &node.name, &base, &list, page_size)?;
+        write_feeds(out_dir, &site, &base, Some(&node.name), &list)?;
+        category_slugs.insert(node.slug.as_str());
+    }
+    remove_stale(&out_dir.join("category"), &category_slugs)?;
+
+    let mut tags: BTreeMap<&str, (&str, Vec<ArticlePage>)> = BTreeMap::new();
+    for (_, article) in &articles {
+        for tag in article.tags.iter().filter(|t| is_path_segment(&t.slug)) {
+            tags.entry(tag.slug.as_str()).or_insert_with(|| (tag.name.as_str(), Vec::new())).1.push(article.clone());
+        }
+    }
+    for (slug, (name, list)) in &tags {
+        let base = format!("/tag/{slug}/");
+        let heading = format!("#{name}");
+        let meta = listing_meta(&site, &heading, "", &base);
+        write_listing(out_dir, &site, &meta, &heading, &base, list, page_size)?;
+        write_feeds(out_dir, &site, &base, Some(name), list)?;
+    }
+    remove_stale(&out_dir.join("tag"), &tags.keys().copied().collect())?;
+
+    let mut urls = Vec::with_capacity(all.len() + 1);
+    urls.push(feed::SitemapUrl {
+        loc: format!("{}/", site.url),
+        lastmod: all.iter().map(|a| a.last_update).max(),
+    });
+    urls.extend(all.iter().map(|a| feed::SitemapUrl { loc: article_url(&a.slug), lastmod: Some(a.last_update) }));
+    let sitemaps = feed::sitemaps(&urls, |n| format!("{}/sitemap-{n}.xml", site.url));
+    fs::write(out_dir.join("sitemap.xml"), &sitemaps.index)?;
+    for (i, part) in sitemaps.parts.iter().enumerate() {
+        fs::write(out_dir.join(format!("sitemap-{}.xml", i + 1)), part)?;
+    }
+    fs::write(out_dir.join("robots.txt"), ROBOTS_TXT.as_str())?;
+    fs::write(out_dir.join("style.css"), pages::STYLE_CSS)?;
+    fs::write(out_dir.join("highlight.css"), render::highlight_css())?;
+
+    // 最后写入记录，中途失败时下次仍会重新生成
+    fs::write(out_dir.join(MANIFEST), manifest)?;
+
+    println!(
+        "Exported {} articles into {}, {written} article pages regenerated",
+        articles.len(),
+        out_dir.display()
+    );
+    Ok(())
+}
+
+fn listing_meta(site: &Site, name: &str, description: &str, base: &str) -> PageMeta {
+    PageMeta {
+        title: format!("{name} - {}", site.title),
+        description: description.to_owned(),
+        url: format!("{}{base}", site.url),
+        article: None,
+    }
+}
+
+/// 分页写入列表页，旧的分页目录先整体删除，避免文章减少后残留多余的页
+fn write_listing(
+    out_dir: &Path,
+    site: &Site,
+    meta: &PageMeta,
+    heading: &str,
+    base: &str,
+    articles: &[ArticlePage],
+    page_size: usize,
+) -> std::io::Result<()> {
+    let dir = out_dir.join(base.trim_matches('/'));
+    let paged = dir.join("page");
+    if paged.is_dir() {
+        fs::remove_dir_all(&paged)?;
+    }
+
+    // 没有文章时仍然生成第一页
+    let chunks: Vec<&[ArticlePage]> = match articles.is_empty() {
+        true => vec![&[]],
+        false => articles.chunks(page_size).collect(),
+    };
+    let pages = chunks.len();
+    for (i, chunk) in chunks.into_iter().enumerate() {
+        let page = i + 1;
+        let path = pages::page_path(base, page);
+        let meta = PageMeta { url: format!("{}{path}", site.url), ..meta.clone() };
+        let listing = Listing { heading, description: &meta.description, base, articles: chunk, page, pages };
+        write_page(&out_dir.join(path.trim_matches('/')), &pages::export_listing(site, &meta, &listing))?;
+    }
+    Ok(())
+}
+
+/// 与服务端的 `/feed.xml`、`/atom.xml` 相同，输出最新的 `FEED_SIZE` 篇文章全文
+fn write_feeds(out_dir: &Path, site: &Site, base: &str, name: Option<&str>, articles: &[ArticlePage]) -> std::io::Result<()> {
+    let entries = articles.iter()
+        .take(*FEED_SIZE as usize)
+        .map(|a| feed::Entry {
+            title: a.title.clone(),
+            link: article_url(&a.slug),
+            summary: a.description.clone(),
+            content: Some(a.content_html.clone()),
+            categories: a.category.iter().chain(&a.tags).map(|t| t.name.clone()).collect(),
+            published: a.created_at,
+            updated: a.last_update,
+        })
+        .collect();
+
+    let mut channel = feed::Channel {
+        title: match name {
+            Some(name) => format!("{} - {name}", site.title),
+            None => site.title.clone(),
+        },
+        description: site.description.clone(),
+        link: site.url.clone(),
+        feed_link: format!("{}{base}feed.xml", site.url),
+        entries,
+    };
+
+    let dir = out_dir.join(base.trim_matches('/'));
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join("feed.xml"), feed::rss(&channel))?;
+    channel.feed_link = format!("{}{base}atom.xml", site.url);
+    fs::write(dir.join("atom.xml"), feed::atom(&channel))
+}
+
+fn write_page(dir: &Path, html: &str) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join("index.html"), html)
+}
+
+/// 第一行为导出配置的指纹，之后每行为 `slug\t文章指纹`；指纹不一致或文件不存在时返回空
+fn read_manifest(out_dir: &Path, header: &str) -> HashMap<String, String> {
+    let Ok(text) = fs::read_to_string(out_dir.join(MANIFEST)) else {
+        return HashMap::new();
+    };
+    let mut lines = text.lines();
+    if lines.next() != Some(header) {
+        return HashMap::new();
+    }
+    lines
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(slug, print)| (slug.to_owned(), print.to_owned()))
+        .collect()
+}
+
+/// 只用于和同一程序上次写入的记录比较，升级后指纹不同只会导致一次全量生成
+fn fingerprint(value: &impl Hash) -> String {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
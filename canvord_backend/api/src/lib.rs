@@ -1,14 +1,22 @@
 mod article_controller;
+mod category_controller;
 mod app_state;
 mod util;
 mod admin_controller;
 mod visitor_controller;
 mod api_info;
+mod bootstrap;
+mod prerender;
+mod scheduler;
+mod export;
+mod media_controller;
+mod site;
+mod sitemap_controller;
 
 use crate::app_state::AppState;
 use crate::article_controller::article_route;
-use actix_files::Files as Fs;
-use actix_web::middleware::Logger;
+use crate::category_controller::category_route;
+use actix_web::middleware::{Compress, Logger};
 use actix_web::{web, HttpResponse, HttpServer};
 use apistos::app::{BuildConfig, OpenApiWrapper};
 use apistos::web::ServiceConfig;
@@ -17,11 +25,19 @@ use migration::sea_orm::Database;
 use migration::{Migrator, MigratorTrait};
 use std::env;
 use std::sync::Arc;
+use once_cell::sync::Lazy;
 use actix_cors::Cors;
 use actix_web::http::header;
 use crate::admin_controller::admin_route;
 use crate::api_info::api_info;
 use crate::visitor_controller::visitor_route;
+use crate::media_controller::media_route;
+use crate::sitemap_controller::sitemap_route;
+use canvord_service::article_mutation::ArticleMutation;
+use canvord_service::article_query::ArticleQuery;
+use search::SearchIndex;
+use middleware::local_cache::LocalCache;
+use media::{LocalMediaStore, MediaStore, S3MediaStore};
 
 #[actix_web::main]
 async fn start() -> std::io::Result<()> {
@@ -46,12 +62,61 @@ async fn start() -> std::io::Result<()> {
     let redis_client = redis::Client::open(redis_url.clone()).unwrap();
     Migrator::up(&conn, None).await.unwrap();
 
-    let app_state = AppState::new(Arc::from(conn), redis_client.clone());
+    init_allowlist();
+
+    // ROBOTS_TXT 指向的文件不存在时在启动阶段报错
+    Lazy::force(&site::ROBOTS_TXT);
+
+    // 渲染规则更新后，旧文章的 HTML 需要重新生成
+    let rendered = ArticleMutation::render_stale(&conn).await.expect("failed to render articles");
+    if rendered > 0 {
+        log::info!("已重新渲染 {rendered} 篇文章");
+    }
+
+    // 全文索引以数据库为准，索引为空或版本变化时整体重建
+    let search_dir = env::var("SEARCH_INDEX_DIR").unwrap_or_else(|_| "./search_index".into());
+    let search_index = SearchIndex::open(&search_dir).expect("failed to open search index");
+    if search_index.is_empty() {
+        let articles = ArticleQuery::list_all_articles(&conn).await.unwrap();
+        search_index.rebuild(articles).await.expect("failed to rebuild search index");
+    }
+
+    let media_max_bytes = env::var("MEDIA_MAX_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(10 * 1024 * 1024);
+
+    // 访客缓存在 Redis 之前的进程内缓存，由所有 worker 共享
+    let local_cache_entries = env::var("LOCAL_CACHE_ENTRIES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(1024);
+    let local_cache_bytes = env::var("LOCAL_CACHE_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(64 * 1024 * 1024);
+    let local_cache = Arc::new(LocalCache::new(local_cache_entries, local_cache_bytes));
+
+    let app_state = AppState::new(
+        Arc::from(conn),
+        redis_client.clone(),
+        Arc::new(search_index),
+        media_store(),
+        media_max_bytes,
+        local_cache.clone(),
+    );
+
+    scheduler::spawn(app_state.clone());
+    prerender::spawn(app_state.clone());
+    let subscriber = redis_client.clone();
+    actix_web::rt::spawn(async move { local_cache.subscribe(subscriber).await });
 
     let server = HttpServer::new(move || {
         actix_web::App::new()
             .document(api_info())
             .app_data(web::Data::new(app_state.clone()))
+            // 按 Accept-Encoding 压缩响应，访客缓存命中时直接返回预先压缩的内容
+            .wrap(Compress::default())
             .wrap(Logger::default())
             .wrap(
                 Cors::default()
@@ -62,12 +127,12 @@ async fn start() -> std::io::Result<()> {
                     .max_age(3600),
             )
             .configure(|cfg| {
-                init_route(cfg, redis_client.clone());
+                init_route(cfg, redis_client.clone(), app_state.local_cache.clone());
             })
             .default_service(web::route().to(|| async {
                 HttpResponse::Ok().body("404 Not Found")
             }))
-            // .service(Fs::new("/static", "./api/static"))
+            // .service(actix_files::Files::new("/static", "./api/static"))
             .build_with(
                     "/openapi.json",
                     BuildConfig::default()
@@ -83,14 +148,57 @@ async fn start() -> std::io::Result<()> {
     Ok(())
 }
 
-fn init_route(cfg: &mut ServiceConfig, redis_client: redis::Client) {
+/// 在默认规则之外放行的标签和属性，需在首次渲染前设置
+fn init_allowlist() {
+    let mut allowlist = render::Allowlist::default();
+    allowlist.extend_from_config(
+        &env::var("HTML_ALLOWED_TAGS").unwrap_or_default(),
+        &env::var("HTML_ALLOWED_ATTRIBUTES").unwrap_or_default(),
+    );
+    render::set_allowlist(allowlist);
+}
+
+/// 按 MEDIA_STORE 选择媒体存储后端，默认保存在本地目录
+fn media_store() -> Arc<dyn MediaStore> {
+    let public_url = env::var("MEDIA_PUBLIC_URL").unwrap_or_else(|_| "/media".into());
+
+    match env::var("MEDIA_STORE").as_deref() {
+        Ok("s3") => {
+            let var = |name: &str| env::var(name).unwrap_or_else(|_| panic!("{name} is not set in .env file"));
+            Arc::new(S3MediaStore::new(
+                &var("S3_ENDPOINT"),
+                &env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".into()),
+                &var("S3_BUCKET"),
+                &var("S3_ACCESS_KEY"),
+                &var("S3_SECRET_KEY"),
+                &public_url,
+            ))
+        }
+        _ => {
+            let dir = env::var("MEDIA_LOCAL_DIR").unwrap_or_else(|_| "./uploads".into());
+            Arc::new(LocalMediaStore::new(dir, public_url).expect("failed to create media directory"))
+        }
+    }
+}
+
+fn init_route(cfg: &mut ServiceConfig, redis_client: redis::Client, local_cache: Arc<LocalCache>) {
     admin_route(cfg);
     article_route(cfg);
-    visitor_route(cfg, redis_client);
+    category_route(cfg);
+    media_route(cfg);
+    visitor_route(cfg, redis_client.clone(), local_cache.clone());
+    sitemap_route(cfg, redis_client, local_cache);
 }
 
 pub fn main() {
-    let result = start();
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("create-admin") => bootstrap::create_admin(&args[1..]),
+        Some("prerender") => prerender::prerender(&args[1..]),
+        Some("export") => export::export(&args[1..]),
+        _ => start(),
+    };
 
     if let Some(err) = result.err() {
         println!("Error: {err}");
@@ -0,0 +1,128 @@
+use crate::app_state::AppState;
+use crate::util::{handle_api_result, validate, AuthenticatedUser};
+use actix_multipart::Multipart;
+use actix_web::http::header::{self, ContentDisposition};
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use apistos::web as aweb;
+use apistos::web::ServiceConfig;
+use apistos::{api_operation, ApiComponent};
+use bytes::Bytes;
+use command::delete_media_command::DeleteMediaCommand;
+use dto::app_error::AppError;
+use dto::app_response::AppResponse;
+use dto::media::MediaInfo;
+use dto::pagination::PageResult;
+use futures_util::StreamExt;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use validator::Validate;
+
+/// 公开访问媒体文件，文章中的图片链接指向这里
+pub fn media_route(cfg: &mut ServiceConfig) {
+    cfg.service(
+        aweb::scope("/media")
+            .route("/{key}", aweb::get().to(find_media_file))
+    );
+}
+
+#[api_operation(summary = "上传媒体文件")]
+pub async fn upload_media(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    payload: Multipart,
+) -> impl Responder {
+    handle_api_result(upload(&data, &req, payload).await).await
+}
+
+#[api_operation(summary = "分页查询媒体文件")]
+pub async fn list_media(
+    data: web::Data<AppState>,
+    query: web::Query<MediaPageParams>,
+) -> impl Responder {
+    let params = query.into_inner();
+    if let Err(e) = validate(&params) {
+        return AppResponse::from_error(&e);
+    }
+
+    match data.list_media_page.execute(params.page, params.per).await {
+        Ok((media, total)) => {
+            let page_result = PageResult {
+                total: total as usize,
+                current: params.page as usize,
+                size: params.per as usize,
+                data: media,
+            };
+            AppResponse::ok(page_result)
+        }
+        Err(e) => AppResponse::from_error(&e),
+    }
+}
+
+#[api_operation(summary = "删除媒体文件")]
+pub async fn delete_media(
+    data: web::Data<AppState>,
+    payload: web::Json<DeleteMediaCommand>,
+) -> impl Responder {
+    handle_api_result(data.delete_media.execute(payload.into_inner()).await).await
+}
+
+#[api_operation(summary = "读取媒体文件")]
+pub async fn find_media_file(
+    data: web::Data<AppState>,
+    key: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let key = key.into_inner();
+    let (content_type, body) = data.find_media_file.execute(key.clone()).await?;
+
+    // key 随上传生成且内容不会变化，可以长期缓存
+    let mut response = HttpResponse::Ok();
+    response
+        .insert_header((header::CACHE_CONTROL, "public, max-age=31536000, immutable"))
+        .insert_header(("X-Content-Type-Options", "nosniff"));
+    // PDF 可能带有脚本，只允许下载，不在站点的源下由浏览器直接打开
+    if content_type == "application/pdf" {
+        response.insert_header(ContentDisposition::attachment(key));
+    }
+    Ok(response.insert_header((header::CONTENT_TYPE, content_type)).body(body))
+}
+
+async fn upload(data: &AppState, req: &HttpRequest, mut payload: Multipart) -> Result<MediaInfo, AppError> {
+    let owner_id = AuthenticatedUser::of(req)?.id()?;
+
+    let handler = &data.upload_media;
+    let max_bytes = handler.max_bytes();
+
+    // 只处理名为 file 的字段，边读边检查大小，超限立即中止
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(|e| AppError::BadRequest(e.to_string()))?;
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let file_name = field.content_disposition()
+            .and_then(|cd| cd.get_filename())
+            .unwrap_or_default()
+            .to_owned();
+
+        let mut buf = Vec::new();
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(|e| AppError::BadRequest(e.to_string()))?;
+            if buf.len() + chunk.len() > max_bytes {
+                return Err(AppError::BadRequest(format!("file exceeds {max_bytes} bytes")));
+            }
+            buf.extend_from_slice(&chunk);
+        }
+
+        return handler.execute(owner_id, file_name, Bytes::from(buf)).await;
+    }
+
+    Err(AppError::BadRequest("missing file field".into()))
+}
+
+#[derive(Debug, Deserialize, Validate, JsonSchema, ApiComponent)]
+pub struct MediaPageParams {
+    #[validate(range(min = 1))]
+    pub page: u64,
+    #[validate(range(min = 1, max = 100))]
+    pub per: u64,
+}
@@ -0,0 +1,79 @@
+use std::env;
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+use handler::prerender_article_handler::{list_article_pages, PrerenderTarget};
+use migration::sea_orm::Database;
+use migration::{Migrator, MigratorTrait};
+use pages::{ArticleLink, PageMeta};
+use crate::app_state::AppState;
+use crate::site::site_info;
+
+/// 为爬虫和不执行 JavaScript 的读者预渲染访客页面：`canvord_backend prerender [index.html] [输出目录]`。
+///
+/// 模板为访客客户端构建出的 `index.html`，未通过参数提供时回退到 `PRERENDER_TEMPLATE` / `PRERENDER_DIR` 环境变量。
+/// 输出目录中的 `index.html` 为首页，`article/{slug}/index.html` 为文章页；
+/// 不再公开的文章对应的目录会被删除。服务运行时配置了这两个环境变量的话，文章变化后会自动更新对应页面，
+/// 这里用于首次生成或模板变化后全部重新生成。
+#[actix_web::main]
+pub async fn prerender(args: &[String]) -> std::io::Result<()> {
+    dotenvy::dotenv().ok();
+
+    let template = args.first().cloned()
+        .or_else(|| env::var("PRERENDER_TEMPLATE").ok())
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "template is required"))?;
+    let out_dir = args.get(1).cloned()
+        .or_else(|| env::var("PRERENDER_DIR").ok())
+        .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "output directory is required"))?;
+    let template = fs::read_to_string(&template)?;
+    let out_dir = Path::new(&out_dir);
+
+    let db_url = env::var("DATABASE_URL").expect("DATABASE_URL is not set in .env file");
+    let conn = Database::connect(&db_url).await.map_err(Error::other)?;
+    Migrator::up(&conn, None).await.map_err(Error::other)?;
+
+    let (articles, skipped) = list_article_pages(&conn).await.map_err(Error::other)?;
+    for (id, slug) in skipped {
+        println!("Skipped article {id} with unsafe slug '{slug}'");
+    }
+    let articles: Vec<_> = articles.into_iter().map(|(_, a)| a).collect();
+
+    let site = site_info();
+    let (_, removed) = pages::write_articles(out_dir, &articles, |article, _| {
+        Some(pages::prerender(&template, &site, &article.meta(&site), &pages::article_body(article)))
+    })?;
+
+    let home = PageMeta {
+        title: site.title.clone(),
+        description: site.description.clone(),
+        url: format!("{}/", site.url),
+        article: None,
+    };
+    fs::write(out_dir.join("index.html"), pages::prerender(&template, &site, &home, &pages::home_body(&site, &articles.iter().map(ArticleLink::from).collect::<Vec<_>>())))?;
+
+    println!("Prerendered {} articles into {}, removed {removed}", articles.len(), out_dir.display());
+    Ok(())
+}
+
+/// 同时配置了 `PRERENDER_TEMPLATE` 和 `PRERENDER_DIR` 时，服务在文章变化后自动更新预渲染页面
+pub(crate) fn prerender_target() -> Option<PrerenderTarget> {
+    let var = |name| env::var(name).ok().filter(|v: &String| !v.is_empty());
+    let (template, out_dir) = (var("PRERENDER_TEMPLATE")?, var("PRERENDER_DIR")?);
+    Some(PrerenderTarget {
+        template: fs::read_to_string(&template).unwrap_or_else(|e| panic!("failed to read {template}: {e}")),
+        out_dir: out_dir.into(),
+        site: site_info(),
+    })
+}
+
+/// 后台处理文章和分类修改时登记的预渲染任务，同一时间登记的 slug 合并成一次更新
+pub fn spawn(data: AppState) {
+    actix_web::rt::spawn(async move {
+        loop {
+            let slugs = data.prerender_queue.pop().await;
+            if let Err(e) = data.prerender_article.execute(&slugs).await {
+                log::error!("更新预渲染页面失败: {e}");
+            }
+        }
+    });
+}
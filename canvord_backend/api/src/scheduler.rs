@@ -0,0 +1,36 @@
+use std::time::Duration;
+use chrono::Utc;
+use crate::app_state::AppState;
+
+/// 没有待发布文章时的检查间隔，也用于发现其它实例新设置的定时发布
+const IDLE_INTERVAL: Duration = Duration::from_secs(60);
+/// 发布失败时避免立即重试
+const MIN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 后台发布到期的定时文章。任务本身不保存状态，每次都从数据库读取待发布的文章，重启后会立即补发错过的文章
+pub fn spawn(data: AppState) {
+    actix_web::rt::spawn(async move {
+        loop {
+            match data.publish_scheduled.execute().await {
+                Ok(published) if !published.is_empty() => {
+                    for article in &published {
+                        log::info!("已定时发布文章 {} ({})", article.id, article.slug);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => log::error!("定时发布失败: {e}"),
+            }
+
+            let wait = match data.publish_scheduled.next_publish_at().await {
+                Ok(Some(at)) => (at - Utc::now().naive_utc()).to_std().unwrap_or_default().clamp(MIN_INTERVAL, IDLE_INTERVAL),
+                Ok(None) => IDLE_INTERVAL,
+                Err(e) => {
+                    log::error!("查询定时发布失败: {e}");
+                    IDLE_INTERVAL
+                }
+            };
+            // 新设置或修改定时发布时提前唤醒
+            let _ = tokio::time::timeout(wait, data.scheduler.notified()).await;
+        }
+    });
+}
@@ -0,0 +1,73 @@
+use std::{env, fs};
+use once_cell::sync::Lazy;
+
+/// 访客站点的公开地址，订阅源中的文章链接以此为前缀
+pub static SITE_URL: Lazy<String> = Lazy::new(|| {
+    dotenvy::dotenv().ok();
+    env::var("SITE_URL")
+        .unwrap_or_else(|_| "http://localhost:8082".into())
+        .trim_end_matches('/')
+        .to_owned()
+});
+
+/// 接口的公开地址，订阅源的自身链接以此为前缀；未配置时与 `SITE_URL` 相同。
+///
+/// 不从请求的 `Host` 推断，否则伪造的请求头会随响应一起写入缓存
+pub static API_URL: Lazy<String> = Lazy::new(|| {
+    dotenvy::dotenv().ok();
+    env::var("API_URL")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .map(|v| v.trim_end_matches('/').to_owned())
+        .unwrap_or_else(|| SITE_URL.clone())
+});
+
+pub static SITE_TITLE: Lazy<String> = Lazy::new(|| {
+    dotenvy::dotenv().ok();
+    env::var("SITE_TITLE").unwrap_or_else(|_| "Canvord".into())
+});
+
+pub static SITE_DESCRIPTION: Lazy<String> = Lazy::new(|| {
+    dotenvy::dotenv().ok();
+    env::var("SITE_DESCRIPTION").unwrap_or_default()
+});
+
+/// 分享卡片使用的图片地址，未配置时不输出图片
+pub static SITE_IMAGE: Lazy<Option<String>> = Lazy::new(|| {
+    dotenvy::dotenv().ok();
+    env::var("SITE_IMAGE").ok().filter(|v| !v.is_empty())
+});
+
+/// 每个订阅源包含的最新文章数
+pub static FEED_SIZE: Lazy<u64> = Lazy::new(|| {
+    dotenvy::dotenv().ok();
+    env::var("FEED_SIZE").ok().and_then(|v| v.parse().ok()).unwrap_or(20)
+});
+
+/// `ROBOTS_TXT` 指向的文件内容，未配置时允许抓取全部页面；文件中没有 `Sitemap:` 时自动追加。
+///
+/// 隐藏和未发布的文章不会出现在 sitemap 中，也不在这里逐条 `Disallow`，以免暴露它们的 slug
+pub static ROBOTS_TXT: Lazy<String> = Lazy::new(|| {
+    dotenvy::dotenv().ok();
+    let mut robots = match env::var("ROBOTS_TXT").ok().filter(|p| !p.is_empty()) {
+        Some(path) => fs::read_to_string(&path).unwrap_or_else(|e| panic!("failed to read {path}: {e}")),
+        None => "User-agent: *\nAllow: /\n".to_owned(),
+    };
+    if !robots.lines().any(|l| l.trim_start().to_ascii_lowercase().starts_with("sitemap:")) {
+        robots.push_str(&format!("\nSitemap: {}/sitemap.xml\n", *SITE_URL));
+    }
+    robots
+});
+
+pub fn site_info() -> pages::Site {
+    pages::Site {
+        title: SITE_TITLE.clone(),
+        description: SITE_DESCRIPTION.clone(),
+        url: SITE_URL.clone(),
+        image: SITE_IMAGE.clone(),
+    }
+}
+
+pub fn article_url(slug: &str) -> String {
+    format!("{}/article/{slug}", *SITE_URL)
+}
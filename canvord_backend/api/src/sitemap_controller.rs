@@ -0,0 +1,80 @@
+use std::sync::Arc;
+use actix_web::http::header;
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
+use apistos::api_operation;
+use apistos::web as aweb;
+use apistos::web::ServiceConfig;
+use dto::app_error::AppError;
+use middleware::cache::{CacheMiddleware, CacheTag, CacheTags};
+use middleware::local_cache::LocalCache;
+use crate::app_state::AppState;
+use crate::site::ROBOTS_TXT;
+
+/// 搜索引擎只在站点根目录查找这些文件，需由访客站点的域名反向代理到这里
+pub fn sitemap_route(cfg: &mut ServiceConfig, redis_client: redis::Client, local_cache: Arc<LocalCache>) {
+    // 与 /visitor 共用 Redis 缓存，文章变化时按列表标签清除，所有实例都能看到
+    let cache = || {
+        CacheMiddleware::new(redis_client.clone())
+            .with_ttl(3600)
+            .with_local(local_cache.clone())
+    };
+    cfg.service(
+        aweb::resource("/sitemap.xml")
+            .wrap(cache())
+            .route(aweb::get().to(sitemap))
+    );
+    cfg.service(
+        aweb::resource("/sitemap-{n}.xml")
+            .wrap(cache())
+            .route(aweb::get().to(sitemap_part))
+    );
+    cfg.service(
+        aweb::resource("/robots.txt")
+            .route(aweb::get().to(robots_txt))
+    );
+}
+
+#[api_operation(summary = "站点地图，文章超过 50000 篇时为索引")]
+pub async fn sitemap(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+) -> Result<HttpResponse, AppError> {
+    tag_listing(&req);
+    let sitemaps = data.generate_sitemap.execute().await?;
+    Ok(xml_response(sitemaps.index))
+}
+
+#[api_operation(summary = "站点地图分片")]
+pub async fn sitemap_part(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    n: web::Path<usize>,
+) -> Result<HttpResponse, AppError> {
+    tag_listing(&req);
+    let mut sitemaps = data.generate_sitemap.execute().await?;
+    let index = n.into_inner()
+        .checked_sub(1)
+        .filter(|i| *i < sitemaps.parts.len())
+        .ok_or_else(|| AppError::NotFound("sitemap not found".into()))?;
+    Ok(xml_response(sitemaps.parts.swap_remove(index)))
+}
+
+#[api_operation(summary = "robots.txt")]
+pub async fn robots_txt() -> impl Responder {
+    HttpResponse::Ok()
+        .insert_header((header::CONTENT_TYPE, "text/plain; charset=utf-8"))
+        .insert_header((header::CACHE_CONTROL, "public, max-age=86400"))
+        .body(ROBOTS_TXT.as_str())
+}
+
+/// sitemap 包含所有已发布文章，任何文章发布、隐藏或删除后都需要清除
+fn tag_listing(req: &HttpRequest) {
+    req.extensions_mut().insert(CacheTags(vec![CacheTag::Listing]));
+}
+
+fn xml_response(body: String) -> HttpResponse {
+    HttpResponse::Ok()
+        .insert_header((header::CONTENT_TYPE, feed::SITEMAP_CONTENT_TYPE))
+        .insert_header((header::CACHE_CONTROL, "public, max-age=3600"))
+        .body(body)
+}
@@ -5,6 +5,13 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 use actix_web::{dev::{ServiceRequest, ServiceResponse, Transform, Service, Payload}, Error, HttpMessage, FromRequest, HttpRequest};
+use actix_web::http::header::HeaderMap;
+use actix_web::web;
+use dto::auth::TokenPair;
+use entity::user::{Permission, Role};
+use middleware::connection::RedisConnection;
+use redis::AsyncCommands;
+use crate::app_state::AppState;
 use futures_util::future::{LocalBoxFuture, Ready, ready};
 use once_cell::sync::Lazy;
 use jsonwebtoken::{decode, DecodingKey, Validation, Algorithm, encode, Header, EncodingKey,};
@@ -26,7 +33,18 @@ pub fn validate<T: Validate>(val: &T) -> Result<(), AppError> {
     val.validate().map_err(AppError::from)
 }
 
-pub struct JwtAuth;
+/// JWT 认证中间件，可选地要求用户角色具备某项权限
+#[derive(Clone, Copy, Default)]
+pub struct JwtAuth {
+    permission: Option<Permission>,
+}
+
+impl JwtAuth {
+    /// 校验 token 有效，且角色拥有 `permission`，否则返回 403
+    pub fn require(permission: Permission) -> Self {
+        Self { permission: Some(permission) }
+    }
+}
 
 impl<S, B> Transform<S, ServiceRequest> for JwtAuth
 where
@@ -42,12 +60,14 @@ where
     fn new_transform(&self, service: S) -> Self::Future {
         ready(Ok(JwtAuthMiddleware {
             service: Rc::new(service),
+            permission: self.permission,
         }))
     }
 }
 
 pub struct JwtAuthMiddleware<S> {
     service: Rc<S>,
+    permission: Option<Permission>,
 }
 
 impl<S, B> Service<ServiceRequest> for JwtAuthMiddleware<S>
@@ -65,58 +85,110 @@ where
 
     fn call(&self, req: ServiceRequest) -> Self::Future {
         let service = Rc::clone(&self.service);
+        let permission = self.permission;
 
         Box::pin(async move {
-            if let Some(auth_header) = req.headers().get("Authorization") {
-                if let Ok(auth_str) = auth_header.to_str() {
-                    if let Some(token) = auth_str.strip_prefix("Bearer ") {
-                        if let Ok(data) = decode::<Claims>(
-                            token,
-                            &DecodingKey::from_secret(SECRET_KEY.as_ref()),
-                            &Validation::new(Algorithm::HS256),
-                        ) {
-                            let claims = data.claims;
-                            req.extensions_mut().insert(AuthenticatedUser {
-                                user_id: claims.sub,
-                                role: claims.role,
-                            });
-                        }
-                    }
+            // ❗️如果没有用户信息，拒绝请求
+            let Some(claims) = decode_bearer(req.headers()).filter(|c| c.typ == TokenType::Access) else {
+                return Err(AppError::Unauthorized("Missing or invalid token".into()).into());
+            };
+
+            let state = req.app_data::<web::Data<AppState>>().cloned().ok_or(AppError::InternalError)?;
+            // 无法确认是否已吊销时拒绝请求（fail closed）：退出登录的 token 在 Redis 故障期间不能继续使用。
+            // 受保护的只有后台接口，访客页面不受影响；熔断期间立即返回 503，不会挂起请求
+            match is_token_revoked(&state.redis, &claims.jti).await {
+                Ok(false) => {}
+                Ok(true) => return Err(AppError::Unauthorized("Token has been revoked".into()).into()),
+                Err(e) => {
+                    log::error!("failed to check token revocation: {e}");
+                    return Err(token_store_unavailable().into());
                 }
             }
 
-            // ❗️如果没有用户信息，拒绝请求
-            if req.extensions().get::<AuthenticatedUser>().is_none() {
-                return Err(actix_web::error::ErrorUnauthorized("Missing or invalid token"));
+            if let Some(permission) = permission
+                && !claims.role.has_permission(permission)
+            {
+                return Err(AppError::Forbidden(format!("{:?} is not allowed for role {:?}", permission, claims.role)).into());
             }
 
+            req.extensions_mut().insert(AuthenticatedUser {
+                user_id: claims.sub,
+                role: claims.role,
+                jti: claims.jti,
+                exp: claims.exp,
+            });
+
             service.call(req).await
         })
     }
 }
 
+fn decode_bearer(headers: &HeaderMap) -> Option<Claims> {
+    let token = headers
+        .get("Authorization")?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")?;
+
+    decode_token(token)
+}
+
+pub fn decode_token(token: &str) -> Option<Claims> {
+    decode::<Claims>(
+        token,
+        &DecodingKey::from_secret(SECRET_KEY.as_ref()),
+        &Validation::new(Algorithm::HS256),
+    )
+    .ok()
+    .map(|data| data.claims)
+}
+
 
 static SECRET_KEY: Lazy<String> = Lazy::new(|| {
     dotenvy::dotenv().ok(); // 加载 .env 文件
     env::var("JWT_SECRET").expect("JWT_SECRET must be set")
 });
 
+static ACCESS_TOKEN_TTL: Lazy<Duration> = Lazy::new(|| {
+    dotenvy::dotenv().ok();
+    let minutes = env::var("JWT_ACCESS_TTL_MINUTES").ok().and_then(|v| v.parse().ok()).unwrap_or(30);
+    Duration::minutes(minutes)
+});
+
+static REFRESH_TOKEN_TTL: Lazy<Duration> = Lazy::new(|| {
+    dotenvy::dotenv().ok();
+    let days = env::var("JWT_REFRESH_TTL_DAYS").ok().and_then(|v| v.parse().ok()).unwrap_or(7);
+    Duration::days(days)
+});
+
+const REVOKED_TOKEN_PREFIX: &str = "revoked_token:";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TokenType {
+    Access,
+    Refresh,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String,
-    pub role: String,
+    pub role: Role,
+    pub jti: String,
+    pub typ: TokenType,
     pub exp: usize,
 }
 
-pub fn create_jwt(user_id: &str, role: &str) -> Result<String, jsonwebtoken::errors::Error> {
+fn create_jwt(user_id: &str, role: Role, typ: TokenType, ttl: Duration) -> Result<String, jsonwebtoken::errors::Error> {
     let expiration = Utc::now()
-        .checked_add_signed(Duration::hours(1))
+        .checked_add_signed(ttl)
         .unwrap()
         .timestamp() as usize;
 
     let claims = Claims {
         sub: user_id.to_owned(),
-        role: role.to_owned(),
+        role,
+        jti: uuid::Uuid::new_v4().to_string(),
+        typ,
         exp: expiration,
     };
 
@@ -127,34 +199,86 @@ pub fn create_jwt(user_id: &str, role: &str) -> Result<String, jsonwebtoken::err
     )
 }
 
+/// 同时签发短期 access token 与长期 refresh token
+pub fn create_token_pair(user_id: &str, role: Role) -> Result<TokenPair, jsonwebtoken::errors::Error> {
+    Ok(TokenPair {
+        access_token: create_jwt(user_id, role, TokenType::Access, *ACCESS_TOKEN_TTL)?,
+        refresh_token: create_jwt(user_id, role, TokenType::Refresh, *REFRESH_TOKEN_TTL)?,
+        expires_in: ACCESS_TOKEN_TTL.num_seconds(),
+    })
+}
+
+/// 黑名单读写失败时返回给客户端的错误
+pub fn token_store_unavailable() -> AppError {
+    AppError::ServiceUnavailable("Token store is unavailable".into())
+}
+
+/// 命令出错时让共享连接进入熔断
+fn tracked<T>(redis: &RedisConnection, result: redis::RedisResult<T>) -> redis::RedisResult<T> {
+    if let Err(e) = &result {
+        redis.failed(e);
+    }
+    result
+}
+
+/// 将 token 的 jti 写入 Redis 黑名单，过期时间与 token 剩余有效期一致
+pub async fn revoke_token(redis: &RedisConnection, jti: &str, exp: usize) -> redis::RedisResult<()> {
+    let ttl = (exp as i64 - Utc::now().timestamp()).max(1) as u64;
+    let mut conn = redis.try_connection().await?;
+    tracked(redis, conn.set_ex(format!("{REVOKED_TOKEN_PREFIX}{jti}"), 1, ttl).await)
+}
+
+/// 原子地将 jti 写入黑名单，已在黑名单中时返回 `false`，用于保证 refresh token 只能使用一次
+pub async fn claim_token(redis: &RedisConnection, jti: &str, exp: usize) -> redis::RedisResult<bool> {
+    let ttl = (exp as i64 - Utc::now().timestamp()).max(1) as u64;
+    let mut conn = redis.try_connection().await?;
+    let claimed: Option<String> = tracked(redis, redis::cmd("SET")
+        .arg(format!("{REVOKED_TOKEN_PREFIX}{jti}"))
+        .arg(1)
+        .arg("NX")
+        .arg("EX")
+        .arg(ttl)
+        .query_async(&mut conn)
+        .await)?;
+    Ok(claimed.is_some())
+}
+
+pub async fn is_token_revoked(redis: &RedisConnection, jti: &str) -> redis::RedisResult<bool> {
+    let mut conn = redis.try_connection().await?;
+    tracked(redis, conn.exists(format!("{REVOKED_TOKEN_PREFIX}{jti}")).await)
+}
+
+#[derive(Clone)]
 pub struct AuthenticatedUser {
     pub user_id: String,
-    pub role: String,
+    pub role: Role,
+    pub jti: String,
+    pub exp: usize,
+}
+
+impl AuthenticatedUser {
+    /// 由 `JwtAuth` 写入请求扩展的当前用户
+    pub fn of(req: &HttpRequest) -> Result<Self, AppError> {
+        req.extensions()
+            .get::<AuthenticatedUser>()
+            .cloned()
+            .ok_or_else(|| AppError::Unauthorized("Invalid or missing token".into()))
+    }
+
+    pub fn id(&self) -> Result<i64, AppError> {
+        self.user_id.parse().map_err(|_| AppError::Unauthorized("Invalid or missing token".into()))
+    }
 }
 
 impl FromRequest for AuthenticatedUser {
     type Error = Error;
     type Future = Ready<Result<Self, Self::Error>>;
 
+    /// 由 `JwtAuth` 写入请求扩展，未经过该中间件的路由无法提取
     fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
-        if let Some(auth_header) = req.headers().get("Authorization") {
-            if let Ok(auth_str) = auth_header.to_str() {
-                if let Some(token) = auth_str.strip_prefix("Bearer ") {
-                    if let Ok(data) = decode::<Claims>(
-                        token,
-                        &DecodingKey::from_secret(SECRET_KEY.as_ref()),
-                        &Validation::new(Algorithm::HS256),
-                    ) {
-                        let claims = data.claims;
-                        return ready(Ok(AuthenticatedUser {
-                            user_id: claims.sub,
-                            role: claims.role,
-                        }));
-                    }
-                }
-            }
+        match req.extensions().get::<AuthenticatedUser>() {
+            Some(user) => ready(Ok(user.clone())),
+            None => ready(Err(AppError::Unauthorized("Invalid or missing token".into()).into())),
         }
-
-        ready(Err(actix_web::error::ErrorUnauthorized("Invalid or missing token")))
     }
 }
\ No newline at end of file
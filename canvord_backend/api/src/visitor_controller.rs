@@ -1,23 +1,34 @@
-use actix_web::{web, Responder};
+use std::sync::Arc;
+use std::time::SystemTime;
+use actix_web::http::header::{self, HttpDate, LastModified};
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
 use apistos::{web as aweb, ApiComponent};
 use apistos::api_operation;
 use apistos::web::ServiceConfig;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use validator::Validate;
+use dto::app_error::AppError;
 use dto::app_response::AppResponse;
+use dto::article::ArticleDetail;
 use dto::pagination::PageResult;
 use entity::article::Status;
-use middleware::cache::CacheMiddleware;
+use handler::list_feed_articles_handler::FeedScope;
+use middleware::cache::{CacheMiddleware, CacheTag, CacheTags};
+use middleware::local_cache::LocalCache;
 use crate::app_state::AppState;
+use crate::article_controller::SearchParams;
+use crate::site::{article_url, API_URL, FEED_SIZE, SITE_DESCRIPTION, SITE_TITLE, SITE_URL};
 use crate::util::{handle_api_result, validate};
 
-pub fn visitor_route(cfg: &mut ServiceConfig, redis_client: redis::Client) {
+pub fn visitor_route(cfg: &mut ServiceConfig, redis_client: redis::Client, local_cache: Arc<LocalCache>) {
     cfg.service(
         aweb::scope("/visitor")
             .wrap(
                 CacheMiddleware::new(redis_client)
                     .with_ttl(666)
+                    .with_stale(120)
+                    .with_local(local_cache)
                     .with_key_gen(|req| {
                         let uri = req.uri();
                         let path = uri.path();
@@ -41,28 +52,69 @@ pub fn visitor_route(cfg: &mut ServiceConfig, redis_client: redis::Client) {
             .route("/slug/{slug}", aweb::get().to(find_article_by_slug))
             .route("/title/{title}", aweb::get().to(list_article_by_title))
             .route("/page", aweb::get().to(list_articles))
+            .route("/articles", aweb::get().to(list_articles_by_cursor))
+            .route("/search", aweb::get().to(search_articles))
+            .route("/tags", aweb::get().to(list_tags))
+            .route("/tag/{slug}/page", aweb::get().to(list_articles_by_tag))
+            .route("/categories", aweb::get().to(list_categories))
+            .route("/category/{slug}/page", aweb::get().to(list_articles_by_category))
+            .route("/feed.xml", aweb::get().to(rss_feed))
+            .route("/atom.xml", aweb::get().to(atom_feed))
+            .route("/category/{slug}/feed.xml", aweb::get().to(rss_feed_by_category))
+            .route("/category/{slug}/atom.xml", aweb::get().to(atom_feed_by_category))
+            .route("/tag/{slug}/feed.xml", aweb::get().to(rss_feed_by_tag))
+            .route("/tag/{slug}/atom.xml", aweb::get().to(atom_feed_by_tag))
+    );
+    // 样式表由客户端直接引用，不在 /visitor 之下
+    cfg.service(
+        aweb::resource("/highlight.css")
+            .route(aweb::get().to(highlight_css))
     );
 }
 
+#[api_operation(summary = "代码高亮样式")]
+pub async fn highlight_css() -> impl Responder {
+    HttpResponse::Ok()
+        .insert_header((header::CONTENT_TYPE, "text/css; charset=utf-8"))
+        .insert_header((header::CACHE_CONTROL, "public, max-age=86400"))
+        .body(render::highlight_css())
+}
+
 #[api_operation(summary = "根据Slug查询公开文章")]
 pub async fn find_article_by_slug(
     data: web::Data<AppState>,
+    req: HttpRequest,
     slug: web::Path<String>,
 ) -> impl Responder {
-    handle_api_result(data.find_publish_by_slug.execute(slug.clone()).await).await
+    let result = data.find_publish_by_slug.execute(slug.clone()).await;
+    // 文章不存在时同样按 slug 记录，发布后立即可见
+    let id = result.as_ref().ok().map(|a| a.meta.id);
+    let last_update = result.as_ref().ok().map(|a| a.meta.last_update);
+    tag_cache(&req, [CacheTag::Slug(slug.into_inner()), CacheTag::Categories].into_iter().chain(id.map(CacheTag::Article)));
+
+    // 缓存层按该头回应 If-Modified-Since，列表类响应没有可靠的修改时间，不设置
+    let mut response = handle_api_result(result).await.customize();
+    if let Some(last_update) = last_update {
+        response = response.insert_header(LastModified(HttpDate::from(SystemTime::from(last_update.and_utc()))));
+    }
+    response
 }
 
 #[api_operation(summary = "根据Title查询公开文章")]
 pub async fn list_article_by_title(
     data: web::Data<AppState>,
+    req: HttpRequest,
     title: web::Path<String>,
 ) -> impl Responder {
-    handle_api_result(data.list_publish_by_title.execute(title.clone()).await).await
+    let result = data.list_publish_by_title.execute(title.clone()).await;
+    tag_listing(&req, result.iter().flatten().map(|a| a.id));
+    handle_api_result(result).await
 }
 
 #[api_operation(summary = "分页查询公开文章")]
 pub async fn list_articles(
     data: web::Data<AppState>,
+    req: HttpRequest,
     query: web::Query<ArticlePageParams>,
 ) -> impl Responder {
     let params = query.into_inner();
@@ -71,7 +123,9 @@ pub async fn list_articles(
     }
 
     let handler = &data.list_by_status_page;
-    match handler.execute(params.page, params.per, Option::from(Status::Published)).await {
+    let result = handler.execute(params.page, params.per, Option::from(Status::Published)).await;
+    tag_listing(&req, result.iter().flat_map(|(articles, _)| articles).map(|a| a.id));
+    match result {
         Ok((articles, total)) => {
 
             let page_result = PageResult {
@@ -86,10 +140,283 @@ pub async fn list_articles(
     }
 }
 
+#[api_operation(summary = "按游标分页查询公开文章")]
+pub async fn list_articles_by_cursor(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<ArticleCursorParams>,
+) -> impl Responder {
+    let params = query.into_inner();
+    if let Err(e) = validate(&params) {
+        return AppResponse::from_error(&e);
+    }
+
+    let handler = &data.list_by_cursor;
+    let result = handler.execute(params.cursor, params.per, Option::from(Status::Published)).await;
+    tag_listing(&req, result.iter().flat_map(|page| &page.data).map(|a| a.id));
+    handle_api_result(result).await
+}
+
+#[api_operation(summary = "全文搜索公开文章")]
+pub async fn search_articles(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<SearchParams>,
+) -> impl Responder {
+    let params = query.into_inner();
+    if let Err(e) = validate(&params) {
+        return AppResponse::from_error(&e);
+    }
+
+    let handler = &data.search_articles;
+    let result = handler.execute(params.q, params.page, params.per, true).await;
+    tag_listing(&req, result.iter().flat_map(|(hits, _)| hits).map(|h| h.meta.id));
+    match result {
+        Ok((hits, total)) => {
+            let page_result = PageResult {
+                total: total as usize,
+                current: params.page as usize,
+                size: params.per as usize,
+                data: hits,
+            };
+            AppResponse::ok(page_result)
+        }
+        Err(e) => AppResponse::from_error(&e),
+    }
+}
+
+#[api_operation(summary = "查询标签及其公开文章数")]
+pub async fn list_tags(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+) -> impl Responder {
+    tag_listing(&req, []);
+    handle_api_result(data.list_tags.execute().await).await
+}
+
+#[api_operation(summary = "按标签分页查询公开文章")]
+pub async fn list_articles_by_tag(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    slug: web::Path<String>,
+    query: web::Query<ArticlePageParams>,
+) -> impl Responder {
+    let params = query.into_inner();
+    if let Err(e) = validate(&params) {
+        return AppResponse::from_error(&e);
+    }
+
+    let handler = &data.list_publish_by_tag_page;
+    let result = handler.execute(slug.into_inner(), params.page, params.per).await;
+    tag_listing(&req, result.iter().flat_map(|(articles, _)| articles).map(|a| a.id));
+    match result {
+        Ok((articles, total)) => {
+            let page_result = PageResult {
+                total: total as usize,
+                current: params.page as usize,
+                size: params.per as usize,
+                data: articles,
+            };
+            AppResponse::ok(page_result)
+        }
+        Err(e) => AppResponse::from_error(&e),
+    }
+}
+
+#[api_operation(summary = "查询分类树")]
+pub async fn list_categories(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+) -> impl Responder {
+    tag_listing(&req, []);
+    handle_api_result(data.list_category_tree.execute().await).await
+}
+
+#[api_operation(summary = "按分类分页查询公开文章（含子分类）")]
+pub async fn list_articles_by_category(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    slug: web::Path<String>,
+    query: web::Query<ArticlePageParams>,
+) -> impl Responder {
+    let params = query.into_inner();
+    if let Err(e) = validate(&params) {
+        return AppResponse::from_error(&e);
+    }
+
+    let handler = &data.list_publish_by_category_page;
+    let result = handler.execute(slug.into_inner(), params.page, params.per).await;
+    tag_listing(&req, result.iter().flat_map(|(articles, _)| articles).map(|a| a.id));
+    match result {
+        Ok((articles, total)) => {
+            let page_result = PageResult {
+                total: total as usize,
+                current: params.page as usize,
+                size: params.per as usize,
+                data: articles,
+            };
+            AppResponse::ok(page_result)
+        }
+        Err(e) => AppResponse::from_error(&e),
+    }
+}
+
+#[api_operation(summary = "RSS 订阅")]
+pub async fn rss_feed(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<FeedParams>,
+) -> Result<HttpResponse, AppError> {
+    feed_response(&data, &req, FeedScope::All, query.content, FeedFormat::Rss).await
+}
+
+#[api_operation(summary = "Atom 订阅")]
+pub async fn atom_feed(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    query: web::Query<FeedParams>,
+) -> Result<HttpResponse, AppError> {
+    feed_response(&data, &req, FeedScope::All, query.content, FeedFormat::Atom).await
+}
+
+#[api_operation(summary = "按分类 RSS 订阅（含子分类）")]
+pub async fn rss_feed_by_category(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    slug: web::Path<String>,
+    query: web::Query<FeedParams>,
+) -> Result<HttpResponse, AppError> {
+    feed_response(&data, &req, FeedScope::Category(slug.into_inner()), query.content, FeedFormat::Rss).await
+}
+
+#[api_operation(summary = "按分类 Atom 订阅（含子分类）")]
+pub async fn atom_feed_by_category(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    slug: web::Path<String>,
+    query: web::Query<FeedParams>,
+) -> Result<HttpResponse, AppError> {
+    feed_response(&data, &req, FeedScope::Category(slug.into_inner()), query.content, FeedFormat::Atom).await
+}
+
+#[api_operation(summary = "按标签 RSS 订阅")]
+pub async fn rss_feed_by_tag(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    slug: web::Path<String>,
+    query: web::Query<FeedParams>,
+) -> Result<HttpResponse, AppError> {
+    feed_response(&data, &req, FeedScope::Tag(slug.into_inner()), query.content, FeedFormat::Rss).await
+}
+
+#[api_operation(summary = "按标签 Atom 订阅")]
+pub async fn atom_feed_by_tag(
+    data: web::Data<AppState>,
+    req: HttpRequest,
+    slug: web::Path<String>,
+    query: web::Query<FeedParams>,
+) -> Result<HttpResponse, AppError> {
+    feed_response(&data, &req, FeedScope::Tag(slug.into_inner()), query.content, FeedFormat::Atom).await
+}
+
+#[derive(Clone, Copy)]
+enum FeedFormat {
+    Rss,
+    Atom,
+}
+
+async fn feed_response(
+    data: &AppState,
+    req: &HttpRequest,
+    scope: FeedScope,
+    content: FeedContent,
+    format: FeedFormat,
+) -> Result<HttpResponse, AppError> {
+    tag_listing(req, []);
+    let (name, articles) = data.list_feed_articles.execute(scope, *FEED_SIZE).await?;
+    tag_listing(req, articles.iter().map(|a| a.meta.id));
+
+    let channel = feed::Channel {
+        title: match name {
+            Some(name) => format!("{} - {name}", *SITE_TITLE),
+            None => SITE_TITLE.clone(),
+        },
+        description: SITE_DESCRIPTION.clone(),
+        link: SITE_URL.clone(),
+        feed_link: format!("{}{}", *API_URL, req.path()),
+        entries: articles.into_iter().map(|a| feed_entry(a, content)).collect(),
+    };
+
+    let (body, content_type) = match format {
+        FeedFormat::Rss => (feed::rss(&channel), feed::RSS_CONTENT_TYPE),
+        FeedFormat::Atom => (feed::atom(&channel), feed::ATOM_CONTENT_TYPE),
+    };
+    Ok(HttpResponse::Ok()
+        .insert_header((header::CONTENT_TYPE, content_type))
+        .body(body))
+}
+
+/// 记录响应包含的内容，文章修改后带有相应标签的缓存会被清除
+fn tag_cache(req: &HttpRequest, tags: impl IntoIterator<Item = CacheTag>) {
+    req.extensions_mut().insert(CacheTags(tags.into_iter().collect()));
+}
+
+/// 列表类响应在任何文章发布或隐藏后都会变化，其中的分类信息随分类修改变化，
+/// 另外记录其中的文章以便修改时清除
+fn tag_listing(req: &HttpRequest, ids: impl IntoIterator<Item = i64>) {
+    tag_cache(req, [CacheTag::Listing, CacheTag::Categories].into_iter().chain(ids.into_iter().map(CacheTag::Article)));
+}
+
+fn feed_entry(article: ArticleDetail, content: FeedContent) -> feed::Entry {
+    let meta = article.meta;
+    let categories = meta.category
+        .map(|c| c.name)
+        .into_iter()
+        .chain(article.tags.into_iter().map(|t| t.name))
+        .collect();
+
+    feed::Entry {
+        link: article_url(&meta.slug),
+        title: meta.title,
+        summary: meta.description,
+        content: match content {
+            FeedContent::Full => Some(article.content_html),
+            FeedContent::Summary => None,
+        },
+        categories,
+        published: article.created_at,
+        updated: meta.last_update,
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema, ApiComponent)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedContent {
+    /// 输出渲染后的正文
+    #[default]
+    Full,
+    /// 只输出文章描述
+    Summary,
+}
+
+#[derive(Debug, Deserialize, JsonSchema, ApiComponent)]
+pub struct FeedParams {
+    #[serde(default)]
+    pub content: FeedContent,
+}
+
 #[derive(Debug, Deserialize, Validate, JsonSchema, ApiComponent)]
 pub struct ArticlePageParams {
     #[validate(range(min = 1))]
     pub page: u64,
     #[validate(range(min = 1, max = 100))]
     pub per: u64,
-}
\ No newline at end of file
+}
+
+#[derive(Debug, Deserialize, Validate, JsonSchema, ApiComponent)]
+pub struct ArticleCursorParams {
+    /// 上次结果中的 `next` 或 `prev`，为空时返回第一页
+    pub cursor: Option<String>,
+    #[validate(range(min = 1, max = 100))]
+    pub per: u64,
+}
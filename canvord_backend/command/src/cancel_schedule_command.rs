@@ -0,0 +1,9 @@
+use apistos::ApiComponent;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+/// 取消定时发布，文章回到未发布状态
+#[derive(Deserialize, JsonSchema, ApiComponent)]
+pub struct CancelScheduleCommand {
+    pub id: i64,
+}
@@ -12,8 +12,10 @@ pub struct CreateArticleCommand {
     pub title: String,
     pub slug: String,
     pub description: String,
-    pub category: String,
+    pub category_id: Option<i64>,
     pub content_md: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 impl IntoActiveModel for CreateArticleCommand {
@@ -23,7 +25,7 @@ impl IntoActiveModel for CreateArticleCommand {
             slug: Set(self.slug),
             description: Set(self.description),
             content_md: Set(self.content_md),
-            category: Set(self.category),
+            category_id: Set(self.category_id),
             created_at: Set(now),
             last_update: Set(now),
             status: Set(Status::Published),
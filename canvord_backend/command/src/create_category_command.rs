@@ -0,0 +1,12 @@
+use apistos::ApiComponent;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+#[derive(Deserialize, JsonSchema, ApiComponent)]
+pub struct CreateCategoryCommand {
+    pub parent_id: Option<i64>,
+    pub name: String,
+    pub slug: String,
+    #[serde(default)]
+    pub description: String,
+}
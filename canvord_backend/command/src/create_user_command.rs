@@ -0,0 +1,11 @@
+use apistos::ApiComponent;
+use entity::user::Role;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+#[derive(Deserialize, JsonSchema, ApiComponent)]
+pub struct CreateUserCommand {
+    pub username: String,
+    pub password: String,
+    pub role: Role,
+}
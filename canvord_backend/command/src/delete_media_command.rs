@@ -0,0 +1,11 @@
+use apistos::ApiComponent;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+#[derive(Deserialize, JsonSchema, ApiComponent)]
+pub struct DeleteMediaCommand {
+    pub id: i64,
+    /// 仍被文章引用时默认拒绝删除
+    #[serde(default)]
+    pub force: bool,
+}
@@ -8,6 +8,15 @@ pub mod delete_article_command;
 pub mod hide_article_command;
 pub mod publish_article_command;
 pub mod publish_draft_command;
+pub mod schedule_article_command;
+pub mod cancel_schedule_command;
+pub mod create_user_command;
+pub mod restore_article_revision_command;
+pub mod create_category_command;
+pub mod update_category_command;
+pub mod delete_category_command;
+pub mod delete_media_command;
+pub mod preview_article_command;
 
 pub trait IntoActiveModel {
     fn into_active_model(self, now: NaiveDateTime) -> article::ActiveModel;
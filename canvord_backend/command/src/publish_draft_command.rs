@@ -13,8 +13,11 @@ pub struct PublishDraftCommand {
     pub title: String,
     pub slug: String,
     pub description: String,
-    pub category: String,
+    pub category_id: Option<i64>,
     pub content_md: String,
+    /// 不传时保留文章现有标签
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
 }
 
 impl IntoActiveModel for PublishDraftCommand {
@@ -25,7 +28,7 @@ impl IntoActiveModel for PublishDraftCommand {
             slug: Set(self.slug),
             description: Set(self.description),
             content_md: Set(self.content_md),
-            category: Set(self.category),
+            category_id: Set(self.category_id),
             last_update: Set(now),
             status: Set(Status::Published),
             ..Default::default()
@@ -0,0 +1,8 @@
+use apistos::ApiComponent;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+#[derive(Deserialize, JsonSchema, ApiComponent)]
+pub struct RestoreArticleRevisionCommand {
+    pub revision_id: i64,
+}
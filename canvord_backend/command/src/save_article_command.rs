@@ -12,8 +12,10 @@ pub struct SaveArticleCommand {
     pub title: String,
     pub slug: String,
     pub description: String,
-    pub category: String,
+    pub category_id: Option<i64>,
     pub content_md: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 impl IntoActiveModel for SaveArticleCommand {
@@ -22,7 +24,7 @@ impl IntoActiveModel for SaveArticleCommand {
             title: Set(self.title),
             slug: Set(self.slug),
             description: Set(self.description),
-            category: Set(self.category),
+            category_id: Set(self.category_id),
             content_md: Set(self.content_md),
             created_at: Set(now),
             last_update: Set(now),
@@ -0,0 +1,12 @@
+use apistos::ApiComponent;
+use chrono::NaiveDateTime;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+/// 设置或修改定时发布时间
+#[derive(Deserialize, JsonSchema, ApiComponent)]
+pub struct ScheduleArticleCommand {
+    pub id: i64,
+    /// UTC 时间，须晚于当前时间
+    pub publish_at: NaiveDateTime,
+}
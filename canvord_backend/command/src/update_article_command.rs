@@ -4,7 +4,7 @@ use chrono::NaiveDateTime;
 use entity::article;
 use entity::article::Status;
 use schemars::JsonSchema;
-use sea_orm::Set;
+use sea_orm::{NotSet, Set};
 use serde::Deserialize;
 
 #[derive(Deserialize, JsonSchema, ApiComponent)]
@@ -13,8 +13,11 @@ pub struct UpdateArticleCommand {
     pub title: String,
     pub slug: String,
     pub description: String,
-    pub category: String,
+    pub category_id: Option<i64>,
     pub content_md: String,
+    /// 不传时保留文章现有标签
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
     pub status: Status,
 }
 
@@ -25,9 +28,11 @@ impl IntoActiveModel for UpdateArticleCommand {
             title: Set(self.title),
             slug: Set(self.slug),
             description: Set(self.description),
-            category: Set(self.category),
+            category_id: Set(self.category_id),
             content_md: Set(self.content_md),
             last_update: Set(now),
+            // 改为其它状态时取消定时发布，发布时间只能通过定时发布接口修改
+            publish_at: if self.status == Status::Scheduled { NotSet } else { Set(None) },
             status: Set(self.status),
             ..Default::default()
         }
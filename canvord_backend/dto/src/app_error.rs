@@ -1,21 +1,41 @@
 use crate::app_response::AppResponse;
 use actix_web::{HttpResponse, ResponseError};
-use apistos::ApiComponent;
+use apistos::{ApiComponent, ApiErrorComponent};
+use canvord_service::error::MutationError;
 use schemars::JsonSchema;
 use sea_orm::DbErr;
 use thiserror::Error;
 use validator::ValidationError;
 
-#[derive(Debug, Error, JsonSchema, ApiComponent)]
+// openapi_error 的每个状态码都写作 status(..)，会被误报为重复属性
+#[allow(clippy::duplicated_attributes)]
+#[derive(Debug, Error, JsonSchema, ApiComponent, ApiErrorComponent)]
+#[openapi_error(
+    status(code = 400),
+    status(code = 401),
+    status(code = 403),
+    status(code = 404),
+    status(code = 409),
+    status(code = 500),
+    status(code = 503)
+)]
 pub enum AppError {
     #[error("Not Found: {0}")]
     NotFound(String),
     #[error("Bad Request: {0}")]
     BadRequest(String),
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+    #[error("Conflict: {0}")]
+    Conflict(String),
     #[error("Internal Server Error")]
     InternalError,
     #[error("Database Error: {0}")]
     DbError(String),
+    #[error("Service Unavailable: {0}")]
+    ServiceUnavailable(String),
 }
 
 impl AppError {
@@ -23,8 +43,12 @@ impl AppError {
         match self {
             AppError::NotFound(_) => 404,
             AppError::BadRequest(_) => 400,
+            AppError::Unauthorized(_) => 401,
+            AppError::Forbidden(_) => 403,
+            AppError::Conflict(_) => 409,
             AppError::InternalError => 500,
             AppError::DbError(_) => 500,
+            AppError::ServiceUnavailable(_) => 503,
         }
     }
 
@@ -46,7 +70,21 @@ impl ResponseError for AppError {
 
 impl From<DbErr> for AppError {
     fn from(err: DbErr) -> Self {
-        AppError::DbError(err.to_string())
+        match err {
+            DbErr::RecordNotFound(msg) => AppError::NotFound(msg),
+            err => AppError::DbError(err.to_string()),
+        }
+    }
+}
+
+impl From<MutationError> for AppError {
+    fn from(err: MutationError) -> Self {
+        match err {
+            MutationError::Invalid(msg) => AppError::BadRequest(msg),
+            MutationError::Conflict(msg) => AppError::Conflict(msg),
+            MutationError::Forbidden(msg) => AppError::Forbidden(msg),
+            MutationError::Db(err) => err.into(),
+        }
     }
 }
 
@@ -1,4 +1,5 @@
 use crate::app_error::AppError;
+use actix_web::http::StatusCode;
 use actix_web::{HttpResponse, Responder};
 use apistos::ApiComponent;
 use schemars::JsonSchema;
@@ -43,8 +44,14 @@ where
 {
     type Body = actix_web::body::BoxBody;
 
+    /// 错误码同时作为 HTTP 状态码，错误响应不会被当作成功响应缓存
     fn respond_to(self, _req: &actix_web::HttpRequest) -> HttpResponse<Self::Body> {
-        HttpResponse::Ok()
+        let status = u16::try_from(self.code)
+            .ok()
+            .and_then(|code| StatusCode::from_u16(code).ok())
+            .filter(|status| status.is_client_error() || status.is_server_error())
+            .unwrap_or(StatusCode::OK);
+        HttpResponse::build(status)
             .content_type("application/json")
             .json(self)
     }
@@ -1,8 +1,10 @@
 use apistos::ApiComponent;
 use chrono::NaiveDateTime;
-use entity::article::{ActiveModel, Model, Status};
+use entity::article::{ActiveModel, Model, Status, TocEntry};
 use schemars::JsonSchema;
 use sea_orm::{DbErr, TryIntoModel};
+use crate::category::CategoryInfo;
+use crate::tag::TagInfo;
 
 #[derive(Debug, Clone, serde::Serialize, JsonSchema, ApiComponent)]
 pub struct ArticleMeta {
@@ -10,9 +12,11 @@ pub struct ArticleMeta {
     pub title: String,
     pub slug: String,
     pub description: String,
-    pub category: String,
+    pub category: Option<CategoryInfo>,
     pub last_update: NaiveDateTime,
     pub status: Status,
+    /// 定时发布的时间（UTC）
+    pub publish_at: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, JsonSchema, ApiComponent)]
@@ -20,7 +24,22 @@ pub struct ArticleDetail {
     #[serde(flatten)]
     pub meta: ArticleMeta,
     pub content_md: String,
+    /// 服务端渲染的正文
+    pub content_html: String,
+    pub toc: Vec<TocEntry>,
+    pub word_count: i32,
+    pub reading_minutes: i32,
     pub created_at: NaiveDateTime,
+    pub tags: Vec<TagInfo>,
+}
+
+/// 未保存正文的渲染结果，用于后台预览
+#[derive(Debug, Clone, serde::Serialize, JsonSchema, ApiComponent)]
+pub struct RenderedContent {
+    pub content_html: String,
+    pub toc: Vec<TocEntry>,
+    pub word_count: i32,
+    pub reading_minutes: i32,
 }
 
 impl From<Model> for ArticleMeta {
@@ -30,9 +49,19 @@ impl From<Model> for ArticleMeta {
             title: m.title,
             slug: m.slug,
             description: m.description,
-            category: m.category,
+            category: None,
             last_update: m.last_update,
             status: m.status,
+            publish_at: m.publish_at,
+        }
+    }
+}
+
+impl From<(Model, Option<entity::category::Model>)> for ArticleMeta {
+    fn from((m, category): (Model, Option<entity::category::Model>)) -> Self {
+        Self {
+            category: category.map(CategoryInfo::from),
+            ..m.into()
         }
     }
 }
@@ -42,11 +71,24 @@ impl From<Model> for ArticleDetail {
         Self {
             meta: m.clone().into(),
             content_md: m.content_md,
+            content_html: m.content_html.unwrap_or_default(),
+            toc: m.toc.map(|t| t.0).unwrap_or_default(),
+            word_count: m.word_count,
+            reading_minutes: m.reading_minutes,
             created_at: m.created_at,
+            tags: Vec::new(),
         }
     }
 }
 
+impl ArticleDetail {
+    pub fn with_relations(mut self, category: Option<entity::category::Model>, tags: Vec<entity::tag::Model>) -> Self {
+        self.meta.category = category.map(CategoryInfo::from);
+        self.tags = tags.into_iter().map(TagInfo::from).collect();
+        self
+    }
+}
+
 impl TryFrom<ActiveModel> for ArticleDetail {
     type Error = DbErr;
 
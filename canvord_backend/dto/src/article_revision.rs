@@ -0,0 +1,61 @@
+use apistos::ApiComponent;
+use chrono::NaiveDateTime;
+use entity::article_revision::Model;
+use schemars::JsonSchema;
+
+#[derive(Debug, Clone, serde::Serialize, JsonSchema, ApiComponent)]
+pub struct RevisionMeta {
+    pub id: i64,
+    pub article_id: i64,
+    pub title: String,
+    pub description: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, serde::Serialize, JsonSchema, ApiComponent)]
+pub struct RevisionDetail {
+    #[serde(flatten)]
+    pub meta: RevisionMeta,
+    pub content_md: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, JsonSchema, ApiComponent)]
+pub enum DiffTag {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Debug, Clone, serde::Serialize, JsonSchema, ApiComponent)]
+pub struct DiffLine {
+    pub tag: DiffTag,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, JsonSchema, ApiComponent)]
+pub struct RevisionDiff {
+    pub from: RevisionMeta,
+    pub to: RevisionMeta,
+    pub lines: Vec<DiffLine>,
+}
+
+impl From<Model> for RevisionMeta {
+    fn from(m: Model) -> Self {
+        Self {
+            id: m.id,
+            article_id: m.article_id,
+            title: m.title,
+            description: m.description,
+            created_at: m.created_at,
+        }
+    }
+}
+
+impl From<Model> for RevisionDetail {
+    fn from(m: Model) -> Self {
+        Self {
+            meta: m.clone().into(),
+            content_md: m.content_md,
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use apistos::ApiComponent;
+use schemars::JsonSchema;
+
+#[derive(Debug, Clone, serde::Serialize, JsonSchema, ApiComponent)]
+pub struct TokenPair {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_in: i64, // access token 有效期（秒）
+}
@@ -0,0 +1,90 @@
+use apistos::ApiComponent;
+use entity::category::Model;
+use schemars::JsonSchema;
+
+#[derive(Debug, Clone, serde::Serialize, JsonSchema, ApiComponent)]
+pub struct CategoryInfo {
+    pub id: i64,
+    pub name: String,
+    pub slug: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, JsonSchema, ApiComponent)]
+pub struct CategoryNode {
+    pub id: i64,
+    pub parent_id: Option<i64>,
+    pub name: String,
+    pub slug: String,
+    pub description: String,
+    pub children: Vec<CategoryNode>,
+}
+
+impl From<Model> for CategoryInfo {
+    fn from(m: Model) -> Self {
+        Self {
+            id: m.id,
+            name: m.name,
+            slug: m.slug,
+        }
+    }
+}
+
+impl From<Model> for CategoryNode {
+    fn from(m: Model) -> Self {
+        Self {
+            id: m.id,
+            parent_id: m.parent_id,
+            name: m.name,
+            slug: m.slug,
+            description: m.description,
+            children: Vec::new(),
+        }
+    }
+}
+
+impl CategoryNode {
+    /// 由扁平的分类列表构建森林，父分类不存在的节点视为根节点
+    pub fn build_tree(models: Vec<Model>) -> Vec<CategoryNode> {
+        let ids: Vec<i64> = models.iter().map(|m| m.id).collect();
+        let (roots, mut rest): (Vec<Model>, Vec<Model>) = models
+            .into_iter()
+            .partition(|m| m.parent_id.is_none_or(|p| !ids.contains(&p)));
+
+        roots
+            .into_iter()
+            .map(|m| Self::attach_children(m.into(), &mut rest))
+            .collect()
+    }
+
+    fn attach_children(mut node: CategoryNode, rest: &mut Vec<Model>) -> CategoryNode {
+        let (children, others): (Vec<Model>, Vec<Model>) = std::mem::take(rest)
+            .into_iter()
+            .partition(|m| m.parent_id == Some(node.id));
+        *rest = others;
+
+        node.children = children
+            .into_iter()
+            .map(|m| Self::attach_children(m.into(), rest))
+            .collect();
+        node
+    }
+
+    /// 返回该节点及其所有后代的 id
+    pub fn subtree_ids(&self) -> Vec<i64> {
+        let mut ids = vec![self.id];
+        for child in &self.children {
+            ids.extend(child.subtree_ids());
+        }
+        ids
+    }
+
+    pub fn find_by_slug(nodes: &[CategoryNode], slug: &str) -> Option<CategoryNode> {
+        nodes.iter().find_map(|n| {
+            if n.slug == slug {
+                Some(n.clone())
+            } else {
+                Self::find_by_slug(&n.children, slug)
+            }
+        })
+    }
+}
@@ -1,4 +1,11 @@
 pub mod pagination;
 pub mod article;
+pub mod article_revision;
 pub mod app_error;
-pub mod app_response;
\ No newline at end of file
+pub mod app_response;
+pub mod user;
+pub mod auth;
+pub mod tag;
+pub mod category;
+pub mod search;
+pub mod media;
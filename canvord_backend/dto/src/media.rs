@@ -0,0 +1,27 @@
+use apistos::ApiComponent;
+use chrono::NaiveDateTime;
+use schemars::JsonSchema;
+
+#[derive(Debug, Clone, serde::Serialize, JsonSchema, ApiComponent)]
+pub struct MediaInfo {
+    pub id: i64,
+    pub owner_id: Option<i64>,
+    pub key: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i64,
+    /// 写入 Markdown 时使用的地址
+    pub url: String,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    /// 按宽度从小到大排列的 WebP 版本，非图片或无法处理的格式为空
+    pub variants: Vec<MediaVariantInfo>,
+    pub usage_count: u64,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, serde::Serialize, JsonSchema, ApiComponent)]
+pub struct MediaVariantInfo {
+    pub width: i32,
+    pub url: String,
+}
@@ -1,4 +1,5 @@
 use apistos::ApiComponent;
+use chrono::{DateTime, NaiveDateTime};
 use schemars::JsonSchema;
 
 #[derive(Debug, Clone, serde::Serialize, JsonSchema, ApiComponent)]
@@ -7,4 +8,86 @@ pub struct PageResult<T: JsonSchema> {
     pub current: usize, // 当前页数（从 1 开始）
     pub size: usize,    // 每页大小
     pub data: Vec<T>,   // 当前页的数据
-}
\ No newline at end of file
+}
+
+/// 按游标翻页的结果，不统计总数
+#[derive(Debug, Clone, serde::Serialize, JsonSchema, ApiComponent)]
+pub struct CursorPage<T: JsonSchema> {
+    pub size: usize,          // 每页大小
+    pub data: Vec<T>,         // 当前页的数据
+    pub next: Option<String>, // 下一页的游标，没有更多时为空
+    pub prev: Option<String>, // 上一页的游标，第一页时为空
+}
+
+/// 按 `(created_at, id)` 翻页的位置和方向，对外编码为不透明的字符串
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub created_at: NaiveDateTime,
+    pub id: i64,
+    /// `true` 表示该位置之后的一页，`false` 表示之前的一页
+    pub forward: bool,
+    /// 是否包含该位置上的文章，只有翻过头得到空页后返回的游标包含
+    pub inclusive: bool,
+}
+
+impl Cursor {
+    /// 由请求的游标和本页首尾文章的 `(created_at, id)` 计算 `(next, prev)`
+    pub fn links(
+        cursor: Option<Cursor>,
+        first: Option<(NaiveDateTime, i64)>,
+        last: Option<(NaiveDateTime, i64)>,
+        has_more: bool,
+    ) -> (Option<String>, Option<String>) {
+        let at = |(created_at, id): (NaiveDateTime, i64), forward: bool| {
+            Cursor { created_at, id, forward, inclusive: false }.encode()
+        };
+        let first = first.map(|p| at(p, false));
+        let last = last.map(|p| at(p, true));
+        // 翻过头得到空页时，从原位置（含该位置上的文章）反向翻页回到上一页
+        let back = cursor.map(|c| Cursor { forward: !c.forward, inclusive: true, ..c }.encode());
+
+        if cursor.is_none_or(|c| c.forward) {
+            (last.filter(|_| has_more), cursor.and(first.or(back)))
+        } else {
+            (last.or(back), first.filter(|_| has_more))
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let direction = match (self.forward, self.inclusive) {
+            (true, false) => 'a',
+            (false, false) => 'b',
+            (true, true) => 'A',
+            (false, true) => 'B',
+        };
+        let text = format!("{direction}{}:{}", self.created_at.and_utc().timestamp_micros(), self.id);
+        text.bytes().map(|b| format!("{b:02x}")).collect()
+    }
+
+    /// 不是由 [`encode`](Self::encode) 生成的字符串返回 `None`
+    pub fn decode(cursor: &str) -> Option<Self> {
+        if !cursor.len().is_multiple_of(2) || !cursor.is_ascii() {
+            return None;
+        }
+        let bytes = (0..cursor.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&cursor[i..i + 2], 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+        let text = String::from_utf8(bytes).ok()?;
+
+        let (forward, inclusive) = match text.get(..1)? {
+            "a" => (true, false),
+            "b" => (false, false),
+            "A" => (true, true),
+            "B" => (false, true),
+            _ => return None,
+        };
+        let (micros, id) = text[1..].split_once(':')?;
+        Some(Self {
+            created_at: DateTime::from_timestamp_micros(micros.parse().ok()?)?.naive_utc(),
+            id: id.parse().ok()?,
+            forward,
+            inclusive,
+        })
+    }
+}
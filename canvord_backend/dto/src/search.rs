@@ -0,0 +1,13 @@
+use apistos::ApiComponent;
+use schemars::JsonSchema;
+use crate::article::ArticleMeta;
+
+/// 搜索命中的文章，高亮片段中只包含 `<mark>` 标签，其余内容均已转义
+#[derive(Debug, Clone, serde::Serialize, JsonSchema, ApiComponent)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub meta: ArticleMeta,
+    pub score: f32,
+    pub title_html: String,
+    pub snippet_html: String,
+}
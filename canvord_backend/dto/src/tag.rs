@@ -0,0 +1,27 @@
+use apistos::ApiComponent;
+use entity::tag::Model;
+use schemars::JsonSchema;
+
+#[derive(Debug, Clone, serde::Serialize, JsonSchema, ApiComponent)]
+pub struct TagInfo {
+    pub id: i64,
+    pub name: String,
+    pub slug: String,
+}
+
+#[derive(Debug, Clone, serde::Serialize, JsonSchema, ApiComponent)]
+pub struct TagWithCount {
+    #[serde(flatten)]
+    pub tag: TagInfo,
+    pub article_count: u64,
+}
+
+impl From<Model> for TagInfo {
+    fn from(m: Model) -> Self {
+        Self {
+            id: m.id,
+            name: m.name,
+            slug: m.slug,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use apistos::ApiComponent;
+use chrono::NaiveDateTime;
+use entity::user::{Model, Role};
+use schemars::JsonSchema;
+
+#[derive(Debug, Clone, serde::Serialize, JsonSchema, ApiComponent)]
+pub struct UserInfo {
+    pub id: i64,
+    pub username: String,
+    pub role: Role,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<Model> for UserInfo {
+    fn from(m: Model) -> Self {
+        Self {
+            id: m.id,
+            username: m.username,
+            role: m.role,
+            created_at: m.created_at,
+        }
+    }
+}
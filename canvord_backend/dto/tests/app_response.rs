@@ -0,0 +1,50 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::Arc;
+use actix_web::http::StatusCode;
+use actix_web::{test, web, App};
+use dto::app_error::AppError;
+use dto::app_response::AppResponse;
+use middleware::cache::CacheMiddleware;
+use middleware::local_cache::LocalCache;
+
+/// 没有服务监听的端口，连接会立即失败
+const UNREACHABLE: &str = "redis://127.0.0.1:1/";
+
+#[actix_web::test]
+async fn error_responses_carry_http_status_and_are_not_cached() {
+    let client = redis::Client::open(UNREACHABLE).unwrap();
+    let local = Arc::new(LocalCache::new(16, 1024 * 1024));
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+    let app = test::init_service(
+        App::new()
+            .wrap(CacheMiddleware::new(client).with_local(local.clone()))
+            .route("/article/{slug}", web::get().to(move |slug: web::Path<String>| {
+                counter.set(counter.get() + 1);
+                async move {
+                    match slug.as_str() {
+                        "missing" => AppResponse::<String>::from_error(&AppError::NotFound("article not found".into())),
+                        _ => AppResponse::ok(slug.into_inner()),
+                    }
+                }
+            })),
+    )
+    .await;
+
+    for _ in 0..2 {
+        let res = test::call_service(&app, test::TestRequest::get().uri("/article/missing").to_request()).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        let body: serde_json::Value = test::read_body_json(res).await;
+        assert_eq!(body["code"], 404);
+    }
+    assert_eq!(calls.get(), 2);
+    assert_eq!(local.stats().entries, 0);
+
+    // 成功的响应照常缓存
+    for _ in 0..2 {
+        let res = test::call_service(&app, test::TestRequest::get().uri("/article/hello").to_request()).await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+    assert_eq!(calls.get(), 3);
+}
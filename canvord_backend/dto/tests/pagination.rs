@@ -0,0 +1,79 @@
+use chrono::{DateTime, NaiveDateTime};
+use dto::pagination::Cursor;
+
+fn at(micros: i64) -> NaiveDateTime {
+    DateTime::from_timestamp_micros(micros).unwrap().naive_utc()
+}
+
+fn cursor(micros: i64, id: i64, forward: bool, inclusive: bool) -> Cursor {
+    Cursor { created_at: at(micros), id, forward, inclusive }
+}
+
+#[test]
+fn round_trips_through_encode() {
+    for (forward, inclusive) in [(true, false), (false, false), (true, true), (false, true)] {
+        for c in [cursor(1_754_700_000_123_456, 42, forward, inclusive), cursor(0, 0, forward, inclusive), cursor(-1, i64::MAX, forward, inclusive)] {
+            let encoded = c.encode();
+            assert!(encoded.bytes().all(|b| b.is_ascii_hexdigit()), "{encoded}");
+            assert_eq!(Cursor::decode(&encoded), Some(c));
+        }
+    }
+}
+
+#[test]
+fn rejects_malformed_cursors() {
+    let hex = |text: &str| text.bytes().map(|b| format!("{b:02x}")).collect::<String>();
+    let valid = cursor(1_000_000, 7, true, false).encode();
+
+    for input in [
+        String::new(),
+        valid[1..].to_owned(),
+        format!("{valid}zz"),
+        "é".repeat(4),
+        hex("c1000000:7"),
+        hex("a1000000"),
+        hex("a:7"),
+        hex("ax:7"),
+        hex("a1000000:"),
+        hex("a1000000:x"),
+        hex("a99999999999999999999:7"),
+    ] {
+        assert_eq!(Cursor::decode(&input), None, "{input}");
+    }
+}
+
+#[test]
+fn derives_next_and_prev_from_page_edges() {
+    let (first, last) = ((at(1), 1), (at(2), 2));
+    let next = cursor(2, 2, true, false).encode();
+    let prev = cursor(1, 1, false, false).encode();
+
+    // 第一页：没有上一页，还有更多时才有下一页
+    assert_eq!(Cursor::links(None, Some(first), Some(last), true), (Some(next.clone()), None));
+    assert_eq!(Cursor::links(None, Some(first), Some(last), false), (None, None));
+    assert_eq!(Cursor::links(None, None, None, false), (None, None));
+
+    // 向后翻到最后一页：没有下一页，上一页从本页第一篇之前开始
+    let request = cursor(0, 9, true, false);
+    assert_eq!(Cursor::links(Some(request), Some(first), Some(last), false), (None, Some(prev.clone())));
+
+    // 向前翻到第一页：没有上一页，下一页从本页最后一篇之后开始
+    let request = cursor(3, 9, false, false);
+    assert_eq!(Cursor::links(Some(request), Some(first), Some(last), false), (Some(next.clone()), None));
+    assert_eq!(Cursor::links(Some(request), Some(first), Some(last), true), (Some(next), Some(prev)));
+}
+
+#[test]
+fn empty_page_links_back_including_the_boundary() {
+    // 向后翻过头：上一页包含原位置上的文章
+    let request = cursor(5, 5, true, false);
+    let (next, prev) = Cursor::links(Some(request), None, None, false);
+    assert_eq!(next, None);
+    assert_eq!(Cursor::decode(&prev.unwrap()), Some(cursor(5, 5, false, true)));
+
+    // 向前翻过头：下一页同样包含原位置
+    let request = cursor(5, 5, false, false);
+    let (next, prev) = Cursor::links(Some(request), None, None, false);
+    assert_eq!(prev, None);
+    assert_eq!(Cursor::decode(&next.unwrap()), Some(cursor(5, 5, true, true)));
+}
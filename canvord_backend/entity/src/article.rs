@@ -1,8 +1,7 @@
 use schemars::JsonSchema;
 use sea_orm::entity::prelude::*;
-use sea_orm::{DeleteMany, Set};
+use sea_orm::{DeleteMany, FromJsonQueryResult, QueryOrder, Set};
 use serde::{Deserialize, Serialize};
-use crate::article::Status::Published;
 
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
 #[sea_orm(table_name = "article")]
@@ -14,10 +13,31 @@ pub struct Model {
     pub slug: String,
     pub description: String,
     pub content_md: String,
-    pub category: String,
+    /// 保存时由 `content_md` 渲染，旧数据在启动时补齐
+    pub content_html: Option<String>,
+    pub toc: Option<Toc>,
+    pub word_count: i32,
+    pub reading_minutes: i32,
+    pub render_version: i32,
+    pub category_id: Option<i64>,
     pub created_at: DateTime,
     pub last_update: DateTime,
     pub status: Status,
+    /// 定时发布的时间（UTC），仅在 `Scheduled` 状态下有值
+    pub publish_at: Option<DateTime>,
+    /// 创建者，早于记录创建者的文章和创建者已删除的文章为空
+    #[serde(skip_deserializing)]
+    pub author_id: Option<i64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Default, Deserialize, Serialize, FromJsonQueryResult)]
+pub struct Toc(pub Vec<TocEntry>);
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+pub struct TocEntry {
+    pub level: u8,
+    pub id: String,
+    pub text: String,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize, JsonSchema)]
@@ -26,10 +46,52 @@ pub enum Status {
     Published,
     Unpublished,
     Hidden,
+    /// 等待在 `publish_at` 自动发布
+    Scheduled,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::article_revision::Entity")]
+    Revision,
+    #[sea_orm(has_many = "super::article_tag::Entity")]
+    ArticleTag,
+    #[sea_orm(
+        belongs_to = "super::category::Entity",
+        from = "Column::CategoryId",
+        to = "super::category::Column::Id",
+        on_delete = "SetNull"
+    )]
+    Category,
+}
+
+impl Related<super::category::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Category.def()
+    }
+}
+
+impl Related<super::article_revision::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Revision.def()
+    }
+}
+
+impl Related<super::article_tag::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ArticleTag.def()
+    }
+}
+
+impl Related<super::tag::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::article_tag::Relation::Tag.def()
+    }
+
+    fn via() -> Option<RelationDef> {
+        Some(super::article_tag::Relation::Article.def().rev())
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
 
@@ -40,7 +102,7 @@ impl ActiveModel {
             slug: Set(data.slug.to_owned()),
             description: Set(data.description.to_owned()),
             content_md: Set(data.content_md.to_owned()),
-            category: Set(data.category.to_owned()),
+            category_id: Set(data.category_id),
             created_at: Set(now),
             last_update: Set(now),
             status: Set(status),
@@ -66,6 +128,11 @@ impl Entity {
         Self::find().filter(Column::Status.eq(status))
     }
 
+    /// 按发布时间先后排列的待发布文章
+    pub fn list_scheduled() -> Select<Entity> {
+        Self::list_by_status(Status::Scheduled).order_by_asc(Column::PublishAt)
+    }
+
     pub fn delete_by_id(id: i64) -> DeleteMany<Entity> {
         Self::delete_many().filter(Column::Id.eq(id))
     }
@@ -0,0 +1,60 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::Set;
+use serde::{Deserialize, Serialize};
+use crate::article;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "article_revision")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    #[serde(skip_deserializing)]
+    pub id: i64,
+    pub article_id: i64,
+    pub title: String,
+    pub description: String,
+    pub content_md: String,
+    pub created_at: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::article::Entity",
+        from = "Column::ArticleId",
+        to = "super::article::Column::Id",
+        on_delete = "Cascade"
+    )]
+    Article,
+}
+
+impl Related<super::article::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Article.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl ActiveModel {
+    /// 以文章当前的标题、描述和正文生成一条快照
+    pub fn from_article(article: &article::Model, now: DateTime) -> Self {
+        Self {
+            article_id: Set(article.id),
+            title: Set(article.title.to_owned()),
+            description: Set(article.description.to_owned()),
+            content_md: Set(article.content_md.to_owned()),
+            created_at: Set(now),
+            ..Default::default()
+        }
+    }
+}
+
+impl Entity {
+    pub fn find_by_id(id: i64) -> Select<Entity> {
+        Self::find().filter(Column::Id.eq(id))
+    }
+
+    pub fn list_by_article(article_id: i64) -> Select<Entity> {
+        Self::find().filter(Column::ArticleId.eq(article_id))
+    }
+}
@@ -1 +1,9 @@
-pub mod article;
\ No newline at end of file
+pub mod article;
+pub mod article_media;
+pub mod article_revision;
+pub mod article_tag;
+pub mod category;
+pub mod media;
+pub mod media_variant;
+pub mod tag;
+pub mod user;
\ No newline at end of file
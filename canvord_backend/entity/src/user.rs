@@ -0,0 +1,76 @@
+use schemars::JsonSchema;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Deserialize, Serialize)]
+#[sea_orm(table_name = "user")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    #[serde(skip_deserializing)]
+    pub id: i64,
+    #[sea_orm(unique)]
+    pub username: String,
+    #[serde(skip_serializing)]
+    pub password_hash: String,
+    pub role: Role,
+    pub created_at: DateTime,
+    pub last_update: DateTime,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Deserialize, Serialize, JsonSchema)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::None)", rename_all = "camelCase")]
+pub enum Role {
+    Admin,
+    Editor,
+    Author,
+    Viewer,
+}
+
+/// 路由级别的操作权限，由角色决定是否拥有
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Permission {
+    ViewArticle,
+    SaveDraft,
+    EditArticle,
+    PublishArticle,
+    DeleteArticle,
+    ManageCategories,
+    UploadMedia,
+    ManageMedia,
+    ManageUsers,
+    /// 查看运行状态，如进程内缓存统计
+    ViewStats,
+}
+
+impl Role {
+    pub fn permissions(&self) -> &'static [Permission] {
+        use Permission::*;
+
+        match self {
+            Role::Admin => &[ViewArticle, SaveDraft, EditArticle, PublishArticle, DeleteArticle, ManageCategories, UploadMedia, ManageMedia, ManageUsers, ViewStats],
+            Role::Editor => &[ViewArticle, SaveDraft, EditArticle, PublishArticle, DeleteArticle, ManageCategories, UploadMedia, ManageMedia],
+            // 作者只能创建草稿，并修改自己创建、仍是草稿的文章
+            Role::Author => &[ViewArticle, SaveDraft, UploadMedia],
+            Role::Viewer => &[ViewArticle],
+        }
+    }
+
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        self.permissions().contains(&permission)
+    }
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Entity {
+    pub fn find_by_id(id: i64) -> Select<Entity> {
+        Self::find().filter(Column::Id.eq(id))
+    }
+
+    pub fn find_by_username(username: &str) -> Select<Entity> {
+        Self::find().filter(Column::Username.eq(username))
+    }
+}
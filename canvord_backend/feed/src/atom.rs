@@ -0,0 +1,54 @@
+use atom_syndication::{Category, Content, Entry as AtomEntry, Feed, Generator, Link, Person, Text};
+
+use crate::{Channel, Entry};
+
+/// 生成 Atom 1.0，订阅源的 `updated` 取条目中最晚的 `updated`
+pub fn atom(channel: &Channel) -> String {
+    Feed {
+        title: Text::plain(channel.title.clone()),
+        id: channel.feed_link.clone(),
+        updated: channel.updated().fixed_offset(),
+        subtitle: Some(Text::plain(channel.description.clone())),
+        // Atom 要求每个条目都有作者，文章没有作者信息，统一使用站点名
+        authors: vec![Person { name: channel.title.clone(), ..Default::default() }],
+        generator: Some(Generator { value: "Canvord".into(), ..Default::default() }),
+        links: vec![
+            link(&channel.feed_link, "self", Some("application/atom+xml")),
+            link(&channel.link, "alternate", Some("text/html")),
+        ],
+        entries: channel.entries.iter().map(entry).collect(),
+        ..Default::default()
+    }
+    .to_string()
+}
+
+fn entry(entry: &Entry) -> AtomEntry {
+    AtomEntry {
+        title: Text::plain(entry.title.clone()),
+        id: entry.link.clone(),
+        updated: entry.updated.and_utc().fixed_offset(),
+        published: Some(entry.published.and_utc().fixed_offset()),
+        links: vec![link(&entry.link, "alternate", Some("text/html"))],
+        summary: Some(Text::plain(entry.summary.clone())),
+        content: entry.content.as_ref().map(|html| Content {
+            value: Some(html.clone()),
+            content_type: Some("html".into()),
+            ..Default::default()
+        }),
+        categories: entry
+            .categories
+            .iter()
+            .map(|name| Category { term: name.clone(), ..Default::default() })
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn link(href: &str, rel: &str, mime_type: Option<&str>) -> Link {
+    Link {
+        href: href.to_owned(),
+        rel: rel.to_owned(),
+        mime_type: mime_type.map(str::to_owned),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,50 @@
+mod atom;
+mod rss;
+mod sitemap;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+pub use crate::atom::atom;
+pub use crate::rss::rss;
+pub use crate::sitemap::{sitemaps, SitemapUrl, Sitemaps, SITEMAP_MAX_URLS};
+
+pub const RSS_CONTENT_TYPE: &str = "application/rss+xml; charset=utf-8";
+pub const ATOM_CONTENT_TYPE: &str = "application/atom+xml; charset=utf-8";
+pub const SITEMAP_CONTENT_TYPE: &str = "application/xml; charset=utf-8";
+
+/// 订阅源，所有链接都应为绝对地址
+#[derive(Debug, Clone)]
+pub struct Channel {
+    pub title: String,
+    pub description: String,
+    /// 站点首页
+    pub link: String,
+    /// 订阅源自身的地址
+    pub feed_link: String,
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub title: String,
+    pub link: String,
+    pub summary: String,
+    /// 正文 HTML，为 `None` 时只输出摘要
+    pub content: Option<String>,
+    pub categories: Vec<String>,
+    /// 数据库中的时间均为 UTC
+    pub published: NaiveDateTime,
+    pub updated: NaiveDateTime,
+}
+
+impl Channel {
+    /// 条目中最晚的更新时间，没有条目时为 Unix 纪元
+    fn updated(&self) -> DateTime<Utc> {
+        self.entries
+            .iter()
+            .map(|e| e.updated)
+            .max()
+            .unwrap_or_default()
+            .and_utc()
+    }
+}
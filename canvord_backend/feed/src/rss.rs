@@ -0,0 +1,44 @@
+use ::rss::extension::atom::{AtomExtension, Link};
+use ::rss::{Category, Channel as RssChannel, Guid, Item};
+
+use crate::{Channel, Entry};
+
+/// 生成 RSS 2.0，`lastBuildDate` 取条目中最晚的 `updated`
+pub fn rss(channel: &Channel) -> String {
+    // 按 RSS Advisory Board 的建议用 atom:link 声明订阅源自身的地址
+    let self_link = Link {
+        href: channel.feed_link.clone(),
+        rel: "self".into(),
+        mime_type: Some("application/rss+xml".into()),
+        ..Default::default()
+    };
+
+    RssChannel {
+        title: channel.title.clone(),
+        link: channel.link.clone(),
+        description: channel.description.clone(),
+        last_build_date: Some(channel.updated().to_rfc2822()),
+        generator: Some("Canvord".into()),
+        atom_ext: Some(AtomExtension { links: vec![self_link] }),
+        items: channel.entries.iter().map(item).collect(),
+        ..Default::default()
+    }
+    .to_string()
+}
+
+fn item(entry: &Entry) -> Item {
+    Item {
+        title: Some(entry.title.clone()),
+        link: Some(entry.link.clone()),
+        description: Some(entry.summary.clone()),
+        content: entry.content.clone(),
+        categories: entry
+            .categories
+            .iter()
+            .map(|name| Category { name: name.clone(), domain: None })
+            .collect(),
+        guid: Some(Guid { value: entry.link.clone(), permalink: true }),
+        pub_date: Some(entry.published.and_utc().to_rfc2822()),
+        ..Default::default()
+    }
+}
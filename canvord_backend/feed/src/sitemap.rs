@@ -0,0 +1,71 @@
+use chrono::{NaiveDateTime, SecondsFormat};
+
+/// 单个 sitemap 文件最多包含的 URL 数
+pub const SITEMAP_MAX_URLS: usize = 50_000;
+
+#[derive(Debug, Clone)]
+pub struct SitemapUrl {
+    pub loc: String,
+    /// 数据库中的时间均为 UTC
+    pub lastmod: Option<NaiveDateTime>,
+}
+
+/// `index` 对应 `/sitemap.xml`；URL 不超过 [`SITEMAP_MAX_URLS`] 时它就是唯一的 `<urlset>`，
+/// 否则为 `<sitemapindex>`，各分片依次保存在 `parts` 中
+#[derive(Debug, Clone)]
+pub struct Sitemaps {
+    pub index: String,
+    pub parts: Vec<String>,
+}
+
+/// 生成 sitemap，`part_url(n)` 返回第 n 个分片（从 1 开始）的绝对地址
+pub fn sitemaps(urls: &[SitemapUrl], part_url: impl Fn(usize) -> String) -> Sitemaps {
+    if urls.len() <= SITEMAP_MAX_URLS {
+        return Sitemaps { index: urlset(urls), parts: Vec::new() };
+    }
+
+    let chunks: Vec<&[SitemapUrl]> = urls.chunks(SITEMAP_MAX_URLS).collect();
+    let mut index = String::from(r#"<?xml version="1.0" encoding="UTF-8"?><sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#);
+    for (i, chunk) in chunks.iter().enumerate() {
+        let lastmod = chunk.iter().filter_map(|u| u.lastmod).max();
+        push_entry(&mut index, "sitemap", &part_url(i + 1), lastmod);
+    }
+    index.push_str("</sitemapindex>");
+
+    Sitemaps {
+        index,
+        parts: chunks.into_iter().map(urlset).collect(),
+    }
+}
+
+fn urlset(urls: &[SitemapUrl]) -> String {
+    let mut xml = String::from(r#"<?xml version="1.0" encoding="UTF-8"?><urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#);
+    for url in urls {
+        push_entry(&mut xml, "url", &url.loc, url.lastmod);
+    }
+    xml.push_str("</urlset>");
+    xml
+}
+
+fn push_entry(xml: &mut String, tag: &str, loc: &str, lastmod: Option<NaiveDateTime>) {
+    xml.push_str(&format!("<{tag}><loc>{}</loc>", escape(loc)));
+    if let Some(lastmod) = lastmod {
+        xml.push_str(&format!("<lastmod>{}</lastmod>", lastmod.and_utc().to_rfc3339_opts(SecondsFormat::Secs, true)));
+    }
+    xml.push_str(&format!("</{tag}>"));
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,76 @@
+use chrono::NaiveDate;
+use feed::{atom, rss, Channel, Entry};
+
+fn channel(content: Option<&str>) -> Channel {
+    let at = |day, hour| NaiveDate::from_ymd_opt(2025, 8, day).unwrap().and_hms_opt(hour, 0, 0).unwrap();
+    Channel {
+        title: "Canvord".into(),
+        description: "博客 & 笔记".into(),
+        link: "https://example.com".into(),
+        feed_link: "https://example.com/visitor/feed.xml".into(),
+        entries: vec![
+            Entry {
+                title: "<Rust> & 你好".into(),
+                link: "https://example.com/article/hello".into(),
+                summary: "摘要".into(),
+                content: content.map(str::to_owned),
+                categories: vec!["后端".into(), "rust".into()],
+                published: at(1, 8),
+                updated: at(9, 12),
+            },
+            Entry {
+                title: "second".into(),
+                link: "https://example.com/article/second".into(),
+                summary: String::new(),
+                content: None,
+                categories: Vec::new(),
+                published: at(2, 8),
+                updated: at(3, 8),
+            },
+        ],
+    }
+}
+
+#[test]
+fn rss_round_trips() {
+    let xml = rss(&channel(Some("<p>正文</p><script>x</script>")));
+    let parsed = ::rss::Channel::read_from(xml.as_bytes()).unwrap();
+
+    assert_eq!(parsed.last_build_date(), Some("Sat, 9 Aug 2025 12:00:00 +0000"));
+    assert_eq!(parsed.items().len(), 2);
+    let item = &parsed.items()[0];
+    assert_eq!(item.title(), Some("<Rust> & 你好"));
+    assert_eq!(item.content(), Some("<p>正文</p><script>x</script>"));
+    assert_eq!(item.pub_date(), Some("Fri, 1 Aug 2025 08:00:00 +0000"));
+    assert_eq!(item.categories().len(), 2);
+    assert!(item.guid().unwrap().is_permalink());
+    assert!(xml.contains(r#"href="https://example.com/visitor/feed.xml""#), "{xml}");
+}
+
+#[test]
+fn rss_summary_has_no_content() {
+    let xml = rss(&channel(None));
+    assert!(!xml.contains("content:encoded"), "{xml}");
+}
+
+#[test]
+fn atom_round_trips() {
+    let xml = atom(&channel(Some("<p>正文</p>")));
+    let parsed = xml.parse::<atom_syndication::Feed>().unwrap();
+
+    assert_eq!(parsed.updated().to_rfc3339(), "2025-08-09T12:00:00+00:00");
+    assert_eq!(parsed.id(), "https://example.com/visitor/feed.xml");
+    let entry = &parsed.entries()[0];
+    assert_eq!(entry.title().as_str(), "<Rust> & 你好");
+    assert_eq!(entry.content().and_then(|c| c.value()), Some("<p>正文</p>"));
+    assert_eq!(entry.content().and_then(|c| c.content_type()), Some("html"));
+    assert!(parsed.entries()[1].content().is_none());
+}
+
+#[test]
+fn empty_channel_is_valid() {
+    let mut empty = channel(None);
+    empty.entries.clear();
+    ::rss::Channel::read_from(rss(&empty).as_bytes()).unwrap();
+    atom(&empty).parse::<atom_syndication::Feed>().unwrap();
+}
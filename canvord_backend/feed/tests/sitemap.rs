@@ -0,0 +1,39 @@
+use chrono::NaiveDate;
+use feed::{sitemaps, SitemapUrl, SITEMAP_MAX_URLS};
+
+fn urls(n: usize) -> Vec<SitemapUrl> {
+    let lastmod = NaiveDate::from_ymd_opt(2025, 8, 9).unwrap().and_hms_opt(12, 0, 0).unwrap();
+    (0..n)
+        .map(|i| SitemapUrl {
+            loc: format!("https://example.com/article/{i}?a=1&b=2"),
+            lastmod: Some(lastmod + chrono::Duration::seconds(i as i64)),
+        })
+        .collect()
+}
+
+fn part_url(n: usize) -> String {
+    format!("https://example.com/sitemap-{n}.xml")
+}
+
+#[test]
+fn small_sitemap_is_a_single_urlset() {
+    let out = sitemaps(&urls(2), part_url);
+
+    assert!(out.parts.is_empty());
+    assert!(out.index.contains("<urlset"), "{}", out.index);
+    assert!(out.index.contains("<loc>https://example.com/article/0?a=1&amp;b=2</loc>"), "{}", out.index);
+    assert!(out.index.contains("<lastmod>2025-08-09T12:00:01Z</lastmod>"), "{}", out.index);
+    assert_eq!(out.index.matches("<url>").count(), 2);
+}
+
+#[test]
+fn large_sitemap_is_split_with_an_index() {
+    let out = sitemaps(&urls(SITEMAP_MAX_URLS + 1), part_url);
+
+    assert_eq!(out.parts.len(), 2);
+    assert_eq!(out.parts[0].matches("<url>").count(), SITEMAP_MAX_URLS);
+    assert_eq!(out.parts[1].matches("<url>").count(), 1);
+    assert!(out.index.contains("<sitemapindex"));
+    assert!(out.index.contains("<sitemap><loc>https://example.com/sitemap-1.xml</loc><lastmod>2025-08-10T01:53:19Z</lastmod></sitemap>"), "{}", out.index);
+    assert!(out.index.contains("<loc>https://example.com/sitemap-2.xml</loc>"));
+}
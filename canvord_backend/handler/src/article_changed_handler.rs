@@ -0,0 +1,65 @@
+use entity::article;
+use middleware::cache::{CacheInvalidator, CacheTag};
+use search::SearchIndex;
+use crate::prerender_article_handler::PrerenderQueue;
+
+/// 一篇文章发生的变化
+// 只在一次修改中短暂存在，不必为 `Deleted` 节省空间而把文章装箱
+#[allow(clippy::large_enum_variant)]
+pub enum ArticleChanged {
+    /// 创建、修改、发布、隐藏、定时等，带有修改后的文章
+    Saved(article::Model),
+    /// 删除前查到的 slug，文章不存在时为 `None`
+    Deleted { id: i64, slug: Option<String> },
+}
+
+/// 文章修改之后统一调用：更新搜索索引，清除访客缓存（包括 sitemap），登记需要在后台更新的预渲染页面。
+///
+/// 修改已经提交，这里的失败只记录日志，不影响接口的返回结果
+pub struct ArticleChangedHandler<'a> {
+    index: &'a SearchIndex,
+    cache: &'a CacheInvalidator,
+    prerender: &'a PrerenderQueue,
+}
+
+impl<'a> ArticleChangedHandler<'a> {
+    pub fn new(index: &'a SearchIndex, cache: &'a CacheInvalidator, prerender: &'a PrerenderQueue) -> Self {
+        Self { index, cache, prerender }
+    }
+
+    pub async fn execute(&self, changes: &[ArticleChanged]) {
+        if changes.is_empty() {
+            return;
+        }
+
+        // 列表、订阅源、标签统计和 sitemap 都带有列表标签
+        let mut tags = vec![CacheTag::Listing];
+        let mut slugs = Vec::with_capacity(changes.len());
+        for change in changes {
+            let (id, slug) = match change {
+                ArticleChanged::Saved(model) => {
+                    if let Err(e) = self.index.upsert(model.clone()).await {
+                        log::error!("更新搜索索引失败: {e}");
+                    }
+                    (model.id, Some(&model.slug))
+                }
+                ArticleChanged::Deleted { id, slug } => {
+                    if let Err(e) = self.index.delete(*id).await {
+                        log::error!("删除搜索索引失败: {e}");
+                    }
+                    (*id, slug.as_ref())
+                }
+            };
+            tags.push(CacheTag::Article(id));
+            if let Some(slug) = slug {
+                tags.push(CacheTag::Slug(slug.clone()));
+                slugs.push(slug.clone());
+            }
+        }
+
+        if let Err(e) = self.cache.invalidate(&tags).await {
+            log::error!("清除访客缓存失败: {e}");
+        }
+        self.prerender.push(slugs);
+    }
+}
@@ -0,0 +1,25 @@
+use canvord_service::article_mutation::ArticleMutation;
+use canvord_service::article_query::ArticleQuery;
+use command::cancel_schedule_command::CancelScheduleCommand;
+use dto::app_error::AppError;
+use dto::article::ArticleDetail;
+use sea_orm::DbConn;
+use crate::article_changed_handler::{ArticleChanged, ArticleChangedHandler};
+
+pub struct CancelScheduleHandler<'a> {
+    db: &'a DbConn,
+    changed: &'a ArticleChangedHandler<'a>,
+}
+
+impl<'a> CancelScheduleHandler<'a> {
+    pub fn new(db: &'a DbConn, changed: &'a ArticleChangedHandler<'a>) -> Self {
+        Self { db, changed }
+    }
+
+    pub async fn execute(&self, cmd: CancelScheduleCommand) -> Result<ArticleDetail, AppError> {
+        let model = ArticleMutation::cancel_schedule(self.db, cmd).await?;
+        self.changed.execute(&[ArticleChanged::Saved(model.clone())]).await;
+        let (category, tags) = ArticleQuery::find_relations(self.db, &model).await?;
+        Ok(ArticleDetail::from(model).with_relations(category, tags))
+    }
+}
@@ -0,0 +1,42 @@
+use canvord_service::category_mutation::CategoryMutation;
+use canvord_service::category_query::CategoryQuery;
+use command::create_category_command::CreateCategoryCommand;
+use dto::app_error::AppError;
+use dto::category::CategoryNode;
+use middleware::cache::{CacheInvalidator, CacheTag};
+use sea_orm::DbConn;
+
+pub struct CreateCategoryHandler<'a> {
+    db: &'a DbConn,
+    cache: &'a CacheInvalidator,
+}
+
+impl<'a> CreateCategoryHandler<'a> {
+    pub fn new(db: &'a DbConn, cache: &'a CacheInvalidator) -> Self {
+        Self { db, cache }
+    }
+
+    pub async fn execute(&self, cmd: CreateCategoryCommand) -> Result<CategoryNode, AppError> {
+        if cmd.name.trim().is_empty() || cmd.slug.trim().is_empty() {
+            return Err(AppError::BadRequest("name and slug are required".into()));
+        }
+        if CategoryQuery::find_category_by_slug(self.db, &cmd.slug).await?.is_some() {
+            return Err(AppError::BadRequest("slug already exists".into()));
+        }
+        if let Some(parent_id) = cmd.parent_id
+            && CategoryQuery::find_category_by_id(self.db, parent_id).await?.is_none()
+        {
+            return Err(AppError::BadRequest("parent category not found".into()));
+        }
+
+        let category = CategoryMutation::create(self.db, cmd).await?;
+        self.invalidate().await;
+        Ok(category.into())
+    }
+    /// 分类列表、分类页和文章中的分类信息都已过期
+    async fn invalidate(&self) {
+        if let Err(e) = self.cache.invalidate(&[CacheTag::Categories]).await {
+            log::error!("清除访客缓存失败: {e}");
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use canvord_service::user_mutation::UserMutation;
+use canvord_service::user_query::UserQuery;
+use command::create_user_command::CreateUserCommand;
+use dto::app_error::AppError;
+use dto::user::UserInfo;
+use sea_orm::DbConn;
+
+pub struct CreateUserHandler<'a> {
+    db: &'a DbConn,
+}
+
+impl<'a> CreateUserHandler<'a> {
+    pub fn new(db: &'a DbConn) -> Self {
+        Self { db }
+    }
+
+    pub async fn execute(&self, cmd: CreateUserCommand) -> Result<UserInfo, AppError> {
+        if cmd.username.trim().is_empty() || cmd.password.is_empty() {
+            return Err(AppError::BadRequest("username and password are required".into()));
+        }
+        if UserQuery::find_user_by_username(self.db, &cmd.username).await?.is_some() {
+            return Err(AppError::BadRequest("username already exists".into()));
+        }
+
+        let user = UserMutation::create(self.db, &cmd.username, &cmd.password, cmd.role).await?;
+        Ok(user.into())
+    }
+}
@@ -1,18 +1,26 @@
 use canvord_service::article_mutation::ArticleMutation;
+use canvord_service::article_query::ArticleQuery;
 use command::delete_article_command::DeleteArticleCommand;
 use dto::app_error::AppError;
 use sea_orm::DbConn;
+use crate::article_changed_handler::{ArticleChanged, ArticleChangedHandler};
 
 pub struct DeleteArticleHandler<'a> {
     db: &'a DbConn,
+    changed: &'a ArticleChangedHandler<'a>,
 }
 
 impl<'a> DeleteArticleHandler<'a> {
-    pub fn new(db: &'a DbConn) -> Self {
-        Self { db }
+    pub fn new(db: &'a DbConn, changed: &'a ArticleChangedHandler<'a>) -> Self {
+        Self { db, changed }
     }
     
     pub async fn execute(&self, cmd: DeleteArticleCommand) -> Result<u64, AppError> {
-        Ok(ArticleMutation::delete(self.db, cmd).await?.rows_affected)
+        let id = cmd.id;
+        // 删除后无法再查到 slug，缓存和预渲染页面按删除前的 slug 清理
+        let slug = ArticleQuery::find_article_by_id(self.db, id).await?.map(|m| m.slug);
+        let rows_affected = ArticleMutation::delete(self.db, cmd).await?.rows_affected;
+        self.changed.execute(&[ArticleChanged::Deleted { id, slug }]).await;
+        Ok(rows_affected)
     }
-}
\ No newline at end of file
+}
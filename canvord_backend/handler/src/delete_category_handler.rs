@@ -0,0 +1,44 @@
+use canvord_service::article_query::ArticleQuery;
+use canvord_service::category_mutation::CategoryMutation;
+use command::delete_category_command::DeleteCategoryCommand;
+use dto::app_error::AppError;
+use middleware::cache::{CacheInvalidator, CacheTag};
+use sea_orm::DbConn;
+use crate::prerender_article_handler::PrerenderQueue;
+
+pub struct DeleteCategoryHandler<'a> {
+    db: &'a DbConn,
+    cache: &'a CacheInvalidator,
+    prerender: &'a PrerenderQueue,
+}
+
+impl<'a> DeleteCategoryHandler<'a> {
+    pub fn new(db: &'a DbConn, cache: &'a CacheInvalidator, prerender: &'a PrerenderQueue) -> Self {
+        Self { db, cache, prerender }
+    }
+
+    pub async fn execute(&self, cmd: DeleteCategoryCommand) -> Result<u64, AppError> {
+        // 删除后文章的分类被置空，需要先查出受影响的文章
+        let slugs = self.affected_slugs(cmd.id).await;
+        let rows_affected = CategoryMutation::delete(self.db, cmd).await?.rows_affected;
+        self.invalidate().await;
+        self.prerender.push(slugs);
+        Ok(rows_affected)
+    }
+    /// 分类列表、分类页和文章中的分类信息都已过期
+    async fn invalidate(&self) {
+        if let Err(e) = self.cache.invalidate(&[CacheTag::Categories]).await {
+            log::error!("清除访客缓存失败: {e}");
+        }
+    }
+
+    /// 预渲染的文章页显示分类名称和链接，该分类下的文章页需要重新生成
+    async fn affected_slugs(&self, category_id: i64) -> Vec<String> {
+        ArticleQuery::list_publish_article_slugs_by_category(self.db, category_id)
+            .await
+            .unwrap_or_else(|e| {
+                log::error!("查询分类下的文章失败: {e}");
+                Vec::new()
+            })
+    }
+}
@@ -0,0 +1,37 @@
+use canvord_service::media_mutation::MediaMutation;
+use canvord_service::media_query::MediaQuery;
+use command::delete_media_command::DeleteMediaCommand;
+use dto::app_error::AppError;
+use media::MediaStore;
+use sea_orm::DbConn;
+
+pub struct DeleteMediaHandler<'a> {
+    db: &'a DbConn,
+    store: &'a dyn MediaStore,
+}
+
+impl<'a> DeleteMediaHandler<'a> {
+    pub fn new(db: &'a DbConn, store: &'a dyn MediaStore) -> Self {
+        Self { db, store }
+    }
+
+    pub async fn execute(&self, cmd: DeleteMediaCommand) -> Result<u64, AppError> {
+        let model = MediaQuery::find_media_by_id(self.db, cmd.id).await?
+            .ok_or_else(|| AppError::NotFound("media not found".into()))?;
+
+        let usage = MediaQuery::count_usage(self.db, model.id).await?;
+        if usage > 0 && !cmd.force {
+            return Err(AppError::BadRequest(format!("media is used by {usage} article(s)")));
+        }
+
+        let variants = MediaQuery::list_variants(self.db, vec![model.id]).await?;
+        for key in variants.iter().map(|v| &v.storage_key).chain([&model.storage_key]) {
+            self.store.delete(key).await.map_err(|e| {
+                log::error!("删除媒体文件失败: {e}");
+                AppError::InternalError
+            })?;
+        }
+
+        Ok(MediaMutation::delete(self.db, model.id).await?.rows_affected)
+    }
+}
@@ -0,0 +1,46 @@
+use canvord_service::article_revision_query::ArticleRevisionQuery;
+use dto::app_error::AppError;
+use dto::article_revision::{DiffLine, DiffTag, RevisionDiff};
+use sea_orm::DbConn;
+use similar::{ChangeTag, TextDiff};
+
+pub struct DiffArticleRevisionsHandler<'a> {
+    db: &'a DbConn,
+}
+
+impl<'a> DiffArticleRevisionsHandler<'a> {
+    pub fn new(db: &'a DbConn) -> Self {
+        Self { db }
+    }
+
+    /// 按行比较两个修订的正文，`from` 为旧版本，`to` 为新版本
+    pub async fn execute(&self, from: i64, to: i64) -> Result<RevisionDiff, AppError> {
+        let from = ArticleRevisionQuery::find_revision_by_id(self.db, from)
+            .await?
+            .ok_or_else(|| AppError::NotFound("revision not found".into()))?;
+        let to = ArticleRevisionQuery::find_revision_by_id(self.db, to)
+            .await?
+            .ok_or_else(|| AppError::NotFound("revision not found".into()))?;
+        if from.article_id != to.article_id {
+            return Err(AppError::BadRequest("revisions belong to different articles".into()));
+        }
+
+        let lines = TextDiff::from_lines(&from.content_md, &to.content_md)
+            .iter_all_changes()
+            .map(|change| DiffLine {
+                tag: match change.tag() {
+                    ChangeTag::Equal => DiffTag::Equal,
+                    ChangeTag::Insert => DiffTag::Insert,
+                    ChangeTag::Delete => DiffTag::Delete,
+                },
+                content: change.value().trim_end_matches('\n').to_string(),
+            })
+            .collect();
+
+        Ok(RevisionDiff {
+            from: from.into(),
+            to: to.into(),
+            lines,
+        })
+    }
+}
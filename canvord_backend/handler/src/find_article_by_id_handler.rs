@@ -17,6 +17,7 @@ impl<'a> FindArticleByIdHandler<'a> {
             .await?
             .ok_or_else(|| AppError::NotFound("article not found".into()))?;
 
-        Ok(model.into())
+        let (category, tags) = ArticleQuery::find_relations(self.db, &model).await?;
+        Ok(ArticleDetail::from(model).with_relations(category, tags))
     }
 }
\ No newline at end of file
@@ -0,0 +1,22 @@
+use canvord_service::article_revision_query::ArticleRevisionQuery;
+use dto::app_error::AppError;
+use dto::article_revision::RevisionDetail;
+use sea_orm::DbConn;
+
+pub struct FindArticleRevisionByIdHandler<'a> {
+    db: &'a DbConn,
+}
+
+impl<'a> FindArticleRevisionByIdHandler<'a> {
+    pub fn new(db: &'a DbConn) -> Self {
+        Self { db }
+    }
+
+    pub async fn execute(&self, id: i64) -> Result<RevisionDetail, AppError> {
+        let model = ArticleRevisionQuery::find_revision_by_id(self.db, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("revision not found".into()))?;
+
+        Ok(model.into())
+    }
+}
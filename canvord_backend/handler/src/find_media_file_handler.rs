@@ -0,0 +1,37 @@
+use bytes::Bytes;
+use canvord_service::media_query::MediaQuery;
+use dto::app_error::AppError;
+use media::MediaStore;
+use sea_orm::DbConn;
+
+pub struct FindMediaFileHandler<'a> {
+    db: &'a DbConn,
+    store: &'a dyn MediaStore,
+}
+
+impl<'a> FindMediaFileHandler<'a> {
+    pub fn new(db: &'a DbConn, store: &'a dyn MediaStore) -> Self {
+        Self { db, store }
+    }
+
+    /// If ok, returns (content type, file content).
+    pub async fn execute(&self, key: String) -> Result<(String, Bytes), AppError> {
+        let content_type = match MediaQuery::find_media_by_key(self.db, &key).await? {
+            Some(model) => model.content_type,
+            None => {
+                MediaQuery::find_variant_by_key(self.db, &key).await?
+                    .ok_or_else(|| AppError::NotFound("media not found".into()))?;
+                "image/webp".to_owned()
+            }
+        };
+
+        let data = self.store.get(&key).await
+            .map_err(|e| {
+                log::error!("读取媒体文件失败: {e}");
+                AppError::InternalError
+            })?
+            .ok_or_else(|| AppError::NotFound("media not found".into()))?;
+
+        Ok((content_type, data))
+    }
+}
@@ -0,0 +1,22 @@
+use canvord_service::user_query::UserQuery;
+use dto::app_error::AppError;
+use dto::user::UserInfo;
+use sea_orm::DbConn;
+
+pub struct FindUserByIdHandler<'a> {
+    db: &'a DbConn,
+}
+
+impl<'a> FindUserByIdHandler<'a> {
+    pub fn new(db: &'a DbConn) -> Self {
+        Self { db }
+    }
+
+    pub async fn execute(&self, id: i64) -> Result<UserInfo, AppError> {
+        let model = UserQuery::find_user_by_id(self.db, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("user not found".into()))?;
+
+        Ok(model.into())
+    }
+}
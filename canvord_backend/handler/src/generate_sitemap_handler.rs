@@ -0,0 +1,33 @@
+use canvord_service::article_query::ArticleQuery;
+use dto::app_error::AppError;
+use feed::{SitemapUrl, Sitemaps};
+use sea_orm::DbConn;
+
+pub struct GenerateSitemapHandler<'a> {
+    db: &'a DbConn,
+    /// 访客站点地址，不以 `/` 结尾
+    site_url: String,
+}
+
+impl<'a> GenerateSitemapHandler<'a> {
+    pub fn new(db: &'a DbConn, site_url: String) -> Self {
+        Self { db, site_url }
+    }
+
+    /// 只包含首页和已发布的文章；结果由接口层的响应缓存保存，这里每次都重新查询
+    pub async fn execute(&self) -> Result<Sitemaps, AppError> {
+        let articles = ArticleQuery::list_publish_article_slugs(self.db).await?;
+
+        let mut urls = Vec::with_capacity(articles.len() + 1);
+        urls.push(SitemapUrl {
+            loc: format!("{}/", self.site_url),
+            lastmod: articles.iter().map(|(_, last_update)| *last_update).max(),
+        });
+        urls.extend(articles.into_iter().map(|(slug, last_update)| SitemapUrl {
+            loc: format!("{}/article/{slug}", self.site_url),
+            lastmod: Some(last_update),
+        }));
+
+        Ok(feed::sitemaps(&urls, |n| format!("{}/sitemap-{n}.xml", self.site_url)))
+    }
+}
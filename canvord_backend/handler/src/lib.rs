@@ -9,5 +9,34 @@ pub mod find_article_by_id_handler;
 pub mod find_article_by_slug_handler;
 pub mod list_article_by_title_handler;
 pub mod list_articles_in_page_by_status_handler;
+pub mod list_articles_by_cursor_handler;
 pub mod find_publish_article_by_slug_handler;
 pub mod list_publish_article_by_title_handler;
+pub mod login_handler;
+pub mod create_user_handler;
+pub mod find_user_by_id_handler;
+pub mod list_article_revisions_handler;
+pub mod find_article_revision_by_id_handler;
+pub mod diff_article_revisions_handler;
+pub mod restore_article_revision_handler;
+pub mod list_tags_with_count_handler;
+pub mod list_publish_articles_in_page_by_tag_handler;
+pub mod list_category_tree_handler;
+pub mod create_category_handler;
+pub mod update_category_handler;
+pub mod delete_category_handler;
+pub mod list_publish_articles_in_page_by_category_handler;
+pub mod search_articles_handler;
+pub mod upload_media_handler;
+pub mod list_media_in_page_handler;
+pub mod delete_media_handler;
+pub mod find_media_file_handler;
+pub mod preview_article_handler;
+pub mod list_feed_articles_handler;
+pub mod generate_sitemap_handler;
+pub mod schedule_article_handler;
+pub mod cancel_schedule_handler;
+pub mod list_scheduled_articles_handler;
+pub mod publish_scheduled_articles_handler;
+pub mod prerender_article_handler;
+pub mod article_changed_handler;
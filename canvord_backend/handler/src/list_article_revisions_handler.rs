@@ -0,0 +1,20 @@
+use canvord_service::article_revision_query::ArticleRevisionQuery;
+use dto::app_error::AppError;
+use dto::article_revision::RevisionMeta;
+use sea_orm::DbConn;
+
+pub struct ListArticleRevisionsHandler<'a> {
+    db: &'a DbConn,
+}
+
+impl<'a> ListArticleRevisionsHandler<'a> {
+    pub fn new(db: &'a DbConn) -> Self {
+        Self { db }
+    }
+
+    pub async fn execute(&self, article_id: i64) -> Result<Vec<RevisionMeta>, AppError> {
+        let revisions = ArticleRevisionQuery::list_revisions_by_article(self.db, article_id).await?;
+
+        Ok(revisions.into_iter().map(RevisionMeta::from).collect())
+    }
+}
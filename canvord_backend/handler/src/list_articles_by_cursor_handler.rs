@@ -0,0 +1,48 @@
+use canvord_service::article_query::ArticleQuery;
+use dto::app_error::AppError;
+use dto::article::ArticleMeta;
+use dto::pagination::{Cursor, CursorPage};
+use entity::article::{Model, Status};
+use sea_orm::DbConn;
+
+pub struct ListArticlesByCursorHandler<'a> {
+    db: &'a DbConn,
+}
+
+impl<'a> ListArticlesByCursorHandler<'a> {
+    pub fn new(db: &'a DbConn) -> Self {
+        Self { db }
+    }
+
+    /// `cursor` 为空时返回第一页，否则为上次结果中的 `next` 或 `prev`
+    pub async fn execute(
+        &self,
+        cursor: Option<String>,
+        posts_per_page: u64,
+        status: Option<Status>,
+    ) -> Result<CursorPage<ArticleMeta>, AppError> {
+        let cursor = match cursor {
+            Some(c) => Some(Cursor::decode(&c).ok_or_else(|| AppError::BadRequest("Invalid cursor".to_string()))?),
+            None => None,
+        };
+        let forward = cursor.is_none_or(|c| c.forward);
+
+        let (articles, has_more) = ArticleQuery::list_articles_by_cursor(
+            self.db,
+            status,
+            cursor.map(|c| (c.created_at, c.id, c.inclusive)),
+            forward,
+            posts_per_page,
+        ).await?;
+
+        let position = |(m, _): &(Model, _)| (m.created_at, m.id);
+        let (next, prev) = Cursor::links(cursor, articles.first().map(position), articles.last().map(position), has_more);
+
+        Ok(CursorPage {
+            size: posts_per_page as usize,
+            data: articles.into_iter().map(ArticleMeta::from).collect(),
+            next,
+            prev,
+        })
+    }
+}
@@ -0,0 +1,19 @@
+use canvord_service::category_query::CategoryQuery;
+use dto::app_error::AppError;
+use dto::category::CategoryNode;
+use sea_orm::DbConn;
+
+pub struct ListCategoryTreeHandler<'a> {
+    db: &'a DbConn,
+}
+
+impl<'a> ListCategoryTreeHandler<'a> {
+    pub fn new(db: &'a DbConn) -> Self {
+        Self { db }
+    }
+
+    pub async fn execute(&self) -> Result<Vec<CategoryNode>, AppError> {
+        let categories = CategoryQuery::list_categories(self.db).await?;
+        Ok(CategoryNode::build_tree(categories))
+    }
+}
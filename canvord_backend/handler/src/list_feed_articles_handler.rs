@@ -0,0 +1,56 @@
+use canvord_service::article_query::ArticleQuery;
+use canvord_service::category_query::CategoryQuery;
+use canvord_service::tag_query::TagQuery;
+use dto::app_error::AppError;
+use dto::article::ArticleDetail;
+use dto::category::CategoryNode;
+use sea_orm::DbConn;
+
+/// 订阅源包含的文章范围
+#[derive(Debug, Clone)]
+pub enum FeedScope {
+    All,
+    /// 分类 slug，包含所有子分类下的文章
+    Category(String),
+    /// 标签 slug
+    Tag(String),
+}
+
+pub struct ListFeedArticlesHandler<'a> {
+    db: &'a DbConn,
+}
+
+impl<'a> ListFeedArticlesHandler<'a> {
+    pub fn new(db: &'a DbConn) -> Self {
+        Self { db }
+    }
+
+    /// If ok, returns (category or tag name, latest published articles with relations).
+    pub async fn execute(&self, scope: FeedScope, limit: u64) -> Result<(Option<String>, Vec<ArticleDetail>), AppError> {
+        let (name, articles) = match scope {
+            FeedScope::All => (None, ArticleQuery::list_latest_publish_articles(self.db, None, limit).await?),
+            FeedScope::Category(slug) => {
+                let tree = CategoryNode::build_tree(CategoryQuery::list_categories(self.db).await?);
+                let category = CategoryNode::find_by_slug(&tree, &slug)
+                    .ok_or_else(|| AppError::NotFound("category not found".into()))?;
+                let articles = ArticleQuery::list_latest_publish_articles(self.db, Some(category.subtree_ids()), limit).await?;
+                (Some(category.name), articles)
+            }
+            FeedScope::Tag(slug) => {
+                let tag = TagQuery::find_tag_by_slug(self.db, &slug)
+                    .await?
+                    .ok_or_else(|| AppError::NotFound("tag not found".into()))?;
+                let articles = TagQuery::list_latest_publish_articles_by_tag(self.db, tag.id, limit).await?;
+                (Some(tag.name), articles)
+            }
+        };
+
+        let mut details = Vec::with_capacity(articles.len());
+        for model in articles {
+            let (category, tags) = ArticleQuery::find_relations(self.db, &model).await?;
+            details.push(ArticleDetail::from(model).with_relations(category, tags));
+        }
+
+        Ok((name, details))
+    }
+}
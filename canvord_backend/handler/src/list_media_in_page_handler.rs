@@ -0,0 +1,47 @@
+use canvord_service::media_query::MediaQuery;
+use dto::app_error::AppError;
+use dto::media::{MediaInfo, MediaVariantInfo};
+use media::MediaStore;
+use sea_orm::DbConn;
+
+pub struct ListMediaInPageHandler<'a> {
+    db: &'a DbConn,
+    store: &'a dyn MediaStore,
+}
+
+impl<'a> ListMediaInPageHandler<'a> {
+    pub fn new(db: &'a DbConn, store: &'a dyn MediaStore) -> Self {
+        Self { db, store }
+    }
+
+    pub async fn execute(&self, page: u64, per_page: u64) -> Result<(Vec<MediaInfo>, u64), AppError> {
+        let (media, num) = MediaQuery::list_media_in_page(self.db, page, per_page).await?;
+        let variants = MediaQuery::list_variants(self.db, media.iter().map(|m| m.id).collect()).await?;
+
+        let media = media
+            .into_iter()
+            .map(|m| MediaInfo {
+                id: m.id,
+                owner_id: m.owner_id,
+                url: self.store.url(&m.storage_key),
+                key: m.storage_key,
+                file_name: m.file_name,
+                content_type: m.content_type,
+                size: m.size,
+                width: m.width,
+                height: m.height,
+                variants: variants.iter()
+                    .filter(|v| v.media_id == m.id)
+                    .map(|v| MediaVariantInfo {
+                        width: v.width,
+                        url: self.store.url(&v.storage_key),
+                    })
+                    .collect(),
+                usage_count: m.usage_count as u64,
+                created_at: m.created_at,
+            })
+            .collect();
+
+        Ok((media, num))
+    }
+}
@@ -0,0 +1,42 @@
+use canvord_service::article_query::ArticleQuery;
+use canvord_service::category_query::CategoryQuery;
+use dto::app_error::AppError;
+use dto::article::ArticleMeta;
+use dto::category::CategoryNode;
+use sea_orm::DbConn;
+
+pub struct ListPublishArticlesInPageByCategoryHandler<'a> {
+    db: &'a DbConn,
+}
+
+impl<'a> ListPublishArticlesInPageByCategoryHandler<'a> {
+    pub fn new(db: &'a DbConn) -> Self {
+        Self { db }
+    }
+
+    /// 包含所有子分类下的文章
+    pub async fn execute(
+        &self,
+        slug: String,
+        page: u64,
+        posts_per_page: u64,
+    ) -> Result<(Vec<ArticleMeta>, u64), AppError> {
+        let tree = CategoryNode::build_tree(CategoryQuery::list_categories(self.db).await?);
+        let category = CategoryNode::find_by_slug(&tree, &slug)
+            .ok_or_else(|| AppError::NotFound("category not found".into()))?;
+
+        let (articles, num) = ArticleQuery::list_publish_articles_in_page_by_categories(
+            self.db,
+            category.subtree_ids(),
+            page,
+            posts_per_page,
+        ).await?;
+
+        let article_metas: Vec<ArticleMeta> = articles
+            .into_iter()
+            .map(ArticleMeta::from)
+            .collect();
+
+        Ok((article_metas, num))
+    }
+}
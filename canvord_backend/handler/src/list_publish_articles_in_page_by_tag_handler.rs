@@ -0,0 +1,39 @@
+use canvord_service::tag_query::TagQuery;
+use dto::app_error::AppError;
+use dto::article::ArticleMeta;
+use sea_orm::DbConn;
+
+pub struct ListPublishArticlesInPageByTagHandler<'a> {
+    db: &'a DbConn,
+}
+
+impl<'a> ListPublishArticlesInPageByTagHandler<'a> {
+    pub fn new(db: &'a DbConn) -> Self {
+        Self { db }
+    }
+
+    pub async fn execute(
+        &self,
+        slug: String,
+        page: u64,
+        posts_per_page: u64,
+    ) -> Result<(Vec<ArticleMeta>, u64), AppError> {
+        let tag = TagQuery::find_tag_by_slug(self.db, &slug)
+            .await?
+            .ok_or_else(|| AppError::NotFound("tag not found".into()))?;
+
+        let (articles, num) = TagQuery::list_publish_articles_in_page_by_tag(
+            self.db,
+            tag.id,
+            page,
+            posts_per_page,
+        ).await?;
+
+        let article_metas: Vec<ArticleMeta> = articles
+            .into_iter()
+            .map(ArticleMeta::from)
+            .collect();
+
+        Ok((article_metas, num))
+    }
+}
@@ -0,0 +1,19 @@
+use canvord_service::article_query::ArticleQuery;
+use dto::app_error::AppError;
+use dto::article::ArticleMeta;
+use sea_orm::DbConn;
+
+pub struct ListScheduledArticlesHandler<'a> {
+    db: &'a DbConn,
+}
+
+impl<'a> ListScheduledArticlesHandler<'a> {
+    pub fn new(db: &'a DbConn) -> Self {
+        Self { db }
+    }
+
+    pub async fn execute(&self) -> Result<Vec<ArticleMeta>, AppError> {
+        let articles = ArticleQuery::list_scheduled_articles(self.db).await?;
+        Ok(articles.into_iter().map(ArticleMeta::from).collect())
+    }
+}
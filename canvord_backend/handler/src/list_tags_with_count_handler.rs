@@ -0,0 +1,31 @@
+use canvord_service::tag_query::TagQuery;
+use dto::app_error::AppError;
+use dto::tag::{TagInfo, TagWithCount};
+use sea_orm::DbConn;
+
+pub struct ListTagsWithCountHandler<'a> {
+    db: &'a DbConn,
+}
+
+impl<'a> ListTagsWithCountHandler<'a> {
+    pub fn new(db: &'a DbConn) -> Self {
+        Self { db }
+    }
+
+    pub async fn execute(&self) -> Result<Vec<TagWithCount>, AppError> {
+        let tags = TagQuery::list_tags_with_count(self.db)
+            .await?
+            .into_iter()
+            .map(|t| TagWithCount {
+                tag: TagInfo {
+                    id: t.id,
+                    name: t.name,
+                    slug: t.slug,
+                },
+                article_count: t.article_count as u64,
+            })
+            .collect();
+
+        Ok(tags)
+    }
+}
@@ -0,0 +1,34 @@
+use canvord_service::password::{verify_dummy_password, verify_password};
+use canvord_service::user_query::UserQuery;
+use dto::app_error::AppError;
+use entity::user;
+use sea_orm::DbConn;
+
+pub struct LoginHandler<'a> {
+    db: &'a DbConn,
+}
+
+impl<'a> LoginHandler<'a> {
+    pub fn new(db: &'a DbConn) -> Self {
+        Self { db }
+    }
+
+    /// Argon2 校验每次耗时数十毫秒，在阻塞线程池中执行，不占用 worker
+    pub async fn execute(&self, username: String, password: String) -> Result<user::Model, AppError> {
+        let found = UserQuery::find_user_by_username(self.db, &username).await?;
+        tokio::task::spawn_blocking(move || match found {
+            Some(user) if verify_password(&password, &user.password_hash) => Some(user),
+            Some(_) => None,
+            None => {
+                verify_dummy_password(&password);
+                None
+            }
+        })
+        .await
+        .map_err(|e| {
+            log::error!("校验密码失败: {e}");
+            AppError::InternalError
+        })?
+        .ok_or_else(|| AppError::Unauthorized("用户名或密码错误".into()))
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use canvord_service::article_query::ArticleQuery;
+use dto::app_error::AppError;
+use dto::article::ArticleDetail;
+use pages::{is_path_segment, remove_stale, ArticleLink, ArticlePage, PageMeta, Site, Term};
+use sea_orm::{DbConn, DbErr};
+use tokio::sync::Notify;
+
+/// 预渲染页面的模板、输出目录和站点信息
+pub struct PrerenderTarget {
+    /// 访客客户端构建出的 `index.html`
+    pub template: String,
+    pub out_dir: PathBuf,
+    pub site: Site,
+}
+
+/// 等待更新预渲染页面的文章 slug。修改文章或分类时只在这里登记受影响的 slug，
+/// 由后台任务合并后交给 [`PrerenderArticleHandler`]，渲染和写文件不占用请求
+#[derive(Default)]
+pub struct PrerenderQueue {
+    pending: Mutex<HashSet<String>>,
+    notify: Notify,
+}
+
+impl PrerenderQueue {
+    pub fn push<I: IntoIterator<Item = String>>(&self, slugs: I) {
+        let mut pending = self.pending.lock().unwrap();
+        let len = pending.len();
+        pending.extend(slugs);
+        if pending.len() > len {
+            self.notify.notify_one();
+        }
+    }
+
+    /// 等到有登记的 slug 后全部取出
+    pub async fn pop(&self) -> Vec<String> {
+        loop {
+            let slugs: Vec<String> = self.pending.lock().unwrap().drain().collect();
+            if !slugs.is_empty() {
+                return slugs;
+            }
+            self.notify.notified().await;
+        }
+    }
+}
+
+/// 文章变化后更新 `canvord_backend prerender` 生成的页面：只重新生成受影响的文章页，
+/// 有文章页新增或删除（包括修改 slug 前的旧目录）时再更新首页的文章列表。未配置预渲染时不做任何事
+pub struct PrerenderArticleHandler<'a> {
+    db: &'a DbConn,
+    target: Option<PrerenderTarget>,
+}
+
+impl<'a> PrerenderArticleHandler<'a> {
+    pub fn new(db: &'a DbConn, target: Option<PrerenderTarget>) -> Self {
+        Self { db, target }
+    }
+
+    pub async fn execute(&self, slugs: &[String]) -> Result<(), AppError> {
+        let Some(target) = &self.target else { return Ok(()) };
+        let article_dir = target.out_dir.join("article");
+
+        // 仍公开的文章页出现在首页上；不再公开的文章只有原来生成过页面时才影响首页
+        let mut listing_changed = false;
+        for slug in slugs.iter().filter(|s| is_path_segment(s)) {
+            let dir = article_dir.join(slug);
+            let Some(model) = ArticleQuery::find_publish_article_by_slug(self.db, slug.clone()).await? else {
+                listing_changed |= tokio::fs::try_exists(&dir).await.unwrap_or(true);
+                continue;
+            };
+            let (category, tags) = ArticleQuery::find_relations(self.db, &model).await?;
+            let article = article_page(ArticleDetail::from(model).with_relations(category, tags));
+            let html = pages::prerender(&target.template, &target.site, &article.meta(&target.site), &pages::article_body(&article));
+            tokio::fs::create_dir_all(&dir).await.map_err(io_error)?;
+            tokio::fs::write(dir.join("index.html"), html).await.map_err(io_error)?;
+            listing_changed = true;
+        }
+        if !listing_changed {
+            return Ok(());
+        }
+
+        // 首页只需要链接和摘要，不读取正文
+        let links: Vec<ArticleLink> = ArticleQuery::list_publish_article_links(self.db)
+            .await?
+            .into_iter()
+            .filter(|(slug, ..)| is_path_segment(slug))
+            .map(|(slug, title, description)| ArticleLink { slug, title, description })
+            .collect();
+
+        let published: HashSet<String> = links.iter().map(|a| a.slug.clone()).collect();
+        tokio::task::spawn_blocking(move || remove_stale(&article_dir, &published.iter().map(String::as_str).collect()))
+            .await
+            .map_err(|e| io_error(std::io::Error::other(e)))?
+            .map_err(io_error)?;
+
+        let site = &target.site;
+        let home = PageMeta {
+            title: site.title.clone(),
+            description: site.description.clone(),
+            url: format!("{}/", site.url),
+            article: None,
+        };
+        let html = pages::prerender(&target.template, site, &home, &pages::home_body(site, &links));
+        tokio::fs::write(target.out_dir.join("index.html"), html).await.map_err(io_error)
+    }
+}
+
+fn io_error(e: std::io::Error) -> AppError {
+    log::error!("写入预渲染页面失败: {e}");
+    AppError::InternalError
+}
+
+/// 预渲染和静态导出用到的全部已发布文章，最新的在前，附带分类 id；
+/// slug 不能作为目录名的文章不会输出，以 (id, slug) 放在第二项中返回
+pub async fn list_article_pages(db: &DbConn) -> Result<(Vec<(Option<i64>, ArticlePage)>, Vec<(i64, String)>), DbErr> {
+    let mut pages = Vec::new();
+    let mut skipped = Vec::new();
+    for (model, category, tags) in ArticleQuery::list_publish_articles_with_relations(db).await? {
+        if !is_path_segment(&model.slug) {
+            skipped.push((model.id, model.slug));
+            continue;
+        }
+        pages.push((model.category_id, article_page(ArticleDetail::from(model).with_relations(category, tags))));
+    }
+    Ok((pages, skipped))
+}
+
+pub fn article_page(detail: ArticleDetail) -> ArticlePage {
+    let meta = detail.meta;
+    ArticlePage {
+        title: meta.title,
+        description: meta.description,
+        slug: meta.slug,
+        category: meta.category.map(|c| Term { name: c.name, slug: c.slug }),
+        tags: detail.tags.into_iter().map(|t| Term { name: t.name, slug: t.slug }).collect(),
+        content_html: detail.content_html,
+        toc: detail.toc.into_iter().map(|e| (e.level, e.id, e.text)).collect(),
+        word_count: detail.word_count,
+        reading_minutes: detail.reading_minutes,
+        created_at: detail.created_at,
+        last_update: meta.last_update,
+    }
+}
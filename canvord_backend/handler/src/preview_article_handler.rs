@@ -0,0 +1,27 @@
+use canvord_service::article_render::ArticleRender;
+use command::preview_article_command::PreviewArticleCommand;
+use dto::app_error::AppError;
+use dto::article::RenderedContent;
+use sea_orm::DbConn;
+
+pub struct PreviewArticleHandler<'a> {
+    db: &'a DbConn,
+}
+
+impl<'a> PreviewArticleHandler<'a> {
+    pub fn new(db: &'a DbConn) -> Self {
+        Self { db }
+    }
+
+    /// 与保存时使用同一套渲染规则，但不写入数据库
+    pub async fn execute(&self, cmd: PreviewArticleCommand) -> Result<RenderedContent, AppError> {
+        let rendered = ArticleRender::render(self.db, &cmd.content_md).await?;
+
+        Ok(RenderedContent {
+            content_html: rendered.html,
+            toc: ArticleRender::toc(rendered.toc).0,
+            word_count: rendered.word_count as i32,
+            reading_minutes: rendered.reading_minutes as i32,
+        })
+    }
+}
@@ -1,21 +1,25 @@
 use canvord_service::article_mutation::ArticleMutation;
+use canvord_service::article_query::ArticleQuery;
 use command::publish_draft_command::PublishDraftCommand;
 use dto::app_error::AppError;
 use dto::article::ArticleDetail;
 use sea_orm::DbConn;
+use crate::article_changed_handler::{ArticleChanged, ArticleChangedHandler};
 
 pub struct PublishDraftHandler<'a> {
     db: &'a DbConn,
+    changed: &'a ArticleChangedHandler<'a>,
 }
 
 impl<'a> PublishDraftHandler<'a> {
-    pub fn new(db: &'a DbConn) -> Self {
-        Self { db }
+    pub fn new(db: &'a DbConn, changed: &'a ArticleChangedHandler<'a>) -> Self {
+        Self { db, changed }
     }
     
     pub async fn execute(&self, cmd: PublishDraftCommand) -> Result<ArticleDetail, AppError> {
-        ArticleMutation::publish_draft(self.db, cmd).await
-            .map(|m| m.into())
-            .map_err(|e| AppError::DbError(e.to_string()))
+        let model = ArticleMutation::publish_draft(self.db, cmd).await?;
+        self.changed.execute(&[ArticleChanged::Saved(model.clone())]).await;
+        let (category, tags) = ArticleQuery::find_relations(self.db, &model).await?;
+        Ok(ArticleDetail::from(model).with_relations(category, tags))
     }
-}
\ No newline at end of file
+}
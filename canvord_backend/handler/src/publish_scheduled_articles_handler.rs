@@ -0,0 +1,31 @@
+use canvord_service::article_mutation::ArticleMutation;
+use canvord_service::article_query::ArticleQuery;
+use chrono::{NaiveDateTime, Utc};
+use dto::app_error::AppError;
+use dto::article::ArticleMeta;
+use sea_orm::DbConn;
+use crate::article_changed_handler::{ArticleChanged, ArticleChangedHandler};
+
+/// 由后台任务调用，发布所有已到时间的定时文章
+pub struct PublishScheduledArticlesHandler<'a> {
+    db: &'a DbConn,
+    changed: &'a ArticleChangedHandler<'a>,
+}
+
+impl<'a> PublishScheduledArticlesHandler<'a> {
+    pub fn new(db: &'a DbConn, changed: &'a ArticleChangedHandler<'a>) -> Self {
+        Self { db, changed }
+    }
+
+    pub async fn execute(&self) -> Result<Vec<ArticleMeta>, AppError> {
+        let published = ArticleMutation::publish_due(self.db, Utc::now().naive_utc()).await?;
+        let changes: Vec<ArticleChanged> = published.iter().cloned().map(ArticleChanged::Saved).collect();
+        self.changed.execute(&changes).await;
+        Ok(published.into_iter().map(ArticleMeta::from).collect())
+    }
+
+    /// 最近一篇待发布文章的发布时间
+    pub async fn next_publish_at(&self) -> Result<Option<NaiveDateTime>, AppError> {
+        Ok(ArticleQuery::next_publish_at(self.db).await?)
+    }
+}
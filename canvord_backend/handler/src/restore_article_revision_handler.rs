@@ -0,0 +1,25 @@
+use canvord_service::article_mutation::ArticleMutation;
+use canvord_service::article_query::ArticleQuery;
+use command::restore_article_revision_command::RestoreArticleRevisionCommand;
+use dto::app_error::AppError;
+use dto::article::ArticleDetail;
+use sea_orm::DbConn;
+use crate::article_changed_handler::{ArticleChanged, ArticleChangedHandler};
+
+pub struct RestoreArticleRevisionHandler<'a> {
+    db: &'a DbConn,
+    changed: &'a ArticleChangedHandler<'a>,
+}
+
+impl<'a> RestoreArticleRevisionHandler<'a> {
+    pub fn new(db: &'a DbConn, changed: &'a ArticleChangedHandler<'a>) -> Self {
+        Self { db, changed }
+    }
+
+    pub async fn execute(&self, cmd: RestoreArticleRevisionCommand) -> Result<ArticleDetail, AppError> {
+        let model = ArticleMutation::restore_revision(self.db, cmd).await?;
+        self.changed.execute(&[ArticleChanged::Saved(model.clone())]).await;
+        let (category, tags) = ArticleQuery::find_relations(self.db, &model).await?;
+        Ok(ArticleDetail::from(model).with_relations(category, tags))
+    }
+}
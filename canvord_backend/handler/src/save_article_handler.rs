@@ -1,21 +1,25 @@
 use canvord_service::article_mutation::ArticleMutation;
+use canvord_service::article_query::ArticleQuery;
 use command::save_article_command::SaveArticleCommand;
 use dto::app_error::AppError;
 use dto::article::ArticleDetail;
-use sea_orm::DbConn;
+use sea_orm::{DbConn, TryIntoModel};
+use crate::article_changed_handler::{ArticleChanged, ArticleChangedHandler};
 
 pub struct SaveArticleHandler<'a> {
     db: &'a DbConn,
+    changed: &'a ArticleChangedHandler<'a>,
 }
 
 impl<'a> SaveArticleHandler<'a> {
-    pub fn new(db: &'a DbConn) -> Self {
-        Self { db }
+    pub fn new(db: &'a DbConn, changed: &'a ArticleChangedHandler<'a>) -> Self {
+        Self { db, changed }
     }
     
-    pub async fn execute(&self, cmd: SaveArticleCommand) -> Result<ArticleDetail, AppError> {
-        let active_model = ArticleMutation::save_draft(self.db, cmd).await?;
-        let detail: ArticleDetail = active_model.try_into()?;
-        Ok(detail)
+    pub async fn execute(&self, cmd: SaveArticleCommand, author_id: i64) -> Result<ArticleDetail, AppError> {
+        let model = ArticleMutation::save_draft(self.db, cmd, author_id).await?.try_into_model()?;
+        self.changed.execute(&[ArticleChanged::Saved(model.clone())]).await;
+        let (category, tags) = ArticleQuery::find_relations(self.db, &model).await?;
+        Ok(ArticleDetail::from(model).with_relations(category, tags))
     }
-}
\ No newline at end of file
+}
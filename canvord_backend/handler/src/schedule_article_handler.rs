@@ -0,0 +1,34 @@
+use canvord_service::article_mutation::ArticleMutation;
+use canvord_service::article_query::ArticleQuery;
+use chrono::Utc;
+use command::schedule_article_command::ScheduleArticleCommand;
+use dto::app_error::AppError;
+use dto::article::ArticleDetail;
+use sea_orm::DbConn;
+use tokio::sync::Notify;
+use crate::article_changed_handler::{ArticleChanged, ArticleChangedHandler};
+
+pub struct ScheduleArticleHandler<'a> {
+    db: &'a DbConn,
+    changed: &'a ArticleChangedHandler<'a>,
+    /// 唤醒定时发布任务，使其按新的发布时间重新计算等待时间
+    scheduler: &'a Notify,
+}
+
+impl<'a> ScheduleArticleHandler<'a> {
+    pub fn new(db: &'a DbConn, changed: &'a ArticleChangedHandler<'a>, scheduler: &'a Notify) -> Self {
+        Self { db, changed, scheduler }
+    }
+
+    pub async fn execute(&self, cmd: ScheduleArticleCommand) -> Result<ArticleDetail, AppError> {
+        if cmd.publish_at <= Utc::now().naive_utc() {
+            return Err(AppError::BadRequest("publish_at must be in the future".into()));
+        }
+
+        let model = ArticleMutation::schedule(self.db, cmd).await?;
+        self.changed.execute(&[ArticleChanged::Saved(model.clone())]).await;
+        self.scheduler.notify_one();
+        let (category, tags) = ArticleQuery::find_relations(self.db, &model).await?;
+        Ok(ArticleDetail::from(model).with_relations(category, tags))
+    }
+}
@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+use canvord_service::article_query::ArticleQuery;
+use dto::app_error::AppError;
+use dto::article::ArticleMeta;
+use dto::search::SearchHit;
+use search::SearchIndex;
+use sea_orm::DbConn;
+
+pub struct SearchArticlesHandler<'a> {
+    db: &'a DbConn,
+    index: &'a SearchIndex,
+}
+
+impl<'a> SearchArticlesHandler<'a> {
+    pub fn new(db: &'a DbConn, index: &'a SearchIndex) -> Self {
+        Self { db, index }
+    }
+
+    /// If ok, returns (hits ordered by score, num pages).
+    pub async fn execute(&self, q: String, page: u64, per: u64, published_only: bool) -> Result<(Vec<SearchHit>, u64), AppError> {
+        let (page_index, page_size) = (usize::try_from(page).unwrap_or(usize::MAX), usize::try_from(per).unwrap_or(usize::MAX));
+        if search::result_offset(page_index, page_size).is_none() {
+            return Err(AppError::BadRequest(format!("page * per must not exceed {}", search::MAX_RESULTS)));
+        }
+
+        let result = self.index
+            .search(q, page_index, page_size, published_only)
+            .await
+            .map_err(|e| {
+                log::error!("搜索失败: {e}");
+                AppError::InternalError
+            })?;
+
+        let ids = result.hits.iter().map(|h| h.id).collect();
+        let mut metas: HashMap<i64, ArticleMeta> = ArticleQuery::list_articles_by_ids(self.db, ids).await?
+            .into_iter()
+            .map(|pair| (pair.0.id, ArticleMeta::from(pair)))
+            .collect();
+
+        // 保持索引给出的相关度顺序，跳过索引中残留但数据库已不存在的文章
+        let hits = result.hits
+            .into_iter()
+            .filter_map(|hit| {
+                metas.remove(&hit.id).map(|meta| SearchHit {
+                    meta,
+                    score: hit.score,
+                    title_html: hit.title_html,
+                    snippet_html: hit.snippet_html,
+                })
+            })
+            .collect();
+
+        Ok((hits, (result.total as u64).div_ceil(per)))
+    }
+}
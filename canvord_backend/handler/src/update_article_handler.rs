@@ -1,21 +1,26 @@
 use canvord_service::article_mutation::ArticleMutation;
+use canvord_service::article_query::ArticleQuery;
 use command::update_article_command::UpdateArticleCommand;
 use dto::app_error::AppError;
 use dto::article::ArticleDetail;
 use sea_orm::DbConn;
+use crate::article_changed_handler::{ArticleChanged, ArticleChangedHandler};
 
 pub struct UpdateArticleHandler<'a> {
     db: &'a DbConn,
+    changed: &'a ArticleChangedHandler<'a>,
 }
 
 impl<'a> UpdateArticleHandler<'a> {
-    pub fn new(db: &'a DbConn) -> Self {
-        Self { db }
+    pub fn new(db: &'a DbConn, changed: &'a ArticleChangedHandler<'a>) -> Self {
+        Self { db, changed }
     }
     
-    pub async fn execute(&self, cmd: UpdateArticleCommand) -> Result<ArticleDetail, AppError> {
-        ArticleMutation::update(self.db, cmd)
-            .await.map(|m| m.into())
-            .map_err(|e| AppError::DbError(e.to_string()))
+    /// `owner` 为没有编辑权限的作者的用户 id，此时只能修改其本人的草稿
+    pub async fn execute(&self, cmd: UpdateArticleCommand, owner: Option<i64>) -> Result<ArticleDetail, AppError> {
+        let model = ArticleMutation::update(self.db, cmd, owner).await?;
+        self.changed.execute(&[ArticleChanged::Saved(model.clone())]).await;
+        let (category, tags) = ArticleQuery::find_relations(self.db, &model).await?;
+        Ok(ArticleDetail::from(model).with_relations(category, tags))
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,69 @@
+use canvord_service::article_query::ArticleQuery;
+use canvord_service::category_mutation::CategoryMutation;
+use canvord_service::category_query::CategoryQuery;
+use command::update_category_command::UpdateCategoryCommand;
+use dto::app_error::AppError;
+use dto::category::CategoryNode;
+use middleware::cache::{CacheInvalidator, CacheTag};
+use sea_orm::DbConn;
+use crate::prerender_article_handler::PrerenderQueue;
+
+pub struct UpdateCategoryHandler<'a> {
+    db: &'a DbConn,
+    cache: &'a CacheInvalidator,
+    prerender: &'a PrerenderQueue,
+}
+
+impl<'a> UpdateCategoryHandler<'a> {
+    pub fn new(db: &'a DbConn, cache: &'a CacheInvalidator, prerender: &'a PrerenderQueue) -> Self {
+        Self { db, cache, prerender }
+    }
+
+    pub async fn execute(&self, cmd: UpdateCategoryCommand) -> Result<CategoryNode, AppError> {
+        if cmd.name.trim().is_empty() || cmd.slug.trim().is_empty() {
+            return Err(AppError::BadRequest("name and slug are required".into()));
+        }
+        if let Some(other) = CategoryQuery::find_category_by_slug(self.db, &cmd.slug).await?
+            && other.id != cmd.id
+        {
+            return Err(AppError::BadRequest("slug already exists".into()));
+        }
+
+        // 沿新父级向上查找，不能回到自身，否则会形成环
+        let mut ancestor = cmd.parent_id;
+        while let Some(id) = ancestor {
+            if id == cmd.id {
+                return Err(AppError::BadRequest("category cannot be moved under itself".into()));
+            }
+            ancestor = CategoryQuery::find_category_by_id(self.db, id)
+                .await?
+                .ok_or_else(|| AppError::BadRequest("parent category not found".into()))?
+                .parent_id;
+        }
+
+        CategoryQuery::find_category_by_id(self.db, cmd.id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("category not found".into()))?;
+
+        let category = CategoryMutation::update(self.db, cmd).await?;
+        self.invalidate().await;
+        self.prerender.push(self.affected_slugs(category.id).await);
+        Ok(category.into())
+    }
+    /// 分类列表、分类页和文章中的分类信息都已过期
+    async fn invalidate(&self) {
+        if let Err(e) = self.cache.invalidate(&[CacheTag::Categories]).await {
+            log::error!("清除访客缓存失败: {e}");
+        }
+    }
+
+    /// 预渲染的文章页显示分类名称和链接，该分类下的文章页需要重新生成
+    async fn affected_slugs(&self, category_id: i64) -> Vec<String> {
+        ArticleQuery::list_publish_article_slugs_by_category(self.db, category_id)
+            .await
+            .unwrap_or_else(|e| {
+                log::error!("查询分类下的文章失败: {e}");
+                Vec::new()
+            })
+    }
+}
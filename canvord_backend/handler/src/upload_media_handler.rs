@@ -0,0 +1,149 @@
+use bytes::Bytes;
+use canvord_service::media_mutation::MediaMutation;
+use dto::app_error::AppError;
+use dto::media::{MediaInfo, MediaVariantInfo};
+use media::{MediaStore, ProcessedImage};
+use sea_orm::DbConn;
+
+pub struct UploadMediaHandler<'a> {
+    db: &'a DbConn,
+    store: &'a dyn MediaStore,
+    max_bytes: usize,
+}
+
+impl<'a> UploadMediaHandler<'a> {
+    pub fn new(db: &'a DbConn, store: &'a dyn MediaStore, max_bytes: usize) -> Self {
+        Self { db, store, max_bytes }
+    }
+
+    /// 单个文件的大小上限，读取上传内容时也以此为准
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    pub async fn execute(&self, owner_id: i64, file_name: String, data: Bytes) -> Result<MediaInfo, AppError> {
+        if data.is_empty() {
+            return Err(AppError::BadRequest("file is empty".into()));
+        }
+        if data.len() > self.max_bytes {
+            return Err(AppError::BadRequest(format!("file exceeds {} bytes", self.max_bytes)));
+        }
+
+        let sniffed = media::sniff(&data)
+            .ok_or_else(|| AppError::BadRequest("unsupported file type".into()))?;
+        let key = media::new_key(sniffed.extension);
+
+        // 解码和编码较慢，放到阻塞线程中执行
+        let (data, image) = if media::is_processable(sniffed.content_type) {
+            let image = tokio::task::spawn_blocking(move || media::process_image(data))
+                .await
+                .map_err(|e| {
+                    log::error!("图片处理任务失败: {e}");
+                    AppError::InternalError
+                })?
+                .map_err(|_| AppError::BadRequest("invalid image".into()))?;
+            (image.original.clone(), Some(image))
+        } else if sniffed.content_type == "image/gif" {
+            let data = media::strip_gif_metadata(&data)
+                .map_err(|_| AppError::BadRequest("invalid image".into()))?;
+            (data, None)
+        } else {
+            (data, None)
+        };
+        let size = data.len() as i64;
+
+        let mut stored = Vec::new();
+        let result = async {
+            self.store_all(&key, data, sniffed.content_type, image.as_ref(), &mut stored).await?;
+            self.save(owner_id, &key, file_name, sniffed.content_type, size, image.as_ref()).await
+        };
+        let model = match result.await {
+            Ok(model) => model,
+            Err(e) => {
+                // 任一步失败时不保留孤立文件
+                for key in &stored {
+                    if let Err(e) = self.store.delete(key).await {
+                        log::error!("清理媒体文件失败: {e}");
+                    }
+                }
+                return Err(e);
+            }
+        };
+
+        let variants = image
+            .map(|image| {
+                image.variants.iter()
+                    .map(|v| MediaVariantInfo {
+                        width: v.width as i32,
+                        url: self.store.url(&media::variant_key(&key, v.width)),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(MediaInfo {
+            id: model.id,
+            owner_id: model.owner_id,
+            url: self.store.url(&model.storage_key),
+            key: model.storage_key,
+            file_name: model.file_name,
+            content_type: model.content_type,
+            size: model.size,
+            width: model.width,
+            height: model.height,
+            variants,
+            usage_count: 0,
+            created_at: model.created_at,
+        })
+    }
+
+    async fn store_all(
+        &self,
+        key: &str,
+        data: Bytes,
+        content_type: &str,
+        image: Option<&ProcessedImage>,
+        stored: &mut Vec<String>,
+    ) -> Result<(), AppError> {
+        let failed = |e: media::MediaError| {
+            log::error!("保存媒体文件失败: {e}");
+            AppError::InternalError
+        };
+
+        self.store.put(key, data, content_type).await.map_err(failed)?;
+        stored.push(key.to_owned());
+
+        for variant in image.map(|i| i.variants.as_slice()).unwrap_or_default() {
+            let variant_key = media::variant_key(key, variant.width);
+            self.store.put(&variant_key, variant.data.clone(), "image/webp").await.map_err(failed)?;
+            stored.push(variant_key);
+        }
+        Ok(())
+    }
+
+    async fn save(
+        &self,
+        owner_id: i64,
+        key: &str,
+        file_name: String,
+        content_type: &str,
+        size: i64,
+        image: Option<&ProcessedImage>,
+    ) -> Result<entity::media::Model, AppError> {
+        let dimensions = image.map(|i| (i.width as i32, i.height as i32));
+        let model = MediaMutation::create(self.db, owner_id, key.to_owned(), file_name, content_type.into(), size, dimensions).await?;
+
+        let variants = image
+            .map(|i| {
+                i.variants.iter()
+                    .map(|v| (v.width as i32, media::variant_key(key, v.width), v.data.len() as i64))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if let Err(e) = MediaMutation::create_variants(self.db, model.id, variants).await {
+            MediaMutation::delete(self.db, model.id).await?;
+            return Err(e.into());
+        }
+        Ok(model)
+    }
+}
@@ -0,0 +1,113 @@
+mod local;
+mod process;
+mod s3;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use thiserror::Error;
+use uuid::Uuid;
+
+pub use local::LocalMediaStore;
+pub use process::{is_processable, process_image, strip_gif_metadata, ProcessedImage, Variant, VARIANT_WIDTHS};
+pub use s3::S3MediaStore;
+
+/// 允许上传的文件类型，以文件内容判断而不是扩展名或请求头。
+/// 上传的图片都要去掉元数据，无法解析的格式（如 AVIF）不接受
+const ALLOWED_TYPES: &[&str] = &[
+    "image/jpeg",
+    "image/png",
+    "image/gif",
+    "image/webp",
+    "application/pdf",
+];
+
+#[derive(Debug, Error)]
+pub enum MediaError {
+    #[error("invalid media key: {0}")]
+    InvalidKey(String),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("storage backend error: {0}")]
+    Backend(String),
+    #[error("image processing error: {0}")]
+    Image(String),
+}
+
+/// 媒体文件的存储后端，`key` 由 [`new_key`] 生成
+#[async_trait]
+pub trait MediaStore: Send + Sync {
+    async fn put(&self, key: &str, data: Bytes, content_type: &str) -> Result<(), MediaError>;
+
+    /// 文件不存在时返回 `None`
+    async fn get(&self, key: &str) -> Result<Option<Bytes>, MediaError>;
+
+    /// 删除不存在的文件不视为错误
+    async fn delete(&self, key: &str) -> Result<(), MediaError>;
+
+    /// 文章中引用该文件使用的地址
+    fn url(&self, key: &str) -> String;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Sniffed {
+    pub content_type: &'static str,
+    pub extension: &'static str,
+}
+
+/// 根据文件头识别类型，不在允许列表中的返回 `None`
+pub fn sniff(data: &[u8]) -> Option<Sniffed> {
+    let kind = infer::get(data)?;
+    ALLOWED_TYPES.contains(&kind.mime_type()).then(|| Sniffed {
+        content_type: kind.mime_type(),
+        extension: kind.extension(),
+    })
+}
+
+pub fn new_key(extension: &str) -> String {
+    format!("{}.{extension}", Uuid::new_v4().simple())
+}
+
+/// 文本中出现的 [`new_key`] 格式的 key，已排序去重，不包括变体的 key
+pub fn keys_in(text: &str) -> Vec<String> {
+    const STEM_LEN: usize = 32;
+    let bytes = text.as_bytes();
+    let is_stem = |b: &u8| b.is_ascii_digit() || (b'a'..=b'f').contains(b);
+
+    let mut keys = Vec::new();
+    let mut i = 0;
+    while i + STEM_LEN < bytes.len() {
+        let start_ok = i == 0 || !bytes[i - 1].is_ascii_alphanumeric();
+        if !start_ok || !bytes[i..i + STEM_LEN].iter().all(is_stem) || bytes[i + STEM_LEN] != b'.' {
+            i += 1;
+            continue;
+        }
+        let ext_start = i + STEM_LEN + 1;
+        let ext_len = bytes[ext_start..].iter().take_while(|b| b.is_ascii_alphanumeric()).count();
+        if ext_len > 0 {
+            keys.push(text[i..ext_start + ext_len].to_owned());
+        }
+        i = ext_start + ext_len;
+    }
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+/// 同一文件的 WebP 变体与原文件共用前缀，例如 `abc.jpg` 的 640 宽版本为 `abc-640.webp`
+pub fn variant_key(key: &str, width: u32) -> String {
+    let stem = key.split_once('.').map_or(key, |(stem, _)| stem);
+    format!("{stem}-{width}.webp")
+}
+
+/// key 只允许 [`new_key`] 生成的字符，避免路径穿越
+fn check_key(key: &str) -> Result<(), MediaError> {
+    let valid = !key.is_empty()
+        && !key.starts_with('.')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(MediaError::InvalidKey(key.to_owned()))
+    }
+}
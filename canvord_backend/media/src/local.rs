@@ -0,0 +1,57 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+
+use crate::{check_key, MediaError, MediaStore};
+
+/// 存放在本地目录，由后端的 `/media/{key}` 路由对外提供
+pub struct LocalMediaStore {
+    root: PathBuf,
+    public_base: String,
+}
+
+impl LocalMediaStore {
+    pub fn new(root: impl Into<PathBuf>, public_base: impl Into<String>) -> Result<Self, MediaError> {
+        let root = root.into();
+        std::fs::create_dir_all(&root)?;
+
+        Ok(Self {
+            root,
+            public_base: public_base.into().trim_end_matches('/').to_owned(),
+        })
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, MediaError> {
+        check_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl MediaStore for LocalMediaStore {
+    async fn put(&self, key: &str, data: Bytes, _content_type: &str) -> Result<(), MediaError> {
+        tokio::fs::write(self.path(key)?, data).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Bytes>, MediaError> {
+        match tokio::fs::read(self.path(key)?).await {
+            Ok(data) => Ok(Some(Bytes::from(data))),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), MediaError> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn url(&self, key: &str) -> String {
+        format!("{}/{key}", self.public_base)
+    }
+}
@@ -0,0 +1,194 @@
+use std::io::Cursor;
+
+use bytes::Bytes;
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::metadata::Orientation;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits};
+use img_parts::jpeg::markers;
+use img_parts::riff::{RiffChunk, RiffContent};
+use img_parts::webp::{WebP, CHUNK_EXIF, CHUNK_VP8X, CHUNK_XMP};
+use img_parts::DynImage;
+
+use crate::MediaError;
+
+/// 生成的响应式宽度，不小于原图宽度的会跳过
+pub const VARIANT_WIDTHS: &[u32] = &[320, 640, 1024, 1600];
+/// 原图宽度的 WebP 版本最多缩到这个宽度
+const MAX_VARIANT_WIDTH: u32 = 2048;
+const MAX_DIMENSION: u32 = 12_000;
+const WEBP_QUALITY: f32 = 80.0;
+const JPEG_QUALITY: u8 = 90;
+
+pub struct Variant {
+    pub width: u32,
+    pub data: Bytes,
+}
+
+pub struct ProcessedImage {
+    /// 去掉 EXIF 和 XMP 后的原图，格式不变
+    pub original: Bytes,
+    pub width: u32,
+    pub height: u32,
+    pub variants: Vec<Variant>,
+}
+
+/// 只处理能解码并重新编码的格式，动图等其余类型原样保存
+pub fn is_processable(content_type: &str) -> bool {
+    matches!(content_type, "image/jpeg" | "image/png" | "image/webp")
+}
+
+/// 去掉 EXIF 和 XMP（包括 GPS 位置）并生成各宽度的 WebP 版本，耗时较长，应放在阻塞线程中调用
+pub fn process_image(data: Bytes) -> Result<ProcessedImage, MediaError> {
+    let mut reader = ImageReader::new(Cursor::new(&data)).with_guessed_format()?;
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+    reader.limits(limits);
+
+    let format = reader.format();
+    let mut decoder = reader.into_decoder().map_err(image_error)?;
+    let orientation = decoder.orientation().map_err(image_error)?;
+    let mut img = DynamicImage::from_decoder(decoder).map_err(image_error)?;
+
+    // 方向信息记录在 EXIF 中，删除前先把像素转正再重新编码
+    let original = if orientation == Orientation::NoTransforms {
+        strip_metadata(data.clone())?
+    } else {
+        img.apply_orientation(orientation);
+        encode(&img, format)?
+    };
+
+    let mut widths: Vec<u32> = VARIANT_WIDTHS.iter().copied().filter(|w| *w < img.width()).collect();
+    widths.push(img.width().min(MAX_VARIANT_WIDTH));
+    widths.dedup();
+
+    let variants = widths
+        .into_iter()
+        .map(|width| {
+            let data = if width == img.width() {
+                encode_webp(&img)
+            } else {
+                encode_webp(&img.resize(width, MAX_DIMENSION, FilterType::CatmullRom))
+            };
+            Variant { width, data }
+        })
+        .collect();
+
+    Ok(ProcessedImage {
+        original,
+        width: img.width(),
+        height: img.height(),
+        variants,
+    })
+}
+
+/// 删除可能带有位置、设备和作者信息的段：JPEG 的 APP1（EXIF、XMP）、APP13（IPTC）和注释，
+/// PNG 的 eXIf 和文本块（XMP 存在 iTXt 中），WebP 的 EXIF 和 XMP 块。ICC 等显示需要的信息保留
+fn strip_metadata(data: Bytes) -> Result<Bytes, MediaError> {
+    let img = DynImage::from_bytes(data.clone()).map_err(|e| MediaError::Image(e.to_string()))?;
+    Ok(match img {
+        Some(DynImage::Jpeg(mut jpeg)) => {
+            jpeg.segments_mut()
+                .retain(|s| !matches!(s.marker(), markers::APP1 | markers::APP13 | markers::COM));
+            jpeg.encoder().bytes()
+        }
+        Some(DynImage::Png(mut png)) => {
+            png.chunks_mut()
+                .retain(|c| !matches!(&c.kind(), b"eXIf" | b"tEXt" | b"zTXt" | b"iTXt"));
+            png.encoder().bytes()
+        }
+        Some(DynImage::WebP(mut webp)) => {
+            webp.remove_chunks_by_id(CHUNK_EXIF);
+            webp.remove_chunks_by_id(CHUNK_XMP);
+            clear_vp8x_metadata_flags(&mut webp);
+            webp.encoder().bytes()
+        }
+        None => data,
+    })
+}
+
+/// VP8X 头中标记了 EXIF 和 XMP 是否存在，块删除后一并清除
+fn clear_vp8x_metadata_flags(webp: &mut WebP) {
+    const EXIF_FLAG: u8 = 0x08;
+    const XMP_FLAG: u8 = 0x04;
+    for chunk in webp.chunks_mut().iter_mut().filter(|c| c.id() == CHUNK_VP8X) {
+        if let Some(header) = chunk.content().data() {
+            let mut header = header.to_vec();
+            header[0] &= !(EXIF_FLAG | XMP_FLAG);
+            *chunk = RiffChunk::new(CHUNK_VP8X, RiffContent::Data(Bytes::from(header)));
+        }
+    }
+}
+
+/// 去掉 GIF 的注释和应用扩展（XMP 存在其中），保留控制循环播放的 NETSCAPE2.0 扩展，图像数据原样保留
+pub fn strip_gif_metadata(data: &[u8]) -> Result<Bytes, MediaError> {
+    let invalid = || MediaError::Image("malformed GIF".into());
+    let color_table = |flags: u8| if flags & 0x80 != 0 { 3 << ((flags & 0x07) + 1) } else { 0 };
+    // 从 `pos` 起跳过一串子块，返回结束符之后的位置
+    let skip_sub_blocks = |mut pos: usize| -> Result<usize, MediaError> {
+        loop {
+            let len = *data.get(pos).ok_or_else(invalid)? as usize;
+            pos += 1 + len;
+            if len == 0 {
+                return Ok(pos);
+            }
+        }
+    };
+
+    // 文件头和逻辑屏幕描述符
+    let mut pos = 13 + color_table(*data.get(10).ok_or_else(invalid)?);
+    let mut out = data.get(..pos).ok_or_else(invalid)?.to_vec();
+    loop {
+        match *data.get(pos).ok_or_else(invalid)? {
+            // 图像描述符、可选的局部颜色表、LZW 最小码长和图像数据
+            0x2C => {
+                let flags = *data.get(pos + 9).ok_or_else(invalid)?;
+                let end = skip_sub_blocks(pos + 10 + color_table(flags) + 1)?;
+                out.extend_from_slice(data.get(pos..end).ok_or_else(invalid)?);
+                pos = end;
+            }
+            0x21 => {
+                let label = *data.get(pos + 1).ok_or_else(invalid)?;
+                let end = skip_sub_blocks(pos + 2)?;
+                let keep = match label {
+                    0xFE => false,
+                    0xFF => matches!(data.get(pos + 3..pos + 14), Some(b"NETSCAPE2.0" | b"ANIMEXTS1.0")),
+                    _ => true,
+                };
+                if keep {
+                    out.extend_from_slice(data.get(pos..end).ok_or_else(invalid)?);
+                }
+                pos = end;
+            }
+            0x3B => {
+                out.push(0x3B);
+                return Ok(Bytes::from(out));
+            }
+            _ => return Err(invalid()),
+        }
+    }
+}
+
+fn encode(img: &DynamicImage, format: Option<ImageFormat>) -> Result<Bytes, MediaError> {
+    let mut buf = Vec::new();
+    match format {
+        Some(ImageFormat::Jpeg) => {
+            let encoder = JpegEncoder::new_with_quality(&mut buf, JPEG_QUALITY);
+            DynamicImage::ImageRgb8(img.to_rgb8()).write_with_encoder(encoder).map_err(image_error)?;
+        }
+        Some(ImageFormat::WebP) => return Ok(encode_webp(img)),
+        _ => img.write_to(&mut Cursor::new(&mut buf), ImageFormat::Png).map_err(image_error)?,
+    }
+    Ok(Bytes::from(buf))
+}
+
+fn encode_webp(img: &DynamicImage) -> Bytes {
+    let rgba = img.to_rgba8();
+    let encoded = webp::Encoder::from_rgba(&rgba, rgba.width(), rgba.height()).encode(WEBP_QUALITY);
+    Bytes::copy_from_slice(&encoded)
+}
+
+fn image_error(e: image::ImageError) -> MediaError {
+    MediaError::Image(e.to_string())
+}
@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use aws_sdk_s3::config::{BehaviorVersion, Credentials, Region};
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::{Client, Config};
+use bytes::Bytes;
+
+use crate::{check_key, MediaError, MediaStore};
+
+/// S3 兼容的对象存储，使用 path-style 地址，因此也可以对接 MinIO 等本地服务
+pub struct S3MediaStore {
+    client: Client,
+    bucket: String,
+    public_base: String,
+}
+
+impl S3MediaStore {
+    pub fn new(
+        endpoint: &str,
+        region: &str,
+        bucket: &str,
+        access_key: &str,
+        secret_key: &str,
+        public_base: &str,
+    ) -> Self {
+        let config = Config::builder()
+            .behavior_version(BehaviorVersion::latest())
+            .endpoint_url(endpoint)
+            .region(Region::new(region.to_owned()))
+            .credentials_provider(Credentials::new(access_key, secret_key, None, None, "canvord"))
+            .force_path_style(true)
+            .build();
+
+        Self {
+            client: Client::from_conf(config),
+            bucket: bucket.to_owned(),
+            public_base: public_base.trim_end_matches('/').to_owned(),
+        }
+    }
+}
+
+#[async_trait]
+impl MediaStore for S3MediaStore {
+    async fn put(&self, key: &str, data: Bytes, content_type: &str) -> Result<(), MediaError> {
+        check_key(key)?;
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            // key 不会复用，文件可被 CDN 和浏览器长期缓存
+            .cache_control("public, max-age=31536000, immutable")
+            .body(ByteStream::from(data))
+            .send()
+            .await
+            .map_err(|e| MediaError::Backend(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Bytes>, MediaError> {
+        check_key(key)?;
+        let output = match self.client.get_object().bucket(&self.bucket).key(key).send().await {
+            Ok(output) => output,
+            Err(e) if e.as_service_error().is_some_and(|e| e.is_no_such_key()) => return Ok(None),
+            Err(e) => return Err(MediaError::Backend(e.to_string())),
+        };
+
+        let data = output.body
+            .collect()
+            .await
+            .map_err(|e| MediaError::Backend(e.to_string()))?;
+        Ok(Some(data.into_bytes()))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), MediaError> {
+        check_key(key)?;
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| MediaError::Backend(e.to_string()))?;
+        Ok(())
+    }
+
+    fn url(&self, key: &str) -> String {
+        format!("{}/{key}", self.public_base)
+    }
+}
@@ -0,0 +1,138 @@
+use std::io::Cursor;
+use bytes::Bytes;
+use image::codecs::jpeg::JpegEncoder;
+use image::{DynamicImage, GenericImageView, ImageFormat, RgbImage};
+use img_parts::jpeg::{markers, Jpeg, JpegSegment};
+use img_parts::png::{Png, PngChunk};
+use img_parts::{DynImage, ImageEXIF};
+use media::{is_processable, process_image, strip_gif_metadata};
+
+fn png(width: u32, height: u32) -> Bytes {
+    let mut buf = Vec::new();
+    DynamicImage::ImageRgb8(RgbImage::new(width, height))
+        .write_to(&mut Cursor::new(&mut buf), ImageFormat::Png)
+        .unwrap();
+    Bytes::from(buf)
+}
+
+/// 带 EXIF 的 JPEG，`orientation` 写入 0x0112 标签
+fn jpeg_with_exif(width: u32, height: u32, orientation: u16) -> Bytes {
+    let mut buf = Vec::new();
+    DynamicImage::ImageRgb8(RgbImage::new(width, height))
+        .write_with_encoder(JpegEncoder::new(&mut buf))
+        .unwrap();
+
+    // 大端 TIFF 头和只有方向一项的 IFD
+    let mut exif = b"MM\0\x2a\0\0\0\x08\0\x01".to_vec();
+    exif.extend_from_slice(&[0x01, 0x12, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01]);
+    exif.extend_from_slice(&orientation.to_be_bytes());
+    exif.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+
+    let mut img = DynImage::from_bytes(Bytes::from(buf)).unwrap().unwrap();
+    img.set_exif(Some(Bytes::from(exif)));
+    img.encoder().bytes()
+}
+
+const XMP: &[u8] = b"<x:xmpmeta><exif:GPSLatitude>31,14.1N</exif:GPSLatitude></x:xmpmeta>";
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+fn decoded_size(data: &Bytes, format: ImageFormat) -> (u32, u32) {
+    image::load_from_memory_with_format(data, format).unwrap().dimensions()
+}
+
+#[test]
+fn variants_cover_smaller_widths_and_the_original() {
+    let processed = process_image(png(1000, 500)).unwrap();
+
+    assert_eq!((processed.width, processed.height), (1000, 500));
+    let widths: Vec<u32> = processed.variants.iter().map(|v| v.width).collect();
+    assert_eq!(widths, [320, 640, 1000]);
+    for variant in &processed.variants {
+        let (width, height) = decoded_size(&variant.data, ImageFormat::WebP);
+        assert_eq!(width, variant.width);
+        assert_eq!(height, variant.width / 2);
+    }
+}
+
+#[test]
+fn small_image_has_a_single_variant() {
+    let processed = process_image(png(100, 80)).unwrap();
+
+    let widths: Vec<u32> = processed.variants.iter().map(|v| v.width).collect();
+    assert_eq!(widths, [100]);
+}
+
+#[test]
+fn largest_variant_is_capped() {
+    let processed = process_image(png(3000, 10)).unwrap();
+
+    let widths: Vec<u32> = processed.variants.iter().map(|v| v.width).collect();
+    assert_eq!(widths, [320, 640, 1024, 1600, 2048]);
+    assert_eq!(processed.width, 3000);
+}
+
+#[test]
+fn exif_is_stripped() {
+    let data = jpeg_with_exif(40, 20, 1);
+    assert!(DynImage::from_bytes(data.clone()).unwrap().unwrap().exif().is_some());
+
+    let processed = process_image(data).unwrap();
+
+    assert!(DynImage::from_bytes(processed.original.clone()).unwrap().unwrap().exif().is_none());
+    assert_eq!(decoded_size(&processed.original, ImageFormat::Jpeg), (40, 20));
+}
+
+#[test]
+fn orientation_is_applied_before_stripping() {
+    // 6 表示需要顺时针旋转 90°
+    let processed = process_image(jpeg_with_exif(40, 20, 6)).unwrap();
+
+    assert_eq!((processed.width, processed.height), (20, 40));
+    assert!(DynImage::from_bytes(processed.original.clone()).unwrap().unwrap().exif().is_none());
+    assert_eq!(decoded_size(&processed.original, ImageFormat::Jpeg), (20, 40));
+    assert_eq!(decoded_size(&processed.variants[0].data, ImageFormat::WebP), (20, 40));
+}
+
+#[test]
+fn rejects_data_that_is_not_an_image() {
+    assert!(process_image(Bytes::from_static(b"%PDF-1.7 not an image")).is_err());
+    assert!(is_processable("image/png"));
+    assert!(!is_processable("image/gif"));
+}
+
+#[test]
+fn xmp_is_stripped() {
+    let mut jpeg = Jpeg::from_bytes(jpeg_with_exif(40, 20, 1)).unwrap();
+    let mut segment = b"http://ns.adobe.com/xap/1.0/\0".to_vec();
+    segment.extend_from_slice(XMP);
+    jpeg.segments_mut().insert(1, JpegSegment::new_with_contents(markers::APP1, Bytes::from(segment)));
+    let processed = process_image(jpeg.encoder().bytes()).unwrap();
+    assert!(!contains(&processed.original, XMP));
+    assert_eq!(decoded_size(&processed.original, ImageFormat::Jpeg), (40, 20));
+
+    let mut png = Png::from_bytes(png(40, 20)).unwrap();
+    let mut chunk = b"XML:com.adobe.xmp\0\0\0\0\0".to_vec();
+    chunk.extend_from_slice(XMP);
+    png.chunks_mut().insert(1, PngChunk::new(*b"iTXt", Bytes::from(chunk)));
+    let processed = process_image(png.encoder().bytes()).unwrap();
+    assert!(!contains(&processed.original, XMP));
+    assert_eq!(decoded_size(&processed.original, ImageFormat::Png), (40, 20));
+}
+
+#[test]
+fn gif_keeps_frames_and_loop_but_drops_metadata() {
+    let header: &[u8] = b"GIF89a\x01\0\x01\0\x80\0\0\0\0\0\xff\xff\xff";
+    let looping: &[u8] = b"\x21\xff\x0bNETSCAPE2.0\x03\x01\0\0\0";
+    let xmp: &[u8] = b"\x21\xff\x0bXMP DataXMP\x05GPS!!\0";
+    let comment: &[u8] = b"\x21\xfe\x03gps\0";
+    let frame: &[u8] = b"\x21\xf9\x04\0\0\0\0\0\x2c\0\0\0\0\x01\0\x01\0\0\x02\x02\x44\x01\0";
+    let gif = [header, looping, xmp, comment, frame, b"\x3b"].concat();
+
+    let stripped = strip_gif_metadata(&gif).unwrap();
+
+    assert_eq!(stripped, [header, looping, frame, b"\x3b"].concat());
+    assert!(strip_gif_metadata(&gif[..gif.len() - 4]).is_err());
+}
@@ -0,0 +1,92 @@
+use bytes::Bytes;
+use media::{new_key, LocalMediaStore, MediaError, MediaStore, S3MediaStore};
+
+/// 各存储后端共同遵守的约定
+async fn check_store(store: &dyn MediaStore) {
+    let key = new_key("png");
+    let data = Bytes::from_static(b"\x89PNG\r\n\x1a\nnot really a png");
+
+    assert!(store.get(&key).await.unwrap().is_none());
+
+    store.put(&key, data.clone(), "image/png").await.unwrap();
+    assert_eq!(store.get(&key).await.unwrap(), Some(data));
+
+    // 覆盖写入同一 key
+    store.put(&key, Bytes::from_static(b"second"), "image/png").await.unwrap();
+    assert_eq!(store.get(&key).await.unwrap(), Some(Bytes::from_static(b"second")));
+
+    store.delete(&key).await.unwrap();
+    assert!(store.get(&key).await.unwrap().is_none());
+    // 重复删除不视为错误
+    store.delete(&key).await.unwrap();
+
+    for key in ["../etc/passwd", ".hidden", "a/b.png", ""] {
+        assert!(
+            matches!(store.put(key, Bytes::new(), "image/png").await, Err(MediaError::InvalidKey(_))),
+            "{key:?} should be rejected"
+        );
+        assert!(matches!(store.get(key).await, Err(MediaError::InvalidKey(_))), "{key:?} should be rejected");
+        assert!(matches!(store.delete(key).await, Err(MediaError::InvalidKey(_))), "{key:?} should be rejected");
+    }
+}
+
+#[tokio::test]
+async fn local_store() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = LocalMediaStore::new(dir.path().join("media"), "https://example.com/media/").unwrap();
+
+    check_store(&store).await;
+    assert_eq!(store.url("abc.png"), "https://example.com/media/abc.png");
+}
+
+/// 需要可用的 S3 兼容服务，未设置 `TEST_S3_ENDPOINT` 时跳过。例如：
+///
+/// ```sh
+/// docker run -p 9000:9000 -e MINIO_ROOT_USER=minio -e MINIO_ROOT_PASSWORD=minio123 minio/minio server /data
+/// TEST_S3_ENDPOINT=http://127.0.0.1:9000 TEST_S3_BUCKET=canvord-test \
+///     TEST_S3_ACCESS_KEY=minio TEST_S3_SECRET_KEY=minio123 cargo test -p media
+/// ```
+///
+/// 存储桶需要事先创建。
+#[tokio::test]
+async fn s3_store() {
+    let Ok(endpoint) = std::env::var("TEST_S3_ENDPOINT") else {
+        eprintln!("TEST_S3_ENDPOINT is not set, skipping");
+        return;
+    };
+    let env = |name: &str| std::env::var(name).unwrap_or_else(|_| panic!("{name} is required with TEST_S3_ENDPOINT"));
+    let store = S3MediaStore::new(
+        &endpoint,
+        &std::env::var("TEST_S3_REGION").unwrap_or_else(|_| "us-east-1".into()),
+        &env("TEST_S3_BUCKET"),
+        &env("TEST_S3_ACCESS_KEY"),
+        &env("TEST_S3_SECRET_KEY"),
+        "https://cdn.example.com",
+    );
+
+    check_store(&store).await;
+    assert_eq!(store.url("abc.png"), "https://cdn.example.com/abc.png");
+}
+
+#[test]
+fn keys_in_markdown() {
+    let a = "0123456789abcdef0123456789abcdef.png";
+    let b = "fedcba9876543210fedcba9876543210.pdf";
+    let markdown = format!(
+        "![cover](https://cdn.example.com/{a}) [doc](/media/{b}) again {a}\n\
+         <img src=\"/media/fedcba9876543210fedcba9876543210-640.webp\"> \
+         x0123456789abcdef0123456789abcdef.png 0123456789ABCDEF0123456789ABCDEF.png"
+    );
+
+    assert_eq!(media::keys_in(&markdown), vec![a.to_owned(), b.to_owned()]);
+    assert!(media::keys_in("").is_empty());
+    assert_eq!(media::keys_in(&new_key("webp")).len(), 1);
+}
+
+#[test]
+fn sniff_rejects_types_whose_metadata_cannot_be_stripped() {
+    let avif = b"\0\0\0\x1cftypavif\0\0\0\0avifmif1miaf";
+    assert!(media::sniff(avif).is_none());
+    assert_eq!(media::sniff(b"GIF89a\x01\0\x01\0\0\0\0").unwrap().content_type, "image/gif");
+    assert_eq!(media::sniff(b"%PDF-1.7\n").unwrap().content_type, "application/pdf");
+}
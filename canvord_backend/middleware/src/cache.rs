@@ -1,19 +1,115 @@
 use actix_web::{
     body::{EitherBody, MessageBody},
     dev::{Service, ServiceRequest, ServiceResponse, Transform},
-    Error, HttpResponse,
+    Error, HttpMessage, HttpRequest,
 };
 use futures_util::future::LocalBoxFuture;
-use redis::{AsyncCommands, Client};
-use std::{future::{ready, Ready}, rc::Rc};
+use once_cell::sync::Lazy;
+use redis::{aio::MultiplexedConnection, AsyncCommands, Client, RedisError, RedisResult, Script};
+use std::{collections::HashMap, fmt, future::{ready, Ready}, rc::Rc, sync::Arc};
 use actix_web::error::ErrorInternalServerError;
+use actix_web::test::TestRequest;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+use actix_web::http::header::ContentEncoding;
+use crate::cached_response::{negotiate, CachedResponse};
+use crate::connection::RedisConnection;
+use crate::local_cache::{LocalCache, INVALIDATE_CHANNEL};
+
+/// 每个缓存条目是一个 Redis hash，字段见 [`CachedResponse`]
+const CACHE_PREFIX: &str = "http_cache:";
+/// 缓存条目 hash 中记录标签的字段，每行一个，命中时随条目写入进程内缓存
+const TAGS_FIELD: &str = "tags";
+/// 每个标签对应一个 Redis set，记录带有该标签的缓存 key
+const TAG_PREFIX: &str = "http_cache_tag:";
+/// 每个缓存 key 对应一把锁，同一时间只有一个请求执行下游服务刷新该条目
+const LOCK_PREFIX: &str = "http_cache_lock:";
+/// 锁的最长持有时间，超过后其他请求可以重新抢占
+const LOCK_TTL: Duration = Duration::from_secs(10);
+/// 缓存不存在时，未抢到锁的请求最多等待这么久，之后自己执行下游服务
+const LOCK_WAIT: Duration = Duration::from_secs(2);
+const LOCK_POLL: Duration = Duration::from_millis(50);
+/// 下游响应不可缓存时，持有者把锁换成这个标记并保留 [`LOCK_WAIT`]，等待的请求看到后立即自己执行
+const UNCACHEABLE: &str = "uncacheable";
+/// 每次失效时递增，请求开始后发生过失效的响应不再写入缓存
+const GENERATION_KEY: &str = "http_cache_generation";
+
+/// 只有代数与请求开始时一致才写入，避免失效前读到的旧内容在失效后写回缓存。
+///
+/// KEYS: 代数、缓存 key、标签 set；ARGV: 请求开始时的代数、TTL，之后依次为 hash 的字段名和值
+static STORE_SCRIPT: Lazy<Script> = Lazy::new(|| Script::new(r#"
+if (redis.call('GET', KEYS[1]) or '') ~= ARGV[1] then
+    return 0
+end
+redis.call('DEL', KEYS[2])
+redis.call('HSET', KEYS[2], unpack(ARGV, 3))
+redis.call('EXPIRE', KEYS[2], ARGV[2])
+for i = 3, #KEYS do
+    redis.call('SADD', KEYS[i], KEYS[2])
+    redis.call('EXPIRE', KEYS[i], ARGV[2])
+end
+return 1
+"#));
+
+/// 只删除自己持有的锁，避免误删超时后被其他请求抢到的锁。
+///
+/// ARGV: 持有者的 token；带有第二、三个参数时不删除，而是换成该标记并设置过期时间（毫秒）
+static RELEASE_SCRIPT: Lazy<Script> = Lazy::new(|| Script::new(r#"
+if redis.call('GET', KEYS[1]) ~= ARGV[1] then
+    return 0
+end
+if ARGV[2] then
+    redis.call('SET', KEYS[1], ARGV[2], 'PX', ARGV[3])
+    return 1
+end
+return redis.call('DEL', KEYS[1])
+"#));
+
+/// 缓存条目包含的内容，修改文章后按标签清除对应的条目
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheTag {
+    /// 包含该文章的响应
+    Article(i64),
+    /// 按 slug 查询的响应，包括文章不存在时的响应
+    Slug(String),
+    /// 文章列表、订阅源、标签和分类统计等随文章发布或隐藏变化的响应
+    Listing,
+    /// 包含分类名称、slug 或层级的响应，分类修改后清除
+    Categories,
+}
+
+impl fmt::Display for CacheTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CacheTag::Article(id) => write!(f, "article:{id}"),
+            CacheTag::Slug(slug) => write!(f, "slug:{slug}"),
+            CacheTag::Listing => f.write_str("listing"),
+            CacheTag::Categories => f.write_str("categories"),
+        }
+    }
+}
+
+/// 由接口写入请求的 extensions，缓存中间件写入缓存时读取
+#[derive(Debug, Clone, Default)]
+pub struct CacheTags(pub Vec<CacheTag>);
+
+impl CacheTags {
+    fn names(&self) -> Vec<String> {
+        self.0.iter().map(CacheTag::to_string).collect()
+    }
+}
 
 /// 缓存中间件构造器（实现 Transform）
+///
+/// 配置了 [`with_local`](Self::with_local) 时先查进程内缓存，未命中再查 Redis。缓存只是优化：
+/// Redis 不可用时记录错误并绕过 Redis，只使用进程内缓存（没有时直接执行下游服务），不会导致请求失败。
 pub struct CacheMiddleware {
     pub client: Client,
     pub key_gen: Rc<dyn Fn(&ServiceRequest) -> String>,
     pub filter: Rc<dyn Fn(&ServiceRequest) -> bool>,
-    pub ttl: u64,
+    pub ttl: Duration,
+    pub stale: Duration,
+    pub local: Option<Arc<LocalCache>>,
 }
 
 impl<S, B> Transform<S, ServiceRequest> for CacheMiddleware
@@ -30,10 +126,12 @@ where
     fn new_transform(&self, service: S) -> Self::Future {
         ready(Ok(CacheMiddlewareImpl {
             service: Rc::new(service),
-            client: self.client.clone(),
+            redis: Rc::new(RedisConnection::new(self.client.clone())),
+            local: self.local.clone(),
             key_gen: self.key_gen.clone(),
             filter: self.filter.clone(),
             ttl: self.ttl,
+            stale: self.stale,
         }))
     }
 }
@@ -41,10 +139,12 @@ where
 /// 缓存中间件实现体（Service）
 pub struct CacheMiddlewareImpl<S> {
     service: Rc<S>,
-    client: Client,
+    redis: Rc<RedisConnection>,
+    local: Option<Arc<LocalCache>>,
     key_gen: Rc<dyn Fn(&ServiceRequest) -> String>,
     filter: Rc<dyn Fn(&ServiceRequest) -> bool>,
-    ttl: u64,
+    ttl: Duration,
+    stale: Duration,
 }
 
 impl<S, B> Service<ServiceRequest> for CacheMiddlewareImpl<S>
@@ -71,66 +171,361 @@ where
             });
         }
 
-        let key = (self.key_gen)(&req);
+        // 每种压缩格式分别缓存
+        let encoding = negotiate(req.request());
+        let store = Store {
+            key: format!("{CACHE_PREFIX}{}#{}", (self.key_gen)(&req), encoding.as_str()),
+            generation: String::new(),
+            expire: self.ttl + self.stale,
+            local_generation: self.local.as_ref().map_or(0, |local| local.generation()),
+            local: self.local.clone(),
+        };
         let svc = Rc::clone(&self.service);
-        let client = self.client.clone();
+        let redis = Rc::clone(&self.redis);
         let ttl = self.ttl;
 
         Box::pin(async move {
-            // 初始化 Redis 客户端和连接
-            let mut conn = client
-                .get_multiplexed_async_connection()
-                .await
-                .map_err(ErrorInternalServerError)?;
-
-            // 读取缓存
-            let cached_opt: Option<String> = conn.get(&key).await.map_err(ErrorInternalServerError)?;
-
-            if let Some(cached) = cached_opt {
-                // 有缓存，直接返回缓存内容
-                return Ok(ServiceResponse::new(
-                    req.request().clone(),
-                    HttpResponse::Ok().body(cached).map_into_right_body(),
-                ));
+            // 先查进程内缓存，命中时不访问 Redis
+            if let Some(local) = &store.local
+                && let Some(cached) = local.get(&store.key, ttl)
+            {
+                let response = cached.respond(req.request(), ttl.saturating_sub(cached.age()));
+                return Ok(req.into_response(response.map_into_right_body()));
             }
 
-            // 缓存未命中，执行下游服务
-            let res = svc.call(req).await?;
+            let Some(mut conn) = redis.connection().await else {
+                return bypass(&*svc, req, &store, encoding, ttl).await;
+            };
 
-            // 将响应体转成 Bytes 以读取内容
-            let (req, res) = res.into_parts();
-            let res_body = res.into_body();  // 这里拿到响应体，impl MessageBody
-            let body_bytes = actix_web::body::to_bytes(res_body).await
-                .map_err(|_| ErrorInternalServerError("to_bytes error"))?;
-            
-            let body_string = String::from_utf8_lossy(&body_bytes).to_string();
+            let (store, lookup) = match store.lookup(&mut conn, ttl).await {
+                Ok(found) => found,
+                Err((store, e)) => {
+                    redis.failed(&e);
+                    return bypass(&*svc, req, &store, encoding, ttl).await;
+                }
+            };
 
-            // 写入缓存
-            let _: () = conn.set_ex(&key, body_string.clone(), ttl).await
-                .map_err(ErrorInternalServerError)?;
+            let lock = match lookup {
+                Lookup::Hit(cached, tags, lock) => {
+                    if cached.age() < ttl {
+                        store.remember(&cached, tags);
+                    }
+                    // 客户端缓存的有效期不超过服务端剩余的新鲜时间
+                    let response = cached.respond(req.request(), ttl.saturating_sub(cached.age()));
+                    let Some(lock) = lock else {
+                        return Ok(req.into_response(response.map_into_right_body()));
+                    };
 
-            // 构造新的响应返回
-            let response = HttpResponse::Ok()
-                .body(body_string)
-                .map_into_right_body();
+                    // 已过软过期时间，返回旧内容，由抢到锁的请求在后台刷新。
+                    // 原请求交给后台任务执行下游服务，保留路由参数和 app data；旧内容挂在按原请求新建的请求上返回。
+                    // 两者不能共享同一个 HttpRequest：下游路由时要独占修改它，响应未释放时会 panic
+                    let standin = detach(req.request());
+                    actix_web::rt::spawn(async move {
+                        match fetch(&*svc, req, encoding).await {
+                            Ok(Fetched::Cached(_, cached, tags)) => {
+                                match store.save(&mut conn, &tags, &cached).await {
+                                    Ok(true) => store.remember(&cached, tags.names()),
+                                    Ok(false) => {}
+                                    Err(e) => redis.failed(&e),
+                                }
+                            }
+                            Ok(Fetched::Uncacheable(_)) => {}
+                            Err(e) => log::error!("刷新访客缓存失败: {e}"),
+                        }
+                        let _ = lock.release(&mut conn).await;
+                    });
+                    return Ok(ServiceResponse::new(standin, response.map_into_right_body()));
+                }
+                Lookup::Miss(lock) => lock,
+            };
 
-            Ok(ServiceResponse::new(req, response))
+            let fetched = fetch(&*svc, req, encoding).await;
+            if let Ok(Fetched::Cached(_, cached, tags)) = &fetched {
+                match store.save(&mut conn, tags, cached).await {
+                    Ok(true) => store.remember(cached, tags.names()),
+                    Ok(false) => {}
+                    Err(e) => redis.failed(&e),
+                }
+            }
+            if let Some(lock) = lock {
+                let _ = match &fetched {
+                    Ok(Fetched::Uncacheable(_)) => lock.release_uncacheable(&mut conn).await,
+                    _ => lock.release(&mut conn).await,
+                };
+            }
+            fetched?.into_response(ttl)
         })
     }
 }
 
+/// 按原请求的方法、URI、版本、请求头和对端地址新建一个独立的请求，只用于承载返回给客户端的响应
+fn detach(req: &HttpRequest) -> HttpRequest {
+    let mut standin = TestRequest::default()
+        .method(req.method().clone())
+        .uri(&req.uri().to_string())
+        .version(req.version());
+    for (name, value) in req.headers() {
+        standin = standin.append_header((name.clone(), value.clone()));
+    }
+    if let Some(addr) = req.peer_addr() {
+        standin = standin.peer_addr(addr);
+    }
+    standin.to_http_request()
+}
+
+/// Redis 不可用时绕过 Redis，可缓存的响应只写入进程内缓存
+async fn bypass<S, B>(
+    svc: &S,
+    req: ServiceRequest,
+    store: &Store,
+    encoding: ContentEncoding,
+    ttl: Duration,
+) -> Result<ServiceResponse<EitherBody<B>>, Error>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    B: MessageBody,
+{
+    if store.local.is_none() {
+        return svc.call(req).await.map(|res| res.map_into_left_body());
+    }
+
+    let fetched = fetch(svc, req, encoding).await?;
+    if let Fetched::Cached(_, cached, tags) = &fetched {
+        store.remember(cached, tags.names());
+    }
+    fetched.into_response(ttl)
+}
+
+enum Lookup {
+    /// 缓存命中，带有条目的标签；已过软过期时间且抢到了刷新的锁时带有锁
+    Hit(CachedResponse, Vec<String>, Option<Lock>),
+    /// 缓存不存在；抢到锁时带有锁，等待其他请求写入超时时没有
+    Miss(Option<Lock>),
+}
+
+enum Fetched<B> {
+    Cached(HttpRequest, CachedResponse, CacheTags),
+    Uncacheable(ServiceResponse<B>),
+}
+
+impl<B> Fetched<B> {
+    fn into_response(self, ttl: Duration) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+        match self {
+            Fetched::Cached(req, cached, _) => {
+                let response = cached.respond(&req, ttl);
+                Ok(ServiceResponse::new(req, response.map_into_right_body()))
+            }
+            Fetched::Uncacheable(res) => Ok(res.map_into_left_body()),
+        }
+    }
+}
+
+/// 执行下游服务，响应可缓存时读出响应体并按 `encoding` 压缩；
+/// 压缩（brotli 最高到 9 级）在阻塞线程池中执行，不占用 worker
+async fn fetch<S, B>(svc: &S, req: ServiceRequest, encoding: ContentEncoding) -> Result<Fetched<B>, Error>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    B: MessageBody,
+{
+    let res = svc.call(req).await?;
+    if !CachedResponse::is_cacheable(res.status(), res.headers()) {
+        return Ok(Fetched::Uncacheable(res));
+    }
+
+    let tags = res.request().extensions().get::<CacheTags>().cloned().unwrap_or_default();
+
+    // 将响应体转成 Bytes 以读取内容
+    let (req, res) = res.into_parts();
+    let (res, res_body) = res.into_parts();
+    let body_bytes = actix_web::body::to_bytes(res_body).await
+        .map_err(|_| ErrorInternalServerError("to_bytes error"))?;
+    let (status, headers) = (res.status(), res.headers().clone());
+    let cached = match encoding {
+        ContentEncoding::Identity => CachedResponse::new(status, &headers, body_bytes, encoding),
+        _ => actix_web::web::block(move || CachedResponse::new(status, &headers, body_bytes, encoding)).await?,
+    };
+    Ok(Fetched::Cached(req, cached, tags))
+}
+
+/// 一个缓存条目的读写参数
+struct Store {
+    key: String,
+    /// 请求开始时 Redis 中的代数
+    generation: String,
+    /// 条目在 Redis 中保留的时间，即软过期时间加上允许返回旧内容的时间
+    expire: Duration,
+    local: Option<Arc<LocalCache>>,
+    /// 请求开始时进程内缓存的代数
+    local_generation: u64,
+}
+
+impl Store {
+    /// 读取缓存并记下当前代数，需要刷新时抢占锁；出错时连同自身一起返回，供绕过缓存时使用
+    async fn lookup(
+        self,
+        conn: &mut MultiplexedConnection,
+        ttl: Duration,
+    ) -> Result<(Self, Lookup), (Self, RedisError)> {
+        match self.try_lookup(conn, ttl).await {
+            Ok((generation, lookup)) => Ok((Self { generation, ..self }, lookup)),
+            Err(e) => Err((self, e)),
+        }
+    }
+
+    async fn try_lookup(&self, conn: &mut MultiplexedConnection, ttl: Duration) -> RedisResult<(String, Lookup)> {
+        // 读取缓存，不存在时为空；同时记下当前代数
+        let (fields, generation): (HashMap<String, Vec<u8>>, Option<String>) = redis::pipe()
+            .hgetall(&self.key)
+            .get(GENERATION_KEY)
+            .query_async(conn)
+            .await?;
+        let generation = generation.unwrap_or_default();
+
+        if let Some((cached, tags)) = Self::parse(fields) {
+            let lock = match cached.age() >= ttl {
+                true => self.lock(conn).await?,
+                false => None,
+            };
+            return Ok((generation, Lookup::Hit(cached, tags, lock)));
+        }
+
+        // 缓存不存在，只让抢到锁的请求执行下游服务，其余请求等待它写入缓存
+        if let Some(lock) = self.lock(conn).await? {
+            return Ok((generation, Lookup::Miss(Some(lock))));
+        }
+        let lock_key = self.lock_key();
+        let deadline = Instant::now() + LOCK_WAIT;
+        loop {
+            let (fields, holder): (HashMap<String, Vec<u8>>, Option<String>) = redis::pipe()
+                .hgetall(&self.key)
+                .get(&lock_key)
+                .query_async(conn)
+                .await?;
+            if let Some((cached, tags)) = Self::parse(fields) {
+                return Ok((generation, Lookup::Hit(cached, tags, None)));
+            }
+            match holder.as_deref() {
+                // 下游响应不可缓存，不再等待
+                Some(UNCACHEABLE) => return Ok((generation, Lookup::Miss(None))),
+                Some(_) => {}
+                // 持有者释放了锁却没有写入（出错或期间发生失效），重新抢锁
+                None => {
+                    if let Some(lock) = self.lock(conn).await? {
+                        return Ok((generation, Lookup::Miss(Some(lock))));
+                    }
+                }
+            }
+            if Instant::now() >= deadline {
+                // 等待超时，自己执行
+                return Ok((generation, Lookup::Miss(None)));
+            }
+            actix_web::rt::time::sleep(LOCK_POLL).await;
+        }
+    }
+
+    fn lock_key(&self) -> String {
+        format!("{LOCK_PREFIX}{}", self.key)
+    }
+
+    fn parse(mut fields: HashMap<String, Vec<u8>>) -> Option<(CachedResponse, Vec<String>)> {
+        let tags = String::from_utf8(fields.remove(TAGS_FIELD)?).ok()?;
+        let tags = tags.lines().map(str::to_owned).collect();
+        Some((CachedResponse::from_fields(fields)?, tags))
+    }
+
+    /// 抢占刷新该条目的锁，已被其他请求持有时返回 `None`
+    async fn lock(&self, conn: &mut MultiplexedConnection) -> RedisResult<Option<Lock>> {
+        let lock = Lock {
+            key: self.lock_key(),
+            token: Uuid::new_v4().to_string(),
+        };
+        let acquired: bool = redis::cmd("SET")
+            .arg(&lock.key)
+            .arg(&lock.token)
+            .arg("NX")
+            .arg("PX")
+            .arg(LOCK_TTL.as_millis() as u64)
+            .query_async::<Option<String>>(conn)
+            .await?
+            .is_some();
+        Ok(acquired.then_some(lock))
+    }
+
+    /// 写入缓存，并登记到各个标签；请求开始后发生过失效时不写入，返回 `false`
+    async fn save(&self, conn: &mut MultiplexedConnection, tags: &CacheTags, cached: &CachedResponse) -> RedisResult<bool> {
+        let mut store = STORE_SCRIPT.prepare_invoke();
+        store.key(GENERATION_KEY).key(&self.key);
+        for tag in &tags.0 {
+            store.key(format!("{TAG_PREFIX}{tag}"));
+        }
+        store.arg(&self.generation).arg(self.expire.as_secs());
+        for (field, value) in cached.to_fields() {
+            store.arg(field).arg(value);
+        }
+        store.arg(TAGS_FIELD).arg(tags.names().join("\n"));
+        let stored: i32 = store.invoke_async(conn).await?;
+        Ok(stored == 1)
+    }
+
+    /// 写入进程内缓存
+    fn remember(&self, cached: &CachedResponse, tags: Vec<String>) {
+        if let Some(local) = &self.local {
+            local.insert(self.key.clone(), cached.clone(), tags, self.local_generation);
+        }
+    }
+}
+
+/// 刷新缓存条目的锁，只由持有者释放；持有者异常退出时在 [`LOCK_TTL`] 后自动过期
+struct Lock {
+    key: String,
+    token: String,
+}
+
+impl Lock {
+    async fn release(&self, conn: &mut MultiplexedConnection) -> RedisResult<()> {
+        RELEASE_SCRIPT.key(&self.key).arg(&self.token).invoke_async(conn).await
+    }
+
+    /// 下游响应不可缓存：把锁换成标记，正在等待和随后到达的请求不必等到超时
+    async fn release_uncacheable(&self, conn: &mut MultiplexedConnection) -> RedisResult<()> {
+        RELEASE_SCRIPT
+            .key(&self.key)
+            .arg(&self.token)
+            .arg(UNCACHEABLE)
+            .arg(LOCK_WAIT.as_millis() as u64)
+            .invoke_async(conn)
+            .await
+    }
+}
+
 impl CacheMiddleware {
     pub fn new(client: Client) -> Self {
         Self {
             client,
-            ttl: 60,
+            ttl: Duration::from_secs(60),
+            stale: Duration::ZERO,
+            local: None,
             key_gen: Rc::new(|req| req.uri().to_string()),
             filter: Rc::new(|req| req.method() == actix_web::http::Method::GET),
         }
     }
 
+    /// 缓存条目保持新鲜的时间（秒，软过期时间），同时作为响应的 `Cache-Control: max-age`
     pub fn with_ttl(mut self, ttl: u64) -> Self {
-        self.ttl = ttl;
+        self.ttl = Duration::from_secs(ttl);
+        self
+    }
+
+    /// 软过期之后仍可返回旧内容的时间（秒），期间由一个请求在后台刷新；
+    /// 硬过期时间为两者之和，默认为 0，即过期后立即删除
+    pub fn with_stale(mut self, stale: u64) -> Self {
+        self.stale = Duration::from_secs(stale);
+        self
+    }
+
+    /// 在 Redis 之前使用的进程内缓存，多个中间件和 [`CacheInvalidator`] 共享同一个实例
+    pub fn with_local(mut self, local: Arc<LocalCache>) -> Self {
+        self.local = Some(local);
         self
     }
 
@@ -149,4 +544,68 @@ impl CacheMiddleware {
         self.key_gen = Rc::new(f);
         self
     }
-}
\ No newline at end of file
+}
+
+/// 修改文章后清除带有指定标签的缓存条目，并通知其他实例清除各自的进程内缓存。
+///
+/// 与缓存中间件一样经过带超时和熔断的连接，Redis 无响应时最多等待 1 秒，熔断期间直接返回错误
+#[derive(Clone)]
+pub struct CacheInvalidator {
+    redis: Arc<RedisConnection>,
+    local: Option<Arc<LocalCache>>,
+}
+
+impl CacheInvalidator {
+    pub fn new(client: Client) -> Self {
+        Self::with_connection(Arc::new(RedisConnection::new(client)))
+    }
+
+    /// 复用已有的共享连接
+    pub fn with_connection(redis: Arc<RedisConnection>) -> Self {
+        Self { redis, local: None }
+    }
+
+    /// 同时清除本进程的进程内缓存，Redis 不可用时也会清除
+    pub fn with_local(mut self, local: Arc<LocalCache>) -> Self {
+        self.local = Some(local);
+        self
+    }
+
+    /// 先递增代数，使尚未写入的旧响应不再写入，再删除已缓存的条目并发布失效通知
+    pub async fn invalidate(&self, tags: &[CacheTag]) -> RedisResult<()> {
+        let names: Vec<String> = tags.iter().map(CacheTag::to_string).collect();
+        let result = self.invalidate_redis(&names).await;
+        if let Some(local) = &self.local {
+            local.invalidate(&names);
+        }
+        result
+    }
+
+    async fn invalidate_redis(&self, names: &[String]) -> RedisResult<()> {
+        let mut conn = self.redis.try_connection().await?;
+        let result = Self::invalidate_with(&mut conn, names).await;
+        if let Err(e) = &result {
+            self.redis.failed(e);
+        }
+        result
+    }
+
+    async fn invalidate_with(conn: &mut MultiplexedConnection, names: &[String]) -> RedisResult<()> {
+        let sets: Vec<String> = names.iter().map(|tag| format!("{TAG_PREFIX}{tag}")).collect();
+
+        let mut pipe = redis::pipe();
+        pipe.atomic().incr(GENERATION_KEY, 1).ignore();
+        for set in &sets {
+            pipe.smembers(set);
+        }
+        let members: Vec<Vec<String>> = pipe.query_async(conn).await?;
+
+        let keys: Vec<String> = members.into_iter().flatten().chain(sets).collect();
+        if !keys.is_empty() {
+            let _: () = conn.del(keys).await?;
+        }
+        // 删除之后再通知，其他实例收到通知后不会再从 Redis 读到旧内容
+        let _: () = conn.publish(INVALIDATE_CHANNEL, names.join("\n")).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use actix_web::http::header::{
+    self, AcceptEncoding, CacheControl, CacheDirective, ContentEncoding, Encoding, EntityTag, ETag, HeaderMap,
+    HeaderName, HeaderValue, HttpDate, IfModifiedSince, IfNoneMatch, LastModified,
+};
+use actix_web::http::StatusCode;
+use actix_web::{HttpMessage, HttpRequest, HttpResponse};
+use bytes::Bytes;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sha2::{Digest, Sha256};
+
+/// 随缓存一起保存并在命中时原样返回的响应头，其余响应头（如 `Set-Cookie`、`Date`）不保存
+const STORED_HEADERS: [HeaderName; 5] = [
+    header::CONTENT_TYPE,
+    header::CONTENT_ENCODING,
+    header::CONTENT_LANGUAGE,
+    header::CONTENT_DISPOSITION,
+    header::X_CONTENT_TYPE_OPTIONS,
+];
+
+/// 预先压缩的格式，按优先级排列
+const SUPPORTED_ENCODINGS: [Encoding; 3] = [Encoding::brotli(), Encoding::gzip(), Encoding::identity()];
+
+/// 小于该长度的响应体压缩后几乎不会变小，不压缩
+const MIN_COMPRESS_SIZE: usize = 256;
+
+/// 按 `Accept-Encoding` 选择缓存条目的压缩格式，只会是 brotli、gzip 或不压缩
+pub fn negotiate(req: &HttpRequest) -> ContentEncoding {
+    match req.get_header::<AcceptEncoding>().and_then(|accept| accept.negotiate(SUPPORTED_ENCODINGS.iter())) {
+        Some(Encoding::Known(encoding)) => encoding,
+        _ => ContentEncoding::Identity,
+    }
+}
+
+fn compress(body: &[u8], encoding: ContentEncoding) -> std::io::Result<Vec<u8>> {
+    match encoding {
+        ContentEncoding::Brotli => {
+            let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, 9, 22);
+            encoder.write_all(body)?;
+            Ok(encoder.into_inner())
+        }
+        ContentEncoding::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+            encoder.write_all(body)?;
+            encoder.finish()
+        }
+        _ => Ok(body.to_vec()),
+    }
+}
+
+const STATUS_FIELD: &str = "status";
+const HEADERS_FIELD: &str = "headers";
+const BODY_FIELD: &str = "body";
+const ETAG_FIELD: &str = "etag";
+const CREATED_AT_FIELD: &str = "created_at";
+const LAST_MODIFIED_FIELD: &str = "last_modified";
+
+/// 缓存的一条响应，保存为 Redis hash 的各个字段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResponse {
+    pub status: StatusCode,
+    pub headers: Vec<(HeaderName, HeaderValue)>,
+    pub body: Bytes,
+    /// 压缩后响应体 SHA-256 的前 16 字节，作为强 ETag，不同压缩格式的 ETag 不同
+    pub etag: String,
+    /// 生成缓存的时间，精确到秒，用于计算剩余有效期
+    pub created_at: SystemTime,
+    /// 后端响应中的 `Last-Modified`，没有时响应也不带该头，条件请求只比较 ETag
+    pub last_modified: Option<SystemTime>,
+}
+
+impl CachedResponse {
+    /// `encoding` 为 [`negotiate`] 的结果，响应体足够大且下游没有压缩时按该格式压缩。
+    /// 压缩较慢，在异步代码中应放到阻塞线程池中调用
+    pub fn new(status: StatusCode, headers: &HeaderMap, body: Bytes, encoding: ContentEncoding) -> Self {
+        let last_modified = headers.get(header::LAST_MODIFIED)
+            .and_then(|value| value.to_str().ok()?.parse::<HttpDate>().ok())
+            .map(SystemTime::from);
+        let mut headers: Vec<(HeaderName, HeaderValue)> = STORED_HEADERS.iter()
+            .flat_map(|name| headers.get_all(name).map(|value| (name.clone(), value.clone())))
+            .collect();
+        let mut body = body;
+        if encoding != ContentEncoding::Identity
+            && body.len() >= MIN_COMPRESS_SIZE
+            && !headers.iter().any(|(name, _)| name == header::CONTENT_ENCODING)
+            && let Ok(compressed) = compress(&body, encoding)
+        {
+            body = Bytes::from(compressed);
+            headers.push((header::CONTENT_ENCODING, encoding.to_header_value()));
+        }
+
+        let digest = Sha256::digest(&body);
+        let etag = digest[..16].iter().map(|b| format!("{b:02x}")).collect();
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+
+        Self {
+            status,
+            headers,
+            body,
+            etag,
+            created_at: UNIX_EPOCH + Duration::from_secs(now),
+            last_modified,
+        }
+    }
+
+    /// 只缓存 2xx 响应，下游声明 `no-store` 或 `private` 时也不缓存
+    pub fn is_cacheable(status: StatusCode, headers: &HeaderMap) -> bool {
+        status.is_success()
+            && !headers.get_all(header::CACHE_CONTROL).any(|v| {
+                v.to_str().is_ok_and(|v| v.split(',').any(|d| matches!(d.trim(), "no-store" | "private")))
+            })
+    }
+
+    pub fn to_fields(&self) -> Vec<(&'static str, Vec<u8>)> {
+        let mut headers = Vec::new();
+        for (name, value) in &self.headers {
+            headers.extend_from_slice(name.as_str().as_bytes());
+            headers.extend_from_slice(b": ");
+            headers.extend_from_slice(value.as_bytes());
+            headers.push(b'\n');
+        }
+        let secs = |time: SystemTime| time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs().to_string();
+
+        let mut fields = vec![
+            (STATUS_FIELD, self.status.as_str().as_bytes().to_vec()),
+            (HEADERS_FIELD, headers),
+            (BODY_FIELD, self.body.to_vec()),
+            (ETAG_FIELD, self.etag.as_bytes().to_vec()),
+            (CREATED_AT_FIELD, secs(self.created_at).into_bytes()),
+        ];
+        if let Some(last_modified) = self.last_modified {
+            fields.push((LAST_MODIFIED_FIELD, secs(last_modified).into_bytes()));
+        }
+        fields
+    }
+
+    /// 字段不完整或格式不对时视为未命中，`last_modified` 可以没有
+    pub fn from_fields(mut fields: HashMap<String, Vec<u8>>) -> Option<Self> {
+        let time = |bytes: Vec<u8>| Some(UNIX_EPOCH + Duration::from_secs(String::from_utf8(bytes).ok()?.parse().ok()?));
+        let status = StatusCode::from_bytes(&fields.remove(STATUS_FIELD)?).ok()?;
+        let etag = String::from_utf8(fields.remove(ETAG_FIELD)?).ok()?;
+        let created_at = time(fields.remove(CREATED_AT_FIELD)?)?;
+        let last_modified = match fields.remove(LAST_MODIFIED_FIELD) {
+            Some(bytes) => Some(time(bytes)?),
+            None => None,
+        };
+        let body = Bytes::from(fields.remove(BODY_FIELD)?);
+
+        let mut headers = Vec::new();
+        for line in fields.remove(HEADERS_FIELD)?.split(|&b| b == b'\n').filter(|l| !l.is_empty()) {
+            let at = line.windows(2).position(|w| w == b": ")?;
+            headers.push((
+                HeaderName::from_bytes(&line[..at]).ok()?,
+                HeaderValue::from_bytes(&line[at + 2..]).ok()?,
+            ));
+        }
+
+        Some(Self {
+            status,
+            headers,
+            body,
+            etag,
+            created_at,
+            last_modified,
+        })
+    }
+
+    /// 缓存条目已存在的时间
+    pub fn age(&self) -> Duration {
+        SystemTime::now().duration_since(self.created_at).unwrap_or_default()
+    }
+
+    /// 有 `If-None-Match` 时只比较 ETag，否则在有 `Last-Modified` 时比较 `If-Modified-Since`
+    pub fn not_modified(&self, req: &HttpRequest) -> bool {
+        let etag = EntityTag::new_strong(self.etag.clone());
+        match req.get_header::<IfNoneMatch>() {
+            Some(IfNoneMatch::Any) => true,
+            Some(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(&etag)),
+            None => req.get_header::<IfModifiedSince>()
+                .zip(self.last_modified)
+                .is_some_and(|(IfModifiedSince(since), last_modified)| last_modified <= SystemTime::from(since)),
+        }
+    }
+
+    /// 构造返回给客户端的响应，条件请求命中时返回 304；`max_age` 为缓存条目剩余的有效期。
+    ///
+    /// 缓存按压缩格式分别保存，响应总是带有 `Vary: Accept-Encoding`
+    pub fn respond(&self, req: &HttpRequest, max_age: Duration) -> HttpResponse {
+        let not_modified = self.not_modified(req);
+        let mut builder = if not_modified {
+            HttpResponse::NotModified()
+        } else {
+            HttpResponse::build(self.status)
+        };
+        if let Some(last_modified) = self.last_modified {
+            builder.insert_header(LastModified(HttpDate::from(last_modified)));
+        }
+        builder
+            .insert_header(ETag(EntityTag::new_strong(self.etag.clone())))
+            .insert_header((header::VARY, "Accept-Encoding"))
+            .insert_header(CacheControl(vec![
+                CacheDirective::Public,
+                CacheDirective::MaxAge(max_age.as_secs() as u32),
+            ]));
+
+        if not_modified {
+            return builder.finish();
+        }
+        for (name, value) in &self.headers {
+            builder.append_header((name.clone(), value.clone()));
+        }
+        builder.body(self.body.clone())
+    }
+}
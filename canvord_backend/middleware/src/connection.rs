@@ -0,0 +1,104 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use redis::aio::MultiplexedConnection;
+use redis::{AsyncConnectionConfig, Client, ErrorKind, RedisError};
+
+/// 建立连接和等待响应的超时时间，Redis 无响应时请求不会被长时间挂起
+const TIMEOUT: Duration = Duration::from_secs(1);
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+enum State {
+    Connected(MultiplexedConnection),
+    /// 某个请求正在重新连接，其他请求直接绕过缓存
+    Connecting,
+    /// 熔断中，到 `retry_at` 之前不再尝试连接
+    Open { retry_at: Instant },
+}
+
+struct Breaker {
+    state: State,
+    backoff: Duration,
+}
+
+/// 共享的 Redis 连接，缓存中间件每个 worker 一个，失效和登录令牌的记录在所有 worker 间共享一个。
+///
+/// 连接失败或命令出错后断开并熔断一段时间，期间 [`connection`](Self::connection) 返回 `None`；
+/// 连续失败时等待时间从 1 秒起逐次翻倍，最长 60 秒，连接成功后重置。
+pub struct RedisConnection {
+    client: Client,
+    breaker: Mutex<Breaker>,
+}
+
+impl RedisConnection {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            breaker: Mutex::new(Breaker { state: State::Open { retry_at: Instant::now() }, backoff: MIN_BACKOFF }),
+        }
+    }
+
+    /// 返回可用的连接，熔断中或连接失败时返回 `None`
+    pub async fn connection(&self) -> Option<MultiplexedConnection> {
+        {
+            let mut breaker = self.breaker.lock().unwrap();
+            match &breaker.state {
+                State::Connected(conn) => return Some(conn.clone()),
+                State::Connecting => return None,
+                State::Open { retry_at } if Instant::now() < *retry_at => return None,
+                State::Open { .. } => {}
+            }
+            breaker.state = State::Connecting;
+        }
+        let _guard = ConnectingGuard(self);
+
+        let config = AsyncConnectionConfig::new()
+            .set_connection_timeout(TIMEOUT)
+            .set_response_timeout(TIMEOUT);
+        match self.client.get_multiplexed_async_connection_with_config(&config).await {
+            Ok(conn) => {
+                let mut breaker = self.breaker.lock().unwrap();
+                if breaker.backoff > MIN_BACKOFF {
+                    log::info!("已重新连接 Redis");
+                }
+                breaker.backoff = MIN_BACKOFF;
+                breaker.state = State::Connected(conn.clone());
+                Some(conn)
+            }
+            Err(e) => {
+                self.failed(&e);
+                None
+            }
+        }
+    }
+
+    /// 与 [`connection`](Self::connection) 相同，熔断中时返回错误而不是 `None`
+    pub async fn try_connection(&self) -> Result<MultiplexedConnection, RedisError> {
+        self.connection().await.ok_or_else(|| RedisError::from((ErrorKind::IoError, "Redis is unavailable")))
+    }
+
+    /// 命令出错后调用，断开连接并开始熔断；已在熔断中时不重复记录
+    pub fn failed(&self, e: &RedisError) {
+        let mut breaker = self.breaker.lock().unwrap();
+        if matches!(breaker.state, State::Open { .. }) {
+            return;
+        }
+        let backoff = breaker.backoff;
+        log::error!("Redis 不可用，{} 秒内不再连接: {e}", backoff.as_secs());
+        breaker.state = State::Open { retry_at: Instant::now() + backoff };
+        breaker.backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+/// 连接中的请求被取消（如客户端断开）时，连接结果不会写回状态；
+/// 此时恢复为可以立即重试，避免一直停留在 `Connecting` 而永远绕过缓存
+struct ConnectingGuard<'a>(&'a RedisConnection);
+
+impl Drop for ConnectingGuard<'_> {
+    fn drop(&mut self) {
+        let mut breaker = self.0.breaker.lock().unwrap();
+        if matches!(breaker.state, State::Connecting) {
+            breaker.state = State::Open { retry_at: Instant::now() };
+        }
+    }
+}
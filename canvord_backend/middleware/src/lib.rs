@@ -1 +1,4 @@
-pub mod cache;
\ No newline at end of file
+pub mod cache;
+pub mod cached_response;
+pub mod local_cache;
+pub mod connection;
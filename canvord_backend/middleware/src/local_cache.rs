@@ -0,0 +1,204 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use futures_util::StreamExt;
+use apistos::ApiComponent;
+use hashlink::LinkedHashMap;
+use redis::Client;
+use schemars::JsonSchema;
+use crate::cached_response::CachedResponse;
+
+/// 修改文章后在此频道发布需要清除的标签，每行一个
+pub(crate) const INVALIDATE_CHANNEL: &str = "http_cache_invalidate";
+
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+struct Entry {
+    cached: CachedResponse,
+    tags: Vec<String>,
+    size: usize,
+}
+
+#[derive(Default)]
+struct Entries {
+    /// 按最近使用排序，最久未使用的在前
+    map: LinkedHashMap<String, Entry>,
+    /// 标签到带有该标签的 key，失效时不必遍历全部条目
+    tagged: HashMap<String, HashSet<String>>,
+    bytes: usize,
+}
+
+impl Entries {
+    fn insert(&mut self, key: String, entry: Entry) {
+        self.remove(&key);
+        for tag in &entry.tags {
+            self.tagged.entry(tag.clone()).or_default().insert(key.clone());
+        }
+        self.bytes += entry.size;
+        self.map.insert(key, entry);
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.map.remove(key) {
+            self.forget(key, &entry);
+        }
+    }
+
+    /// 淘汰最久未使用的条目
+    fn pop_front(&mut self) -> bool {
+        let Some((key, entry)) = self.map.pop_front() else { return false };
+        self.forget(&key, &entry);
+        true
+    }
+
+    fn forget(&mut self, key: &str, entry: &Entry) {
+        self.bytes -= entry.size;
+        for tag in &entry.tags {
+            if let Some(keys) = self.tagged.get_mut(tag) {
+                keys.remove(key);
+                if keys.is_empty() {
+                    self.tagged.remove(tag);
+                }
+            }
+        }
+    }
+}
+
+/// 进程内缓存的统计，计数从启动时开始累计
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, JsonSchema, ApiComponent)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// 因条目数或字节数超出上限而淘汰的条目数，不包括过期和失效的条目
+    pub evictions: u64,
+    pub entries: usize,
+    pub bytes: usize,
+}
+
+/// Redis 之前的进程内 LRU 缓存，由同一进程的所有 worker 共享，按条目数和字节数限制大小。
+///
+/// 其他实例修改文章后通过 Redis 的失效通知频道广播标签，由 [`subscribe`](Self::subscribe) 清除对应的条目；
+/// Redis 不可用时作为唯一的缓存层使用。
+pub struct LocalCache {
+    entries: Mutex<Entries>,
+    max_entries: usize,
+    max_bytes: usize,
+    /// 每次清除时递增，请求开始后发生过清除的响应不再写入
+    generation: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl LocalCache {
+    pub fn new(max_entries: usize, max_bytes: usize) -> Self {
+        Self {
+            entries: Mutex::default(),
+            max_entries,
+            max_bytes,
+            generation: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// 返回存在时间小于 `max_age` 的条目；更旧的条目不删除，等待写入新内容时覆盖
+    pub fn get(&self, key: &str, max_age: Duration) -> Option<CachedResponse> {
+        let cached = self.entries.lock().unwrap().map.to_back(key)
+            .map(|entry| &entry.cached)
+            .filter(|cached| cached.age() < max_age)
+            .cloned();
+
+        let counter = if cached.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        cached
+    }
+
+    /// 写入条目，`generation` 为请求开始时的 [`generation`](Self::generation)，之后发生过清除时放弃写入
+    pub fn insert(&self, key: String, cached: CachedResponse, tags: Vec<String>, generation: u64) {
+        let size = key.len()
+            + cached.body.len()
+            + cached.headers.iter().map(|(k, v)| k.as_str().len() + v.len()).sum::<usize>();
+        if size > self.max_bytes || self.max_entries == 0 {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        if self.generation() != generation {
+            return;
+        }
+        entries.insert(key, Entry { cached, tags, size });
+
+        let mut evicted = 0;
+        while (entries.map.len() > self.max_entries || entries.bytes > self.max_bytes) && entries.pop_front() {
+            evicted += 1;
+        }
+        self.evictions.fetch_add(evicted, Ordering::Relaxed);
+    }
+
+    /// 清除带有任一标签的条目，标签为 [`CacheTag`](crate::cache::CacheTag) 的字符串形式
+    pub fn invalidate<T: AsRef<str>>(&self, tags: &[T]) {
+        let mut entries = self.entries.lock().unwrap();
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        let keys: Vec<String> = tags.iter()
+            .filter_map(|tag| entries.tagged.remove(tag.as_ref()))
+            .flatten()
+            .collect();
+        for key in keys {
+            entries.remove(&key);
+        }
+    }
+
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        *entries = Entries::default();
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        let entries = self.entries.lock().unwrap();
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            entries: entries.map.len(),
+            bytes: entries.bytes,
+        }
+    }
+
+    /// 订阅失效通知，断开后逐次加倍等待时间并重新订阅，不会返回。
+    ///
+    /// 未订阅期间可能错过通知，每次订阅成功时清空全部条目。
+    pub async fn subscribe(&self, client: Client) {
+        let mut backoff = MIN_BACKOFF;
+        loop {
+            match client.get_async_pubsub().await {
+                Ok(mut pubsub) => match pubsub.subscribe(INVALIDATE_CHANNEL).await {
+                    Ok(()) => {
+                        self.clear();
+                        backoff = MIN_BACKOFF;
+                        let mut messages = pubsub.on_message();
+                        while let Some(msg) = messages.next().await {
+                            match msg.get_payload::<String>() {
+                                Ok(payload) => self.invalidate(&payload.lines().collect::<Vec<_>>()),
+                                Err(e) => log::error!("无法解析访客缓存失效通知: {e}"),
+                            }
+                        }
+                        log::error!("访客缓存失效通知的订阅已断开");
+                    }
+                    Err(e) => log::error!("订阅访客缓存失效通知失败: {e}"),
+                },
+                Err(e) => log::error!("订阅访客缓存失效通知失败: {e}"),
+            }
+            actix_web::rt::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::time::{Duration, SystemTime};
+use actix_web::http::header::{self, ContentEncoding, HeaderMap, HeaderValue, HttpDate};
+use actix_web::http::StatusCode;
+use actix_web::test::TestRequest;
+use bytes::Bytes;
+use flate2::read::GzDecoder;
+use middleware::cached_response::{negotiate, CachedResponse};
+
+const MODIFIED: &str = "Sat, 09 Aug 2025 08:00:00 GMT";
+
+fn response() -> CachedResponse {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers.insert(header::SET_COOKIE, HeaderValue::from_static("session=1"));
+    headers.insert(header::LAST_MODIFIED, HeaderValue::from_static(MODIFIED));
+    CachedResponse::new(StatusCode::CREATED, &headers, Bytes::from_static(b"{\"code\":0}"), ContentEncoding::Gzip)
+}
+
+fn if_modified_since(since: SystemTime) -> actix_web::HttpRequest {
+    TestRequest::default().insert_header((header::IF_MODIFIED_SINCE, HttpDate::from(since).to_string())).to_http_request()
+}
+
+#[test]
+fn round_trips_through_fields_without_private_headers() {
+    let cached = response();
+    assert_eq!(cached.headers.len(), 1, "{:?}", cached.headers);
+
+    let fields: HashMap<String, Vec<u8>> = cached.to_fields()
+        .into_iter()
+        .map(|(k, v)| (k.to_owned(), v))
+        .collect();
+    assert_eq!(CachedResponse::from_fields(fields), Some(cached));
+
+    let mut without_last_modified = response();
+    without_last_modified.last_modified = None;
+    let fields = without_last_modified.to_fields().into_iter().map(|(k, v)| (k.to_owned(), v)).collect();
+    assert_eq!(CachedResponse::from_fields(fields), Some(without_last_modified));
+
+    // 旧格式或不完整的条目视为未命中
+    let old = HashMap::from([("body".to_owned(), b"x".to_vec())]);
+    assert_eq!(CachedResponse::from_fields(old), None);
+}
+
+#[test]
+fn only_success_responses_without_no_store_are_cacheable() {
+    let mut headers = HeaderMap::new();
+    assert!(CachedResponse::is_cacheable(StatusCode::OK, &headers));
+    assert!(!CachedResponse::is_cacheable(StatusCode::NOT_FOUND, &headers));
+    assert!(!CachedResponse::is_cacheable(StatusCode::INTERNAL_SERVER_ERROR, &headers));
+
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("max-age=0, no-store"));
+    assert!(!CachedResponse::is_cacheable(StatusCode::OK, &headers));
+}
+
+#[test]
+fn answers_conditional_requests_with_304() {
+    let cached = response();
+    let etag = format!("\"{}\"", cached.etag);
+
+    let req = TestRequest::default().to_http_request();
+    let res = cached.respond(&req, Duration::from_secs(60));
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert_eq!(res.headers().get(header::ETAG).unwrap(), etag.as_str());
+    assert_eq!(res.headers().get(header::CACHE_CONTROL).unwrap(), "public, max-age=60");
+    assert_eq!(res.headers().get(header::CONTENT_TYPE).unwrap(), "application/json");
+    assert_eq!(res.headers().get(header::LAST_MODIFIED).unwrap(), MODIFIED);
+    assert_eq!(res.headers().get(header::VARY).unwrap(), "Accept-Encoding");
+
+    let req = TestRequest::default().insert_header((header::IF_NONE_MATCH, etag.as_str())).to_http_request();
+    let res = cached.respond(&req, Duration::from_secs(60));
+    assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(res.headers().get(header::VARY).unwrap(), "Accept-Encoding");
+    assert!(!res.headers().contains_key(header::CONTENT_TYPE));
+
+    let req = TestRequest::default().insert_header((header::IF_NONE_MATCH, "\"other\"")).to_http_request();
+    assert!(!cached.not_modified(&req));
+
+    let last_modified = cached.last_modified.unwrap();
+    assert!(cached.not_modified(&if_modified_since(last_modified)));
+    assert!(!cached.not_modified(&if_modified_since(last_modified - Duration::from_secs(1))));
+
+    // If-None-Match 不匹配时忽略 If-Modified-Since
+    let req = TestRequest::default()
+        .insert_header((header::IF_NONE_MATCH, "\"other\""))
+        .insert_header((header::IF_MODIFIED_SINCE, MODIFIED))
+        .to_http_request();
+    assert!(!cached.not_modified(&req));
+}
+
+#[test]
+fn last_modified_comes_from_the_backend_response() {
+    let cached = CachedResponse::new(StatusCode::OK, &HeaderMap::new(), Bytes::from_static(b"{}"), ContentEncoding::Identity);
+    assert_eq!(cached.last_modified, None);
+    assert!(cached.age() < Duration::from_secs(2));
+
+    // 没有 Last-Modified 时不返回该头，If-Modified-Since 也不会得到 304
+    let res = cached.respond(&TestRequest::default().to_http_request(), Duration::from_secs(60));
+    assert!(!res.headers().contains_key(header::LAST_MODIFIED));
+    assert!(!cached.not_modified(&if_modified_since(SystemTime::now() + Duration::from_secs(60))));
+
+    // 与生成缓存的时间无关
+    assert!(response().last_modified.unwrap() < response().created_at - Duration::from_secs(3600));
+}
+
+#[test]
+fn negotiates_pre_compressed_encodings() {
+    let negotiated = |accept: Option<&str>| {
+        let mut req = TestRequest::default();
+        if let Some(accept) = accept {
+            req = req.insert_header((header::ACCEPT_ENCODING, accept));
+        }
+        negotiate(&req.to_http_request())
+    };
+    assert_eq!(negotiated(None), ContentEncoding::Identity);
+    assert_eq!(negotiated(Some("gzip, deflate, br")), ContentEncoding::Brotli);
+    assert_eq!(negotiated(Some("gzip")), ContentEncoding::Gzip);
+    assert_eq!(negotiated(Some("br;q=0.5, gzip")), ContentEncoding::Gzip);
+    assert_eq!(negotiated(Some("zstd")), ContentEncoding::Identity);
+}
+
+#[test]
+fn compresses_large_bodies_once() {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    let body = Bytes::from("{\"content\":\"hello\"}".repeat(100));
+
+    let identity = CachedResponse::new(StatusCode::OK, &headers, body.clone(), ContentEncoding::Identity);
+    assert_eq!(identity.body, body);
+
+    let gzip = CachedResponse::new(StatusCode::OK, &headers, body.clone(), ContentEncoding::Gzip);
+    assert!(gzip.body.len() < body.len());
+    assert_ne!(gzip.etag, identity.etag);
+    let mut decoded = String::new();
+    GzDecoder::new(&gzip.body[..]).read_to_string(&mut decoded).unwrap();
+    assert_eq!(decoded.as_bytes(), &body[..]);
+
+    let req = TestRequest::default().to_http_request();
+    let res = gzip.respond(&req, Duration::from_secs(60));
+    assert_eq!(res.headers().get(header::CONTENT_ENCODING).unwrap(), "gzip");
+
+    let brotli = CachedResponse::new(StatusCode::OK, &headers, body.clone(), ContentEncoding::Brotli);
+    assert!(brotli.headers.contains(&(header::CONTENT_ENCODING, HeaderValue::from_static("br"))));
+
+    // 小响应体不压缩
+    let small = CachedResponse::new(StatusCode::OK, &headers, Bytes::from_static(b"{}"), ContentEncoding::Brotli);
+    assert!(!small.headers.iter().any(|(name, _)| name == header::CONTENT_ENCODING));
+}
@@ -0,0 +1,129 @@
+use std::cell::Cell;
+use std::net::TcpListener;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use actix_web::http::header;
+use actix_web::middleware::Compress;
+use actix_web::{test, web, App, HttpResponse};
+use middleware::cache::CacheMiddleware;
+use middleware::local_cache::LocalCache;
+
+/// 没有服务监听的端口，连接会立即失败
+const UNREACHABLE: &str = "redis://127.0.0.1:1/";
+
+async fn requests_reaching_handler(middleware: CacheMiddleware) -> usize {
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+    let app = test::init_service(
+        App::new()
+            .wrap(middleware)
+            .route("/page", web::get().to(move || {
+                counter.set(counter.get() + 1);
+                async { HttpResponse::Ok().body("hello") }
+            })),
+    )
+    .await;
+
+    for _ in 0..3 {
+        let res = test::call_service(&app, test::TestRequest::get().uri("/page").to_request()).await;
+        assert!(res.status().is_success());
+        assert_eq!(test::read_body(res).await, "hello");
+    }
+    calls.get()
+}
+
+#[actix_web::test]
+async fn bypasses_cache_when_redis_is_unavailable() {
+    let client = redis::Client::open(UNREACHABLE).unwrap();
+    assert_eq!(requests_reaching_handler(CacheMiddleware::new(client)).await, 3);
+}
+
+#[actix_web::test]
+async fn serves_from_local_cache_when_redis_is_unavailable() {
+    let client = redis::Client::open(UNREACHABLE).unwrap();
+    let local = Arc::new(LocalCache::new(16, 1024 * 1024));
+    let middleware = CacheMiddleware::new(client).with_local(local.clone());
+    assert_eq!(requests_reaching_handler(middleware).await, 1);
+
+    let stats = local.stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (2, 1, 1));
+}
+
+#[actix_web::test]
+async fn caches_each_encoding_separately_behind_compress() {
+    let client = redis::Client::open(UNREACHABLE).unwrap();
+    let local = Arc::new(LocalCache::new(16, 1024 * 1024));
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+    let app = test::init_service(
+        App::new()
+            // 与服务中一致：Compress 包在整个 App 外层，缓存中间件只包在访客接口上
+            .wrap(Compress::default())
+            .service(
+                web::scope("")
+                    .wrap(CacheMiddleware::new(client).with_local(local))
+                    .route("/page", web::get().to(move || {
+                        counter.set(counter.get() + 1);
+                        async { HttpResponse::Ok().content_type("application/json").body("hello ".repeat(100)) }
+                    })),
+            ),
+    )
+    .await;
+
+    for accept in ["br", "gzip", "br", "identity", "zstd"] {
+        let req = test::TestRequest::get().uri("/page").insert_header((header::ACCEPT_ENCODING, accept)).to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.headers().get(header::VARY).unwrap(), "Accept-Encoding");
+        let encoding = res.headers().get(header::CONTENT_ENCODING).map(|v| v.to_str().unwrap().to_owned());
+        let body = test::read_body(res).await;
+        match accept {
+            "identity" => {
+                assert_eq!(encoding, None);
+                assert_eq!(body.len(), 600);
+            }
+            "zstd" => {
+                // 命中不压缩的条目，由 Compress 压缩
+                assert_eq!(encoding.as_deref(), Some("zstd"));
+                assert!(body.len() < 600);
+            }
+            _ => {
+                // 已压缩的响应不会被 Compress 再压缩一次
+                assert_eq!(encoding.as_deref(), Some(accept));
+                assert!(body.len() < 600);
+            }
+        }
+    }
+    assert_eq!(calls.get(), 3);
+}
+
+#[actix_web::test]
+async fn retries_connection_after_cancelled_connect() {
+    // 接受连接但从不响应，建立 Redis 连接会一直等到超时
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = redis::Client::open(format!("redis://{}/", listener.local_addr().unwrap())).unwrap();
+    let accepted = Arc::new(AtomicUsize::new(0));
+    let counter = accepted.clone();
+    std::thread::spawn(move || {
+        let mut streams = Vec::new();
+        for stream in listener.incoming() {
+            streams.push(stream);
+            counter.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+
+    let app = test::init_service(
+        App::new()
+            .wrap(CacheMiddleware::new(client))
+            .route("/page", web::get().to(|| async { HttpResponse::Ok().body("hello") })),
+    )
+    .await;
+
+    // 连接过程中请求被取消，下一个请求仍会重新连接，而不是一直绕过缓存
+    for attempt in 1..=2 {
+        let call = test::call_service(&app, test::TestRequest::get().uri("/page").to_request());
+        assert!(actix_web::rt::time::timeout(Duration::from_millis(200), call).await.is_err());
+        assert_eq!(accepted.load(Ordering::SeqCst), attempt);
+    }
+}
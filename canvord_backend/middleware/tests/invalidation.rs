@@ -0,0 +1,158 @@
+use std::cell::Cell;
+use std::net::TcpListener;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use actix_web::{test, web, App, HttpMessage, HttpRequest, HttpResponse};
+use middleware::cache::{CacheInvalidator, CacheMiddleware, CacheTag, CacheTags};
+use middleware::local_cache::LocalCache;
+use uuid::Uuid;
+
+/// 没有服务监听的端口，连接会立即失败
+const UNREACHABLE: &str = "redis://127.0.0.1:1/";
+
+/// 需要 Redis 的测试只在设置了 `TEST_REDIS_URL` 时运行
+fn test_redis() -> Option<redis::Client> {
+    let url = std::env::var("TEST_REDIS_URL").ok()?;
+    Some(redis::Client::open(url).unwrap())
+}
+
+/// `/article/{id}` 带有文章和分类标签，`/list` 带有列表标签；返回记录每个路径执行次数的计数器
+fn tagged_routes(cfg: &mut web::ServiceConfig, article: Rc<Cell<usize>>, list: Rc<Cell<usize>>) {
+    cfg.route("/article/{id}", web::get().to(move |req: HttpRequest, id: web::Path<i64>| {
+        article.set(article.get() + 1);
+        req.extensions_mut().insert(CacheTags(vec![CacheTag::Article(*id), CacheTag::Categories]));
+        async { HttpResponse::Ok().body("article") }
+    }))
+    .route("/list", web::get().to(move |req: HttpRequest| {
+        list.set(list.get() + 1);
+        req.extensions_mut().insert(CacheTags(vec![CacheTag::Listing]));
+        async { HttpResponse::Ok().body("list") }
+    }));
+}
+
+macro_rules! get {
+    ($app:expr, $uri:expr) => {
+        assert!(test::call_service($app, test::TestRequest::get().uri($uri).to_request()).await.status().is_success())
+    };
+}
+
+#[actix_web::test]
+async fn invalidates_only_tagged_local_entries() {
+    let client = redis::Client::open(UNREACHABLE).unwrap();
+    let local = Arc::new(LocalCache::new(16, 1024 * 1024));
+    let invalidator = CacheInvalidator::new(client.clone()).with_local(local.clone());
+    let (article, list) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+    let (a, l) = (article.clone(), list.clone());
+    let app = test::init_service(
+        App::new()
+            .wrap(CacheMiddleware::new(client).with_local(local))
+            .configure(move |cfg| tagged_routes(cfg, a, l)),
+    )
+    .await;
+
+    for uri in ["/article/1", "/article/2", "/list", "/article/1", "/article/2", "/list"] {
+        get!(&app, uri);
+    }
+    assert_eq!((article.get(), list.get()), (2, 1));
+
+    // Redis 不可用时仍然清除进程内缓存
+    assert!(invalidator.invalidate(&[CacheTag::Article(1)]).await.is_err());
+    for uri in ["/article/1", "/article/2", "/list"] {
+        get!(&app, uri);
+    }
+    assert_eq!((article.get(), list.get()), (3, 1));
+
+    // 分类修改后文章响应全部失效，列表不受影响
+    let _ = invalidator.invalidate(&[CacheTag::Categories]).await;
+    for uri in ["/article/1", "/article/2", "/list"] {
+        get!(&app, uri);
+    }
+    assert_eq!((article.get(), list.get()), (5, 1));
+}
+
+#[actix_web::test]
+async fn invalidates_redis_entries_by_tag() {
+    let Some(client) = test_redis() else { return };
+    let invalidator = CacheInvalidator::new(client.clone());
+    let (article, list) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+    let (a, l) = (article.clone(), list.clone());
+    // 每次运行使用不同的文章 id，避免读到上次运行留下的条目
+    let id = Uuid::new_v4().as_u64_pair().0 as i64 & i64::MAX;
+    let uri = format!("/article/{id}");
+    let app = test::init_service(
+        App::new()
+            .wrap(CacheMiddleware::new(client).with_key_gen(|req| format!("invalidation-test:{}", req.path())))
+            .configure(move |cfg| tagged_routes(cfg, a, l)),
+    )
+    .await;
+
+    get!(&app, &uri);
+    get!(&app, &uri);
+    assert_eq!(article.get(), 1);
+
+    invalidator.invalidate(&[CacheTag::Article(id)]).await.unwrap();
+    get!(&app, &uri);
+    get!(&app, &uri);
+    assert_eq!(article.get(), 2);
+    assert!(list.get() == 0);
+}
+
+#[actix_web::test]
+async fn does_not_store_responses_invalidated_while_rendering() {
+    let Some(client) = test_redis() else { return };
+    let invalidator = CacheInvalidator::new(client.clone());
+    let slug = Uuid::new_v4().to_string();
+    let uri = format!("/slug/{slug}");
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+    let app = test::init_service(
+        App::new()
+            .wrap(CacheMiddleware::new(client))
+            .route("/slug/{slug}", web::get().to(move |req: HttpRequest, slug: web::Path<String>| {
+                counter.set(counter.get() + 1);
+                let first = counter.get() == 1;
+                let invalidator = invalidator.clone();
+                async move {
+                    req.extensions_mut().insert(CacheTags(vec![CacheTag::Slug(slug.clone())]));
+                    // 读出旧数据之后、写入缓存之前文章被修改：这份响应已经过期，不能写入
+                    if first {
+                        invalidator.invalidate(&[CacheTag::Slug(slug.into_inner())]).await.unwrap();
+                    }
+                    HttpResponse::Ok().body("article")
+                }
+            })),
+    )
+    .await;
+
+    get!(&app, &uri);
+    get!(&app, &uri);
+    get!(&app, &uri);
+    // 第一次的响应没有写入，第二次写入后第三次命中缓存
+    assert_eq!(calls.get(), 2);
+}
+
+#[actix_web::test]
+async fn gives_up_on_unresponsive_redis_and_then_skips_it() {
+    // 接受连接但从不响应
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = redis::Client::open(format!("redis://{}/", listener.local_addr().unwrap())).unwrap();
+    std::thread::spawn(move || {
+        let mut streams = Vec::new();
+        for stream in listener.incoming() {
+            streams.push(stream);
+        }
+    });
+    let local = Arc::new(LocalCache::new(16, 1024 * 1024));
+    let invalidator = CacheInvalidator::new(client).with_local(local);
+
+    // 等到超时后放弃，不会一直挂起
+    let start = Instant::now();
+    assert!(invalidator.invalidate(&[CacheTag::Listing]).await.is_err());
+    assert!(start.elapsed() < Duration::from_secs(3), "{:?}", start.elapsed());
+
+    // 熔断期间不再连接，立即返回
+    let start = Instant::now();
+    assert!(invalidator.invalidate(&[CacheTag::Listing]).await.is_err());
+    assert!(start.elapsed() < Duration::from_millis(100), "{:?}", start.elapsed());
+}
@@ -0,0 +1,106 @@
+use std::time::Duration;
+use actix_web::http::header::{ContentEncoding, HeaderMap};
+use actix_web::http::StatusCode;
+use bytes::Bytes;
+use middleware::cache::CacheTag;
+use middleware::cached_response::CachedResponse;
+use middleware::local_cache::LocalCache;
+
+const MAX_AGE: Duration = Duration::from_secs(60);
+
+fn response(body: &'static str) -> CachedResponse {
+    CachedResponse::new(StatusCode::OK, &HeaderMap::new(), Bytes::from_static(body.as_bytes()), ContentEncoding::Identity)
+}
+
+fn tags(tags: &[CacheTag]) -> Vec<String> {
+    tags.iter().map(CacheTag::to_string).collect()
+}
+
+#[test]
+fn tag_names() {
+    assert_eq!(CacheTag::Article(7).to_string(), "article:7");
+    assert_eq!(CacheTag::Slug("hello".into()).to_string(), "slug:hello");
+    assert_eq!(CacheTag::Listing.to_string(), "listing");
+    assert_eq!(CacheTag::Categories.to_string(), "categories");
+}
+
+#[test]
+fn evicts_least_recently_used_entries_beyond_limits() {
+    let cache = LocalCache::new(2, 1024);
+    let generation = cache.generation();
+    cache.insert("a".into(), response("a"), vec![], generation);
+    cache.insert("b".into(), response("b"), vec![], generation);
+    assert!(cache.get("a", MAX_AGE).is_some());
+
+    // b 最久未使用，先被淘汰
+    cache.insert("c".into(), response("c"), vec![], generation);
+    assert!(cache.get("b", MAX_AGE).is_none());
+    assert!(cache.get("a", MAX_AGE).is_some());
+
+    // 按字节数淘汰：每个条目 1 字节 key 加 8 字节响应体
+    let cache = LocalCache::new(10, 20);
+    for key in ["a", "b", "c"] {
+        cache.insert(key.into(), response("12345678"), vec![], generation);
+    }
+    assert!(cache.get("a", MAX_AGE).is_none());
+
+    // 超过上限的单个条目不写入
+    cache.insert("d".into(), response("123456789012345678901234"), vec![], generation);
+    assert!(cache.get("d", MAX_AGE).is_none());
+
+    let stats = cache.stats();
+    assert_eq!((stats.entries, stats.bytes, stats.evictions), (2, 18, 1));
+    assert_eq!((stats.hits, stats.misses), (0, 2));
+}
+
+#[test]
+fn invalidates_entries_by_tag() {
+    let cache = LocalCache::new(10, 1024);
+    let generation = cache.generation();
+    cache.insert("/visitor/article/hello".into(), response("a"), tags(&[CacheTag::Slug("hello".into()), CacheTag::Article(1)]), generation);
+    cache.insert("/visitor/page".into(), response("b"), tags(&[CacheTag::Listing, CacheTag::Article(2)]), generation);
+
+    cache.invalidate(&tags(&[CacheTag::Article(1)]));
+    assert!(cache.get("/visitor/article/hello", MAX_AGE).is_none());
+    assert!(cache.get("/visitor/page", MAX_AGE).is_some());
+
+    cache.invalidate(&["listing"]);
+    assert!(cache.get("/visitor/page", MAX_AGE).is_none());
+}
+
+#[test]
+fn skips_responses_read_before_invalidation() {
+    let cache = LocalCache::new(10, 1024);
+    let generation = cache.generation();
+    cache.invalidate(&tags(&[CacheTag::Listing]));
+    cache.insert("/visitor/page".into(), response("old"), tags(&[CacheTag::Listing]), generation);
+    assert!(cache.get("/visitor/page", MAX_AGE).is_none());
+
+    cache.insert("/visitor/page".into(), response("new"), tags(&[CacheTag::Listing]), cache.generation());
+    assert!(cache.get("/visitor/page", MAX_AGE).is_some());
+    assert!(cache.get("/visitor/page", Duration::ZERO).is_none());
+}
+
+#[test]
+fn tag_index_follows_replaced_and_evicted_entries() {
+    let cache = LocalCache::new(2, 1024);
+    let generation = cache.generation();
+    cache.insert("a".into(), response("a"), tags(&[CacheTag::Article(1)]), generation);
+    // 覆盖后只带有新的标签
+    cache.insert("a".into(), response("a"), tags(&[CacheTag::Article(2)]), generation);
+    cache.invalidate(&tags(&[CacheTag::Article(1)]));
+    let generation = cache.generation();
+    assert!(cache.get("a", MAX_AGE).is_some());
+
+    // 被淘汰的 key 之后以其他标签重新写入，不会被旧标签清除
+    cache.insert("b".into(), response("b"), tags(&[CacheTag::Listing]), generation);
+    cache.insert("c".into(), response("c"), vec![], generation);
+    assert!(cache.get("a", MAX_AGE).is_none());
+    cache.insert("a".into(), response("a"), vec![], generation);
+    cache.invalidate(&tags(&[CacheTag::Article(2)]));
+    assert!(cache.get("a", MAX_AGE).is_some());
+
+    cache.invalidate(&tags(&[CacheTag::Listing, CacheTag::Article(2)]));
+    assert!(cache.get("b", MAX_AGE).is_none());
+    assert_eq!(cache.stats().entries, 2);
+}
@@ -0,0 +1,175 @@
+use std::cell::Cell;
+use std::future::pending;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use actix_web::{test, web, App, HttpResponse};
+use futures_util::future::join_all;
+use middleware::cache::CacheMiddleware;
+use redis::AsyncCommands;
+use uuid::Uuid;
+
+/// 需要 Redis 的测试只在设置了 `TEST_REDIS_URL` 时运行
+fn test_redis() -> Option<redis::Client> {
+    let url = std::env::var("TEST_REDIS_URL").ok()?;
+    Some(redis::Client::open(url).unwrap())
+}
+
+/// 2 秒后软过期（生成时间按秒记录，1 秒会与刚写入的条目混淆）、之后 60 秒内返回旧内容的中间件；
+/// 每次运行使用不同的 key，避免读到上次运行留下的条目
+fn middleware(client: redis::Client) -> (CacheMiddleware, String) {
+    let key = format!("revalidation-test:{}", Uuid::new_v4());
+    let lock = format!("http_cache_lock:http_cache:{key}#identity");
+    (CacheMiddleware::new(client).with_ttl(2).with_stale(60).with_key_gen(move |_| key.clone()), lock)
+}
+
+macro_rules! get {
+    ($app:expr) => {{
+        let res = test::call_service($app, test::TestRequest::get().uri("/page").to_request()).await;
+        assert!(res.status().is_success());
+        test::read_body(res).await
+    }};
+}
+
+/// 等待条目软过期
+async fn expire() {
+    actix_web::rt::time::sleep(Duration::from_millis(2100)).await;
+}
+
+#[actix_web::test]
+async fn only_one_request_revalidates_while_others_get_stale_body() {
+    let Some(client) = test_redis() else { return };
+    let (middleware, _) = middleware(client);
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+    let app = test::init_service(
+        App::new()
+            .wrap(middleware)
+            .route("/page", web::get().to(move || {
+                counter.set(counter.get() + 1);
+                let body = counter.get().to_string();
+                async move {
+                    // 刷新较慢，其余请求到达时锁仍被持有
+                    actix_web::rt::time::sleep(Duration::from_millis(200)).await;
+                    HttpResponse::Ok().body(body)
+                }
+            })),
+    )
+    .await;
+
+    assert_eq!(get!(&app), "1");
+    expire().await;
+
+    let bodies = join_all((0..5).map(|_| async { get!(&app) })).await;
+    assert!(bodies.iter().all(|body| body == "1"), "{bodies:?}");
+
+    // 后台刷新完成后返回新内容
+    actix_web::rt::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(get!(&app), "2");
+    assert_eq!(calls.get(), 2);
+}
+
+#[actix_web::test]
+async fn lock_expires_when_refresher_never_finishes() {
+    let Some(client) = test_redis() else { return };
+    let mut conn = client.get_multiplexed_async_connection().await.unwrap();
+    let (middleware, lock) = middleware(client);
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+    let app = test::init_service(
+        App::new()
+            .wrap(middleware)
+            .route("/page", web::get().to(move || {
+                counter.set(counter.get() + 1);
+                let call = counter.get();
+                async move {
+                    // 第二次执行（第一次刷新）卡住，锁不会被主动释放
+                    if call == 2 {
+                        pending::<()>().await;
+                    }
+                    HttpResponse::Ok().body(call.to_string())
+                }
+            })),
+    )
+    .await;
+
+    assert_eq!(get!(&app), "1");
+    expire().await;
+    assert_eq!(get!(&app), "1");
+    actix_web::rt::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(calls.get(), 2);
+
+    // 锁被卡住的刷新持有，其他请求只返回旧内容
+    let ttl: i64 = conn.pttl(&lock).await.unwrap();
+    assert!(0 < ttl && ttl <= 10_000, "{ttl}");
+    assert_eq!(get!(&app), "1");
+    actix_web::rt::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(calls.get(), 2);
+
+    // 锁带有过期时间，不必等满 10 秒：提前让它过期，之后的请求重新抢到锁并刷新
+    let _: () = conn.pexpire(&lock, 1).await.unwrap();
+    actix_web::rt::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(get!(&app), "1");
+    actix_web::rt::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(calls.get(), 3);
+    assert_eq!(get!(&app), "3");
+}
+
+#[actix_web::test]
+async fn refreshes_in_background_while_stale_response_is_alive() {
+    let Some(client) = test_redis() else { return };
+    let (middleware, _) = middleware(client);
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new("data"))
+            .service(
+                // 路由参数和 app data 在后台刷新时同样可用
+                web::scope("/{section}").wrap(middleware).route("/page", web::get().to(
+                    move |section: web::Path<String>, data: web::Data<&'static str>| {
+                        counter.set(counter.get() + 1);
+                        let body = format!("{section}-{}-{}", data.get_ref(), counter.get());
+                        async move { HttpResponse::Ok().body(body) }
+                    },
+                )),
+            ),
+    )
+    .await;
+
+    let get = || test::call_service(&app, test::TestRequest::get().uri("/blog/page").to_request());
+    assert_eq!(test::read_body(get().await).await, "blog-data-1");
+    expire().await;
+
+    // 返回旧内容的响应尚未读取和释放时，后台刷新照常完成
+    let stale = get().await;
+    actix_web::rt::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(calls.get(), 2);
+    assert_eq!(test::read_body(stale).await, "blog-data-1");
+    assert_eq!(test::read_body(get().await).await, "blog-data-2");
+}
+
+#[actix_web::test]
+async fn waiters_do_not_wait_out_the_lock_for_uncacheable_responses() {
+    let Some(client) = test_redis() else { return };
+    let (middleware, _) = middleware(client);
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+    let app = test::init_service(
+        App::new()
+            .wrap(middleware)
+            .route("/page", web::get().to(move || {
+                counter.set(counter.get() + 1);
+                async {
+                    actix_web::rt::time::sleep(Duration::from_millis(300)).await;
+                    HttpResponse::ServiceUnavailable().finish()
+                }
+            })),
+    )
+    .await;
+
+    // 持有锁的请求得到不可缓存的响应后，等待的请求立即自己执行，而不是等满 2 秒
+    let start = Instant::now();
+    join_all((0..4).map(|_| test::call_service(&app, test::TestRequest::get().uri("/page").to_request()))).await;
+    assert!(start.elapsed() < Duration::from_millis(1500), "{:?}", start.elapsed());
+    assert_eq!(calls.get(), 4);
+}
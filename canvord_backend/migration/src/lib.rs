@@ -1,12 +1,36 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_table;
+mod m20250801_000001_create_user_table;
+mod m20250802_000001_create_article_revision_table;
+mod m20250803_000001_create_tag_table;
+mod m20250804_000001_create_category_table;
+mod m20250805_000001_create_media_table;
+mod m20250806_000001_create_media_variant_table;
+mod m20250807_000001_add_article_render_columns;
+mod m20250808_000001_add_article_publish_at;
+mod m20250815_000001_add_article_created_at_index;
+mod m20250816_000001_create_article_media_table;
+mod m20250817_000001_add_article_author_id;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20220101_000001_create_table::Migration)]
+        vec![
+            Box::new(m20220101_000001_create_table::Migration),
+            Box::new(m20250801_000001_create_user_table::Migration),
+            Box::new(m20250802_000001_create_article_revision_table::Migration),
+            Box::new(m20250803_000001_create_tag_table::Migration),
+            Box::new(m20250804_000001_create_category_table::Migration),
+            Box::new(m20250805_000001_create_media_table::Migration),
+            Box::new(m20250806_000001_create_media_variant_table::Migration),
+            Box::new(m20250807_000001_add_article_render_columns::Migration),
+            Box::new(m20250808_000001_add_article_publish_at::Migration),
+            Box::new(m20250815_000001_add_article_created_at_index::Migration),
+            Box::new(m20250816_000001_create_article_media_table::Migration),
+            Box::new(m20250817_000001_add_article_author_id::Migration),
+        ]
     }
 }
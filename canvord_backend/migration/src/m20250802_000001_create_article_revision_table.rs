@@ -0,0 +1,79 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 创建文章修订历史表
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ArticleRevision::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ArticleRevision::Id)
+                            .big_integer()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ArticleRevision::ArticleId).big_integer().not_null())
+                    .col(ColumnDef::new(ArticleRevision::Title).string().not_null())
+                    .col(ColumnDef::new(ArticleRevision::Description).string().not_null())
+                    .col(ColumnDef::new(ArticleRevision::ContentMd).custom("MEDIUMTEXT").not_null())
+                    .col(ColumnDef::new(ArticleRevision::CreatedAt).date_time().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_article_revision_article")
+                            .from(ArticleRevision::Table, ArticleRevision::ArticleId)
+                            .to(Article::Table, Article::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 已有文章写入一条初始快照
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "INSERT INTO article_revision (article_id, title, description, content_md, created_at) \
+             SELECT id, title, description, content_md, last_update FROM article",
+        )
+        .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(ArticleRevision::Table)
+                    .name("idx_article_revision_article_id")
+                    .col(ArticleRevision::ArticleId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    // 回滚删除表
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ArticleRevision::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ArticleRevision {
+    Table,
+    Id,
+    ArticleId,
+    Title,
+    Description,
+    ContentMd,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Article {
+    Table,
+    Id,
+}
@@ -0,0 +1,101 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 创建标签表和文章-标签关联表
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Tag::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Tag::Id)
+                            .big_integer()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Tag::Name).string().not_null().unique_key())
+                    .col(ColumnDef::new(Tag::Slug).string().not_null().unique_key())
+                    .col(ColumnDef::new(Tag::CreatedAt).date_time().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ArticleTag::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ArticleTag::ArticleId).big_integer().not_null())
+                    .col(ColumnDef::new(ArticleTag::TagId).big_integer().not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(ArticleTag::ArticleId)
+                            .col(ArticleTag::TagId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_article_tag_article")
+                            .from(ArticleTag::Table, ArticleTag::ArticleId)
+                            .to(Article::Table, Article::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_article_tag_tag")
+                            .from(ArticleTag::Table, ArticleTag::TagId)
+                            .to(Tag::Table, Tag::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 按标签查文章时走 tag_id 索引
+        manager
+            .create_index(
+                Index::create()
+                    .table(ArticleTag::Table)
+                    .name("idx_article_tag_tag_id")
+                    .col(ArticleTag::TagId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    // 回滚删除表
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ArticleTag::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Tag::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Tag {
+    Table,
+    Id,
+    Name,
+    Slug,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ArticleTag {
+    Table,
+    ArticleId,
+    TagId,
+}
+
+#[derive(DeriveIden)]
+enum Article {
+    Table,
+    Id,
+}
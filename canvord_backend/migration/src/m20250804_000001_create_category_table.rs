@@ -0,0 +1,209 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::{ConnectionTrait, Statement};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 创建分类表，并把文章上的分类字符串迁移为外键
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Category::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Category::Id)
+                            .big_integer()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Category::ParentId).big_integer().null())
+                    .col(ColumnDef::new(Category::Name).string().not_null())
+                    .col(ColumnDef::new(Category::Slug).string().not_null().unique_key())
+                    .col(ColumnDef::new(Category::Description).text().not_null())
+                    .col(ColumnDef::new(Category::CreatedAt).date_time().not_null())
+                    .col(ColumnDef::new(Category::LastUpdate).date_time().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_category_parent")
+                            .from(Category::Table, Category::ParentId)
+                            .to(Category::Table, Category::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 已有的分类字符串逐个转为分类行
+        let db = manager.get_connection();
+        let backend = manager.get_database_backend();
+        let rows = db
+            .query_all(Statement::from_string(
+                backend,
+                "SELECT DISTINCT category FROM article WHERE category <> ''",
+            ))
+            .await?;
+
+        let mut slugs: Vec<String> = Vec::with_capacity(rows.len());
+        for row in rows {
+            let name: String = row.try_get("", "category")?;
+            let base = match slugify(&name) {
+                s if s.is_empty() => "category".to_owned(),
+                s => s,
+            };
+            let mut slug = base.clone();
+            let mut n = 1;
+            while slugs.contains(&slug) {
+                n += 1;
+                slug = format!("{base}-{n}");
+            }
+            slugs.push(slug.clone());
+
+            let insert = Query::insert()
+                .into_table(Category::Table)
+                .columns([
+                    Category::Name,
+                    Category::Slug,
+                    Category::Description,
+                    Category::CreatedAt,
+                    Category::LastUpdate,
+                ])
+                .values_panic([
+                    name.into(),
+                    slug.into(),
+                    "".into(),
+                    Expr::current_timestamp().into(),
+                    Expr::current_timestamp().into(),
+                ])
+                .to_owned();
+            manager.exec_stmt(insert).await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Article::Table)
+                    .add_column(ColumnDef::new(Article::CategoryId).big_integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared(
+            "UPDATE article a JOIN category c ON a.category = c.name SET a.category_id = c.id",
+        )
+        .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_article_category")
+                    .from(Article::Table, Article::CategoryId)
+                    .to(Category::Table, Category::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .table(Article::Table)
+                    .name("idx_article_category")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Article::Table)
+                    .drop_column(Article::Category)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    // 回滚时把分类名称写回文章
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Article::Table)
+                    .add_column(ColumnDef::new(Article::Category).string().not_null().default(""))
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "UPDATE article a JOIN category c ON a.category_id = c.id SET a.category = c.name",
+        )
+        .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(Article::Table)
+                    .name("idx_article_category")
+                    .col(Article::Category)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .table(Article::Table)
+                    .name("fk_article_category")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Article::Table)
+                    .drop_column(Article::CategoryId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Category::Table).to_owned())
+            .await
+    }
+}
+
+/// 迁移时 `render::slugify` 的副本。已执行的迁移不能随之后的规则变化，因此保留在这里而不是直接调用
+fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_owned()
+}
+
+#[derive(DeriveIden)]
+enum Category {
+    Table,
+    Id,
+    ParentId,
+    Name,
+    Slug,
+    Description,
+    CreatedAt,
+    LastUpdate,
+}
+
+#[derive(DeriveIden)]
+enum Article {
+    Table,
+    Category,
+    CategoryId,
+}
@@ -0,0 +1,63 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 创建媒体文件表，文件本身由存储后端保存
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Media::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Media::Id)
+                            .big_integer()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Media::OwnerId).big_integer().null())
+                    .col(ColumnDef::new(Media::StorageKey).string().not_null().unique_key())
+                    .col(ColumnDef::new(Media::FileName).string().not_null())
+                    .col(ColumnDef::new(Media::ContentType).string().not_null())
+                    .col(ColumnDef::new(Media::Size).big_integer().not_null())
+                    .col(ColumnDef::new(Media::CreatedAt).date_time().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_media_owner")
+                            .from(Media::Table, Media::OwnerId)
+                            .to(User::Table, User::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    // 回滚删除表
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Media::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Media {
+    Table,
+    Id,
+    OwnerId,
+    StorageKey,
+    FileName,
+    ContentType,
+    Size,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum User {
+    Table,
+    Id,
+}
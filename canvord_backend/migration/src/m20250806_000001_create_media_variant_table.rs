@@ -0,0 +1,81 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 记录图片尺寸，并为每张图片保存多个宽度的 WebP 版本
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Media::Table)
+                    .add_column(ColumnDef::new(Media::Width).integer().null())
+                    .add_column(ColumnDef::new(Media::Height).integer().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(MediaVariant::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MediaVariant::Id)
+                            .big_integer()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(MediaVariant::MediaId).big_integer().not_null())
+                    .col(ColumnDef::new(MediaVariant::Width).integer().not_null())
+                    .col(ColumnDef::new(MediaVariant::StorageKey).string().not_null().unique_key())
+                    .col(ColumnDef::new(MediaVariant::Size).big_integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_media_variant_media")
+                            .from(MediaVariant::Table, MediaVariant::MediaId)
+                            .to(Media::Table, Media::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    // 回滚删除变体表和尺寸列
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MediaVariant::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Media::Table)
+                    .drop_column(Media::Width)
+                    .drop_column(Media::Height)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Media {
+    Table,
+    Id,
+    Width,
+    Height,
+}
+
+#[derive(DeriveIden)]
+enum MediaVariant {
+    Table,
+    Id,
+    MediaId,
+    Width,
+    StorageKey,
+    Size,
+}
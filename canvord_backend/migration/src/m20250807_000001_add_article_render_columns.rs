@@ -0,0 +1,49 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 保存渲染后的正文、目录和字数，旧文章的 render_version 为 0，启动时会重新渲染
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Article::Table)
+                    .add_column(ColumnDef::new(Article::ContentHtml).custom("MEDIUMTEXT").null())
+                    .add_column(ColumnDef::new(Article::Toc).json().null())
+                    .add_column(ColumnDef::new(Article::WordCount).integer().not_null().default(0))
+                    .add_column(ColumnDef::new(Article::ReadingMinutes).integer().not_null().default(0))
+                    .add_column(ColumnDef::new(Article::RenderVersion).integer().not_null().default(0))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    // 回滚删除新增列
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Article::Table)
+                    .drop_column(Article::ContentHtml)
+                    .drop_column(Article::Toc)
+                    .drop_column(Article::WordCount)
+                    .drop_column(Article::ReadingMinutes)
+                    .drop_column(Article::RenderVersion)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Article {
+    Table,
+    ContentHtml,
+    Toc,
+    WordCount,
+    ReadingMinutes,
+    RenderVersion,
+}
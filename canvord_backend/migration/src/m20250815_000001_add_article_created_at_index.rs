@@ -0,0 +1,42 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 按游标翻页时按状态过滤后按 (created_at, id) 排序和比较
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_article_status_created_at")
+                    .table(Article::Table)
+                    .col(Article::Status)
+                    .col(Article::CreatedAt)
+                    .col(Article::Id)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    // 回滚删除索引
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_article_status_created_at")
+                    .table(Article::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Article {
+    Table,
+    Id,
+    Status,
+    CreatedAt,
+}
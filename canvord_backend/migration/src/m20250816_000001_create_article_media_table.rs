@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 记录文章正文引用的媒体文件，并按正文中出现的文件 key 填入已有文章的引用
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ArticleMedia::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(ArticleMedia::ArticleId).big_integer().not_null())
+                    .col(ColumnDef::new(ArticleMedia::MediaId).big_integer().not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(ArticleMedia::ArticleId)
+                            .col(ArticleMedia::MediaId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_article_media_article")
+                            .from(ArticleMedia::Table, ArticleMedia::ArticleId)
+                            .to(Article::Table, Article::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_article_media_media")
+                            .from(ArticleMedia::Table, ArticleMedia::MediaId)
+                            .to(Media::Table, Media::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 统计引用数时走 media_id 索引
+        manager
+            .create_index(
+                Index::create()
+                    .table(ArticleMedia::Table)
+                    .name("idx_article_media_media_id")
+                    .col(ArticleMedia::MediaId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 只在迁移时整表扫描一次，之后由保存文章时维护
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "INSERT INTO article_media (article_id, media_id) \
+                 SELECT article.id, media.id FROM article JOIN media \
+                 ON article.content_md LIKE CONCAT('%', media.storage_key, '%')",
+            )
+            .await
+            .map(|_| ())
+    }
+
+    // 回滚删除表
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ArticleMedia::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ArticleMedia {
+    Table,
+    ArticleId,
+    MediaId,
+}
+
+#[derive(DeriveIden)]
+enum Article {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Media {
+    Table,
+    Id,
+}
@@ -0,0 +1,139 @@
+use crate::html::escape;
+use crate::meta::meta_tags;
+use crate::{ArticlePage, PageMeta, Site};
+
+/// 导出页面的模板有变化时递增，已导出的文章页会全部重新生成
+pub const EXPORT_VERSION: u32 = 1;
+
+/// 导出站点使用的样式表，保存为 `/style.css`
+pub const STYLE_CSS: &str = include_str!("export.css");
+
+/// 文章列表页，第一页位于 `base`，之后为 `{base}page/{n}/`
+#[derive(Debug, Clone)]
+pub struct Listing<'a> {
+    pub heading: &'a str,
+    pub description: &'a str,
+    /// 以 `/` 开头和结尾，如 `/`、`/category/rust/`
+    pub base: &'a str,
+    pub articles: &'a [ArticlePage],
+    /// 从 1 开始
+    pub page: usize,
+    pub pages: usize,
+}
+
+/// 列表第 `n` 页相对站点根目录的路径
+pub fn page_path(base: &str, n: usize) -> String {
+    if n <= 1 { base.to_owned() } else { format!("{base}page/{n}/") }
+}
+
+pub fn article_path(slug: &str) -> String {
+    format!("/article/{slug}/")
+}
+
+/// 不依赖访客客户端的独立页面
+pub fn export_article(site: &Site, meta: &PageMeta, page: &ArticlePage) -> String {
+    let mut main = format!(r#"<article><h1>{}</h1>"#, escape(&page.title));
+    main.push_str(&format!(
+        r#"<p class="meta"><time datetime="{}">{}</time>{}　约 {} 字，阅读需 {} 分钟</p>"#,
+        page.last_update.format("%Y-%m-%dT%H:%M:%SZ"),
+        page.last_update.format("%Y-%m-%d"),
+        page.category.as_ref()
+            .map(|c| format!(r#"　分类：<a href="/category/{}/">{}</a>"#, escape(&c.slug), escape(&c.name)))
+            .unwrap_or_default(),
+        page.word_count,
+        page.reading_minutes,
+    ));
+
+    if !page.tags.is_empty() {
+        main.push_str(r#"<p class="tags">"#);
+        for tag in &page.tags {
+            main.push_str(&format!(r#"<a href="/tag/{}/">#{}</a>"#, escape(&tag.slug), escape(&tag.name)));
+        }
+        main.push_str("</p>");
+    }
+
+    // 只有一个标题时目录没有意义
+    if page.toc.len() > 1 {
+        main.push_str(r#"<nav class="toc"><ul>"#);
+        for (level, id, text) in &page.toc {
+            main.push_str(&format!(
+                r##"<li style="padding-left: {}rem"><a href="#{}">{}</a></li>"##,
+                level.saturating_sub(1) as f32 * 0.75,
+                escape(id),
+                escape(text),
+            ));
+        }
+        main.push_str("</ul></nav>");
+    }
+
+    main.push_str(r#"<div class="prose">"#);
+    main.push_str(&page.content_html);
+    main.push_str("</div></article>");
+    layout(site, meta, &main)
+}
+
+pub fn export_listing(site: &Site, meta: &PageMeta, listing: &Listing) -> String {
+    let mut main = format!("<h1>{}</h1>", escape(listing.heading));
+    if !listing.description.is_empty() {
+        main.push_str(&format!(r#"<p class="meta">{}</p>"#, escape(listing.description)));
+    }
+
+    for article in listing.articles {
+        main.push_str(&format!(
+            r#"<section class="summary"><h2><a href="{}">{}</a></h2><p class="meta">{}{}</p><p>{}</p></section>"#,
+            escape(&article_path(&article.slug)),
+            escape(&article.title),
+            article.created_at.format("%Y-%m-%d"),
+            article.category.as_ref().map(|c| format!("　{}", escape(&c.name))).unwrap_or_default(),
+            escape(&article.description),
+        ));
+    }
+
+    if listing.pages > 1 {
+        let link = |n: usize, text: &str| format!(r#"<a href="{}">{text}</a>"#, escape(&page_path(listing.base, n)));
+        main.push_str(&format!(
+            r#"<nav class="pagination"><span>{}</span><span class="meta">{} / {}</span><span>{}</span></nav>"#,
+            if listing.page > 1 { link(listing.page - 1, "← 上一页") } else { String::new() },
+            listing.page,
+            listing.pages,
+            if listing.page < listing.pages { link(listing.page + 1, "下一页 →") } else { String::new() },
+        ));
+    }
+
+    layout(site, meta, &main)
+}
+
+fn layout(site: &Site, meta: &PageMeta, main: &str) -> String {
+    // 只有包含 Mermaid 代码块的页面才加载 mermaid.js
+    let mermaid = if main.contains(r#"<pre class="mermaid">"#) {
+        r#"
+<script type="module">
+    import mermaid from "https://cdn.jsdelivr.net/npm/mermaid@11/dist/mermaid.esm.min.mjs";
+    mermaid.initialize({ startOnLoad: true, securityLevel: "strict" });
+</script>"#
+    } else {
+        ""
+    };
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="zh-CN">
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    {meta}
+    <link rel="stylesheet" href="/style.css">
+    <link rel="stylesheet" href="/highlight.css">
+    <link rel="alternate" type="application/rss+xml" title="{title}" href="/feed.xml">
+    <link rel="alternate" type="application/atom+xml" title="{title}" href="/atom.xml">
+</head>
+<body>
+<header class="site-header"><a class="site-title" href="/">{title}</a><nav><a href="/feed.xml">RSS</a><a href="/atom.xml">Atom</a></nav></header>
+<main>{main}</main>{mermaid}
+</body>
+</html>
+"#,
+        meta = meta_tags(site, meta),
+        title = escape(&site.title),
+    )
+}
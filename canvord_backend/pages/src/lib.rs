@@ -0,0 +1,101 @@
+mod export;
+mod html;
+mod meta;
+mod output;
+mod prerender;
+
+use chrono::NaiveDateTime;
+
+pub use crate::export::{article_path, export_article, export_listing, page_path, Listing, EXPORT_VERSION, STYLE_CSS};
+pub use crate::meta::meta_tags;
+pub use crate::output::{is_path_segment, remove_stale, write_articles};
+pub use crate::prerender::{article_body, home_body, prerender, PRERENDER_ID};
+
+/// 站点信息，`url` 不以 `/` 结尾
+#[derive(Debug, Clone)]
+pub struct Site {
+    pub title: String,
+    pub description: String,
+    pub url: String,
+    /// 分享卡片使用的图片地址
+    pub image: Option<String>,
+}
+
+/// 页面的 `<head>` 信息，用于 `<title>`、Open Graph 和 Twitter 卡片
+#[derive(Debug, Clone)]
+pub struct PageMeta {
+    pub title: String,
+    pub description: String,
+    /// 页面的绝对地址，同时作为 canonical 链接
+    pub url: String,
+    /// 文章页额外输出 `article:*` 属性
+    pub article: Option<ArticleMeta>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ArticleMeta {
+    /// 数据库中的时间均为 UTC
+    pub published: NaiveDateTime,
+    pub modified: NaiveDateTime,
+    pub section: Option<String>,
+    pub tags: Vec<String>,
+}
+
+/// 分类或标签
+#[derive(Debug, Clone)]
+pub struct Term {
+    pub name: String,
+    pub slug: String,
+}
+
+/// 预渲染文章页需要的内容，字段与访客页面展示的一致
+#[derive(Debug, Clone)]
+pub struct ArticlePage {
+    pub title: String,
+    pub description: String,
+    pub slug: String,
+    pub category: Option<Term>,
+    pub tags: Vec<Term>,
+    /// 已经过白名单过滤的正文
+    pub content_html: String,
+    /// (层级, 锚点, 标题)
+    pub toc: Vec<(u8, String, String)>,
+    pub word_count: i32,
+    pub reading_minutes: i32,
+    pub created_at: NaiveDateTime,
+    pub last_update: NaiveDateTime,
+}
+
+/// 首页列出的一篇文章，只需要链接和摘要，不必读出正文
+#[derive(Debug, Clone)]
+pub struct ArticleLink {
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+}
+
+impl From<&ArticlePage> for ArticleLink {
+    fn from(page: &ArticlePage) -> Self {
+        Self { slug: page.slug.clone(), title: page.title.clone(), description: page.description.clone() }
+    }
+}
+
+impl ArticlePage {
+    pub fn url(&self, site: &Site) -> String {
+        format!("{}/article/{}", site.url, self.slug)
+    }
+
+    pub fn meta(&self, site: &Site) -> PageMeta {
+        PageMeta {
+            title: format!("{} - {}", self.title, site.title),
+            description: self.description.clone(),
+            url: self.url(site),
+            article: Some(ArticleMeta {
+                published: self.created_at,
+                modified: self.last_update,
+                section: self.category.as_ref().map(|c| c.name.clone()),
+                tags: self.tags.iter().map(|t| t.name.clone()).collect(),
+            }),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use chrono::{NaiveDateTime, SecondsFormat};
+
+use crate::html::escape;
+use crate::{PageMeta, Site};
+
+/// 生成 `<title>`、描述、canonical 链接以及 Open Graph 和 Twitter 卡片标签
+pub fn meta_tags(site: &Site, page: &PageMeta) -> String {
+    let mut tags = vec![
+        format!("<title>{}</title>", escape(&page.title)),
+        format!(r#"<meta name="description" content="{}">"#, escape(&page.description)),
+        format!(r#"<link rel="canonical" href="{}">"#, escape(&page.url)),
+    ];
+
+    let mut property = |name: &str, content: &str| {
+        tags.push(format!(r#"<meta property="{name}" content="{}">"#, escape(content)));
+    };
+    property("og:site_name", &site.title);
+    property("og:title", &page.title);
+    property("og:description", &page.description);
+    property("og:url", &page.url);
+    property("og:type", if page.article.is_some() { "article" } else { "website" });
+    if let Some(image) = &site.image {
+        property("og:image", image);
+    }
+    if let Some(article) = &page.article {
+        property("article:published_time", &iso8601(article.published));
+        property("article:modified_time", &iso8601(article.modified));
+        if let Some(section) = &article.section {
+            property("article:section", section);
+        }
+        for tag in &article.tags {
+            property("article:tag", tag);
+        }
+    }
+
+    let card = if site.image.is_some() { "summary_large_image" } else { "summary" };
+    tags.push(format!(r#"<meta name="twitter:card" content="{card}">"#));
+    tags.push(format!(r#"<meta name="twitter:title" content="{}">"#, escape(&page.title)));
+    tags.push(format!(r#"<meta name="twitter:description" content="{}">"#, escape(&page.description)));
+    if let Some(image) = &site.image {
+        tags.push(format!(r#"<meta name="twitter:image" content="{}">"#, escape(image)));
+    }
+
+    tags.join("\n    ")
+}
+
+fn iso8601(time: NaiveDateTime) -> String {
+    time.and_utc().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
@@ -0,0 +1,56 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Component, Path};
+
+use crate::ArticlePage;
+
+/// slug 会作为目录名，不能包含路径分隔符或 `..`
+pub fn is_path_segment(slug: &str) -> bool {
+    let mut components = Path::new(slug).components();
+    matches!((components.next(), components.next()), (Some(Component::Normal(_)), None)) && !slug.contains(['/', '\\'])
+}
+
+/// 预渲染和静态导出共用：将每篇文章写入 `article/{slug}/index.html`，再删除不在 `articles` 中的文章目录。
+///
+/// `render` 返回 `None` 时保留已有的页面；`articles` 中的 slug 应已通过 [`is_path_segment`] 检查。
+/// 返回 (写入的页数, 删除的目录数)
+pub fn write_articles(
+    out_dir: &Path,
+    articles: &[ArticlePage],
+    mut render: impl FnMut(&ArticlePage, &Path) -> Option<String>,
+) -> io::Result<(usize, usize)> {
+    let article_dir = out_dir.join("article");
+    fs::create_dir_all(&article_dir)?;
+
+    let mut written = 0;
+    for article in articles {
+        let dir = article_dir.join(&article.slug);
+        if let Some(html) = render(article, &dir) {
+            fs::create_dir_all(&dir)?;
+            fs::write(dir.join("index.html"), html)?;
+            written += 1;
+        }
+    }
+
+    // 隐藏、撤回或删除的文章不能继续被访问
+    let slugs: HashSet<&str> = articles.iter().map(|a| a.slug.as_str()).collect();
+    let removed = remove_stale(&article_dir, &slugs)?;
+    Ok((written, removed))
+}
+
+/// 删除 `dir` 下不在 `keep` 中的子目录，返回删除的个数
+pub fn remove_stale(dir: &Path, keep: &HashSet<&str>) -> io::Result<usize> {
+    if !dir.is_dir() {
+        return Ok(0);
+    }
+    let mut removed = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() && !keep.contains(entry.file_name().to_string_lossy().as_ref()) {
+            fs::remove_dir_all(entry.path())?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
@@ -0,0 +1,103 @@
+use crate::html::escape;
+use crate::meta::meta_tags;
+use crate::{ArticleLink, ArticlePage, PageMeta, Site};
+
+/// 预渲染内容所在元素的 id，访客客户端启动时会移除该元素
+pub const PRERENDER_ID: &str = "prerender";
+
+/// 以客户端构建出的 `index.html` 为模板，替换 `<title>` 并写入页面信息和预渲染内容；
+/// 没有 JavaScript 的读者和爬虫直接看到内容，浏览器加载客户端后照常渲染
+pub fn prerender(template: &str, site: &Site, page: &PageMeta, body: &str) -> String {
+    let mut html = strip_title(template);
+
+    let head = html.find("</head>").unwrap_or(0);
+    html.insert_str(head, &format!("    {}\n", meta_tags(site, page)));
+
+    let body_start = html
+        .find("<body")
+        .and_then(|start| html[start..].find('>').map(|end| start + end + 1))
+        .unwrap_or(html.len());
+    html.insert_str(body_start, &format!(r#"<div id="{PRERENDER_ID}">{body}</div>"#));
+    html
+}
+
+fn strip_title(template: &str) -> String {
+    match (template.find("<title>"), template.find("</title>")) {
+        (Some(start), Some(end)) if start < end => {
+            let line_start = template[..start].rfind('\n').map_or(start, |i| i + 1);
+            let line_end = template[end..].find('\n').map_or(template.len(), |i| end + i + 1);
+            // 标签独占一行时连同缩进和换行一起去掉
+            if template[line_start..start].trim().is_empty() && template[end + "</title>".len()..line_end].trim().is_empty() {
+                format!("{}{}", &template[..line_start], &template[line_end..])
+            } else {
+                format!("{}{}", &template[..start], &template[end + "</title>".len()..])
+            }
+        }
+        _ => template.to_owned(),
+    }
+}
+
+/// 与访客客户端的文章页结构一致，正文直接使用保存时渲染好的 HTML
+pub fn article_body(page: &ArticlePage) -> String {
+    let mut html = String::from(r#"<div class="min-h-screen bg-[url('/bg-768.webp')] md:bg-[url('/bg.webp')] bg-cover bg-fixed bg-center bg-no-repeat text-white flex justify-center px-4 py-8"><article class="bg-white text-neutral-900 max-w-3xl w-full p-8 rounded-xl shadow-lg bg-opacity-90">"#);
+
+    html.push_str(&format!(r#"<h1 class="text-3xl font-bold mb-4">{}</h1>"#, escape(&page.title)));
+    html.push_str(&format!(
+        r#"<p class="text-xs text-neutral-400 mb-4 italic">最后更新：<time datetime="{}">{}</time>{}　约 {} 字，阅读需 {} 分钟</p>"#,
+        page.last_update.format("%Y-%m-%dT%H:%M:%SZ"),
+        page.last_update.format("%Y-%m-%d %H:%M:%S"),
+        page.category.as_ref().map(|c| format!("　分类：{}", escape(&c.name))).unwrap_or_default(),
+        page.word_count,
+        page.reading_minutes,
+    ));
+
+    if !page.tags.is_empty() {
+        html.push_str(r#"<div class="flex flex-wrap gap-2 mb-4">"#);
+        for tag in &page.tags {
+            html.push_str(&format!(r#"<span class="text-xs bg-neutral-200 text-neutral-700 px-2 py-0.5 rounded">#{}</span>"#, escape(&tag.name)));
+        }
+        html.push_str("</div>");
+    }
+
+    // 只有一个标题时目录没有意义
+    if page.toc.len() > 1 {
+        html.push_str(r#"<nav class="text-sm bg-neutral-100 rounded p-4 mb-6"><p class="font-semibold mb-2">目录</p><ul>"#);
+        for (level, id, text) in &page.toc {
+            html.push_str(&format!(
+                r##"<li style="padding-left: {}rem"><a class="text-neutral-600 hover:text-neutral-900" href="#{}">{}</a></li>"##,
+                level.saturating_sub(1) as f32 * 0.75,
+                escape(id),
+                escape(text),
+            ));
+        }
+        html.push_str("</ul></nav>");
+    }
+
+    html.push_str(r#"<div class="prose max-w-none">"#);
+    html.push_str(&page.content_html);
+    html.push_str("</div></article></div>");
+    html
+}
+
+/// 首页只列出文章链接，供爬虫发现文章页
+pub fn home_body(site: &Site, articles: &[ArticleLink]) -> String {
+    let mut html = format!(
+        r#"<main class="max-w-3xl mx-auto p-8"><h1 class="text-3xl font-bold mb-4">{}</h1>"#,
+        escape(&site.title),
+    );
+    if !site.description.is_empty() {
+        html.push_str(&format!(r#"<p class="text-neutral-500 mb-6">{}</p>"#, escape(&site.description)));
+    }
+
+    html.push_str("<ul>");
+    for article in articles {
+        html.push_str(&format!(
+            r#"<li class="mb-4"><a class="text-xl font-semibold" href="/article/{}">{}</a><p class="text-sm text-neutral-500">{}</p></li>"#,
+            escape(&article.slug),
+            escape(&article.title),
+            escape(&article.description),
+        ));
+    }
+    html.push_str("</ul></main>");
+    html
+}
@@ -0,0 +1,67 @@
+use chrono::NaiveDate;
+use pages::{export_article, export_listing, page_path, ArticlePage, Listing, PageMeta, Site, Term};
+
+fn site() -> Site {
+    Site {
+        title: "Canvord".into(),
+        description: String::new(),
+        url: "https://example.com".into(),
+        image: None,
+    }
+}
+
+fn page(slug: &str, content_html: &str) -> ArticlePage {
+    let at = NaiveDate::from_ymd_opt(2025, 8, 9).unwrap().and_hms_opt(12, 0, 0).unwrap();
+    ArticlePage {
+        title: format!("<{slug}>"),
+        description: "desc".into(),
+        slug: slug.into(),
+        category: Some(Term { name: "后端".into(), slug: "backend".into() }),
+        tags: vec![Term { name: "rust".into(), slug: "rust".into() }],
+        content_html: content_html.into(),
+        toc: Vec::new(),
+        word_count: 2,
+        reading_minutes: 1,
+        created_at: at,
+        last_update: at,
+    }
+}
+
+fn meta(site: &Site) -> PageMeta {
+    PageMeta { title: site.title.clone(), description: String::new(), url: format!("{}/", site.url), article: None }
+}
+
+#[test]
+fn article_links_terms_and_loads_mermaid_only_when_needed() {
+    let site = site();
+    let plain = page("a", "<p>x</p>");
+    let html = export_article(&site, &plain.meta(&site), &plain);
+
+    assert!(html.contains("<h1>&lt;a&gt;</h1>"), "{html}");
+    assert!(html.contains(r#"<a href="/category/backend/">后端</a>"#), "{html}");
+    assert!(html.contains(r#"<a href="/tag/rust/">#rust</a>"#), "{html}");
+    assert!(html.contains(r#"<link rel="canonical" href="https://example.com/article/a">"#), "{html}");
+    assert!(!html.contains("mermaid"), "{html}");
+
+    let diagram = page("b", r#"<pre class="mermaid">graph TD</pre>"#);
+    assert!(export_article(&site, &diagram.meta(&site), &diagram).contains("mermaid.esm.min.mjs"));
+}
+
+#[test]
+fn listing_paginates() {
+    let site = site();
+    let articles = [page("a", ""), page("b", "")];
+    let listing = |n| Listing { heading: "分类", description: "", base: "/category/backend/", articles: &articles, page: n, pages: 3 };
+
+    let first = export_listing(&site, &meta(&site), &listing(1));
+    assert!(first.contains(r#"<a href="/article/a/">&lt;a&gt;</a>"#), "{first}");
+    assert!(first.contains(r#"<a href="/category/backend/page/2/">下一页 →</a>"#), "{first}");
+    assert!(!first.contains("上一页"), "{first}");
+
+    let middle = export_listing(&site, &meta(&site), &listing(2));
+    assert!(middle.contains(r#"<a href="/category/backend/">← 上一页</a>"#), "{middle}");
+    assert!(middle.contains(r#"<a href="/category/backend/page/3/">下一页 →</a>"#), "{middle}");
+
+    assert_eq!(page_path("/", 1), "/");
+    assert_eq!(page_path("/", 4), "/page/4/");
+}
@@ -0,0 +1,50 @@
+use std::fs;
+
+use chrono::NaiveDate;
+use pages::{is_path_segment, write_articles, ArticlePage};
+
+fn page(slug: &str) -> ArticlePage {
+    let at = NaiveDate::from_ymd_opt(2025, 8, 9).unwrap().and_hms_opt(12, 0, 0).unwrap();
+    ArticlePage {
+        title: slug.into(),
+        description: String::new(),
+        slug: slug.into(),
+        category: None,
+        tags: Vec::new(),
+        content_html: String::new(),
+        toc: Vec::new(),
+        word_count: 0,
+        reading_minutes: 0,
+        created_at: at,
+        last_update: at,
+    }
+}
+
+#[test]
+fn unsafe_slugs_are_rejected() {
+    assert!(is_path_segment("hello-world"));
+    for slug in ["", ".", "..", "a/b", "a\\b", "/etc"] {
+        assert!(!is_path_segment(slug), "{slug}");
+    }
+}
+
+#[test]
+fn writes_changed_pages_and_removes_stale_ones() {
+    let dir = tempfile::tempdir().unwrap();
+    let out = dir.path();
+    fs::create_dir_all(out.join("article/hidden")).unwrap();
+    fs::create_dir_all(out.join("article/kept")).unwrap();
+    fs::write(out.join("article/kept/index.html"), "old").unwrap();
+
+    let articles = [page("new"), page("kept")];
+    let (written, removed) = write_articles(out, &articles, |article, dir| {
+        assert_eq!(dir, out.join("article").join(&article.slug));
+        (article.slug != "kept").then(|| format!("<h1>{}</h1>", article.title))
+    })
+    .unwrap();
+
+    assert_eq!((written, removed), (1, 1));
+    assert_eq!(fs::read_to_string(out.join("article/new/index.html")).unwrap(), "<h1>new</h1>");
+    assert_eq!(fs::read_to_string(out.join("article/kept/index.html")).unwrap(), "old");
+    assert!(!out.join("article/hidden").exists());
+}
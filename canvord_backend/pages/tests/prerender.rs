@@ -0,0 +1,82 @@
+use chrono::NaiveDate;
+use pages::{article_body, home_body, prerender, ArticleLink, ArticlePage, Site, Term};
+
+const TEMPLATE: &str = r#"<!DOCTYPE html>
+<html lang="cn">
+<head>
+    <link rel="icon" href="/logo.svg" type="image/svg">
+    <title>Canvord</title>
+<script type="module">init('/canvord_client.js')</script></head>
+<body class="dark">
+    <script>console.log(1)</script>
+</body>
+</html>"#;
+
+fn site() -> Site {
+    Site {
+        title: "Canvord".into(),
+        description: "笔记".into(),
+        url: "https://example.com".into(),
+        image: Some("https://example.com/cover.png".into()),
+    }
+}
+
+fn term(name: &str, slug: &str) -> Term {
+    Term { name: name.into(), slug: slug.into() }
+}
+
+fn page() -> ArticlePage {
+    let at = NaiveDate::from_ymd_opt(2025, 8, 9).unwrap().and_hms_opt(12, 0, 0).unwrap();
+    ArticlePage {
+        title: r#"A "quoted" <title>"#.into(),
+        description: "desc & more".into(),
+        slug: "hello".into(),
+        category: Some(term("后端", "backend")),
+        tags: vec![term("rust", "rust"), term("web", "web")],
+        content_html: "<p>正文</p>".into(),
+        toc: vec![(1, "a".into(), "A".into()), (2, "b".into(), "B".into())],
+        word_count: 2,
+        reading_minutes: 1,
+        created_at: at,
+        last_update: at,
+    }
+}
+
+#[test]
+fn article_page_has_meta_and_content() {
+    let site = site();
+    let page = page();
+    let html = prerender(TEMPLATE, &site, &page.meta(&site), &article_body(&page));
+
+    assert_eq!(html.matches("<title>").count(), 1, "{html}");
+    assert!(html.contains("<title>A &quot;quoted&quot; &lt;title&gt; - Canvord</title>"), "{html}");
+    assert!(html.contains(r#"<meta property="og:type" content="article">"#), "{html}");
+    assert!(html.contains(r#"<meta property="og:url" content="https://example.com/article/hello">"#), "{html}");
+    assert!(html.contains(r#"<meta property="article:published_time" content="2025-08-09T12:00:00Z">"#), "{html}");
+    assert_eq!(html.matches(r#"<meta property="article:tag""#).count(), 2, "{html}");
+    assert!(html.contains(r#"<meta name="twitter:card" content="summary_large_image">"#), "{html}");
+    assert!(html.contains(r#"<meta name="description" content="desc &amp; more">"#), "{html}");
+
+    // 预渲染内容紧跟在 <body> 之后，模板中的脚本保持不变
+    assert!(html.contains(r#"<body class="dark"><div id="prerender">"#), "{html}");
+    assert!(html.contains("<p>正文</p>"), "{html}");
+    assert!(html.contains(r##"href="#b""##), "{html}");
+    assert!(html.contains("<script>console.log(1)</script>"), "{html}");
+    assert!(html.find("og:title").unwrap() < html.find("</head>").unwrap());
+}
+
+#[test]
+fn home_page_links_articles() {
+    let site = site();
+    let meta = pages::PageMeta {
+        title: site.title.clone(),
+        description: site.description.clone(),
+        url: format!("{}/", site.url),
+        article: None,
+    };
+    let html = prerender(TEMPLATE, &site, &meta, &home_body(&site, &[ArticleLink::from(&page())]));
+
+    assert!(html.contains(r#"<meta property="og:type" content="website">"#), "{html}");
+    assert!(html.contains(r#"href="/article/hello""#), "{html}");
+    assert!(!html.contains("article:published_time"), "{html}");
+}
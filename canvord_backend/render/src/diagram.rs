@@ -0,0 +1,37 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use layout::backends::svg::SVGWriter;
+use layout::gv::{DotParser, GraphBuilder};
+
+/// Graphviz 代码块渲染为内联 SVG，`n` 用于区分同一页面中的多张图；无法解析或布局时返回 `None`
+pub(crate) fn graphviz(source: &str, n: usize) -> Option<String> {
+    let graph = DotParser::new(source).process().ok()?;
+    let mut builder = GraphBuilder::new();
+    builder.visit_graph(&graph);
+    let mut visual = builder.get();
+    // 空图布局时会 panic，其他无法布局的输入同样按普通代码块输出
+    if visual.num_nodes() == 0 {
+        return None;
+    }
+    let svg = catch_unwind(AssertUnwindSafe(|| {
+        let mut writer = SVGWriter::new();
+        visual.do_it(false, false, false, &mut writer);
+        writer.finalize()
+    }))
+    .ok()?;
+
+    // 去掉 XML 声明，连线路径和裁剪区域的 id 加上编号
+    let svg = &svg[svg.find("<svg")?..];
+    let prefix = format!("dot{n}-");
+    let svg = svg
+        .replace(r#"id="arrow"#, &format!(r#"id="{prefix}arrow"#))
+        .replace(r##"href="#arrow"##, &format!(r##"href="#{prefix}arrow"##))
+        .replace(r#"id="C"#, &format!(r#"id="{prefix}C"#))
+        .replace("url(#C", &format!("url(#{prefix}C"));
+    Some(format!(r#"<div class="diagram">{svg}</div>"#))
+}
+
+/// Mermaid 没有 Rust 实现，保留源码由页面加载 mermaid.js 后渲染
+pub(crate) fn mermaid(source: &str) -> String {
+    format!(r#"<pre class="mermaid">{}</pre>"#, crate::escape_attr(source))
+}
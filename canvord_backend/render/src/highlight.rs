@@ -0,0 +1,88 @@
+use std::sync::LazyLock;
+
+use syntect::highlighting::ThemeSet;
+use syntect::html::{css_for_theme_with_class_style, ClassStyle, ClassedHTMLGenerator};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+/// 生成的 span 类名统一加前缀，避免和 Tailwind 等样式冲突；主题背景色对应 `hl-code`，即代码块的 `pre`
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "hl-" };
+
+const LIGHT_THEME: &str = "InspiredGitHub";
+const DARK_THEME: &str = "base16-ocean.dark";
+
+/// 支持高亮的语言：(代码块语言标记, syntect 语法名)，未列出的语言按纯文本输出
+const LANGUAGES: &[(&str, &str)] = &[
+    ("rust", "Rust"),
+    ("rs", "Rust"),
+    ("python", "Python"),
+    ("py", "Python"),
+    ("javascript", "JavaScript"),
+    ("js", "JavaScript"),
+    ("json", "JSON"),
+    ("yaml", "YAML"),
+    ("yml", "YAML"),
+    ("bash", "Bourne Again Shell (bash)"),
+    ("sh", "Bourne Again Shell (bash)"),
+    ("shell", "Bourne Again Shell (bash)"),
+    ("c", "C"),
+    ("cpp", "C++"),
+    ("c++", "C++"),
+    ("csharp", "C#"),
+    ("cs", "C#"),
+    ("go", "Go"),
+    ("java", "Java"),
+    ("sql", "SQL"),
+    ("html", "HTML"),
+    ("xml", "XML"),
+    ("css", "CSS"),
+    ("diff", "Diff"),
+    ("dot", "Graphviz (DOT)"),
+    ("graphviz", "Graphviz (DOT)"),
+    ("makefile", "Makefile"),
+    ("markdown", "Markdown"),
+    ("md", "Markdown"),
+];
+
+static SYNTAXES: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+
+static THEME_CSS: LazyLock<String> = LazyLock::new(|| {
+    let themes = ThemeSet::load_defaults();
+    let light = &themes.themes[LIGHT_THEME];
+    let dark = &themes.themes[DARK_THEME];
+    format!(
+        "{}\n@media (prefers-color-scheme: dark) {{\n{}}}\n",
+        css_for_theme_with_class_style(light, CLASS_STYLE).unwrap_or_default(),
+        css_for_theme_with_class_style(dark, CLASS_STYLE).unwrap_or_default(),
+    )
+});
+
+/// 渲染单个代码块，`lang` 取代码块语言标记的第一个词
+pub(crate) fn highlight(code: &str, lang: &str) -> String {
+    let lang = lang.split_whitespace().next().unwrap_or_default().to_ascii_lowercase();
+    let syntax = LANGUAGES
+        .iter()
+        .find(|(token, _)| *token == lang)
+        .and_then(|(_, name)| SYNTAXES.find_syntax_by_name(name));
+
+    let body = syntax
+        .and_then(|syntax| {
+            let mut generator = ClassedHTMLGenerator::new_with_class_style(syntax, &SYNTAXES, CLASS_STYLE);
+            for line in LinesWithEndings::from(code) {
+                generator.parse_html_for_line_which_includes_newline(line).ok()?;
+            }
+            Some(generator.finalize())
+        })
+        .unwrap_or_else(|| crate::escape_attr(code));
+
+    if lang.is_empty() {
+        format!(r#"<pre class="hl-code"><code>{body}</code></pre>"#)
+    } else {
+        format!(r#"<pre class="hl-code"><code class="language-{}">{body}</code></pre>"#, crate::escape_attr(&lang))
+    }
+}
+
+/// 代码高亮样式：浅色主题为默认，深色主题跟随 `prefers-color-scheme`
+pub fn highlight_css() -> &'static str {
+    &THEME_CSS
+}
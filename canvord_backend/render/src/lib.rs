@@ -0,0 +1,278 @@
+mod diagram;
+mod highlight;
+mod math;
+mod sanitize;
+mod slug;
+mod stats;
+
+use std::collections::HashSet;
+use std::hash::{BuildHasher, RandomState};
+
+use pulldown_cmark::html::push_html;
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+pub use crate::highlight::highlight_css;
+pub use crate::sanitize::{allowlist, set_allowlist, Allowlist};
+pub use crate::slug::slugify;
+pub use crate::stats::{is_cjk, reading_minutes, word_count};
+
+/// 渲染规则变化时递增，启动时会重新渲染版本较旧的文章
+pub const VERSION: i32 = 5;
+
+/// 正文最大宽度为 max-w-3xl 减去内边距
+const IMAGE_SIZES: &str = "(max-width: 768px) 100vw, 704px";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    pub level: u8,
+    /// 标题锚点，同时用作目录链接
+    pub id: String,
+    pub text: String,
+}
+
+/// 已生成 WebP 版本的图片，正文中链接到 `key` 的图片会输出 `srcset`
+#[derive(Debug, Clone)]
+pub struct Image {
+    pub key: String,
+    pub width: i32,
+    pub height: i32,
+    /// (宽度, 存储 key)，按宽度从小到大排列
+    pub variants: Vec<(i32, String)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Rendered {
+    pub html: String,
+    pub toc: Vec<Heading>,
+    pub word_count: usize,
+    pub reading_minutes: usize,
+}
+
+/// 将 Markdown 渲染为 HTML，同时生成目录、标题锚点、代码高亮、公式、图表和字数统计；输出按 [`allowlist`] 过滤，正文中的原始 HTML 不会原样保留
+pub fn render(markdown: &str, images: &[Image]) -> Rendered {
+    let mut events = Vec::new();
+    let mut toc = Vec::new();
+    let mut ids = HashSet::new();
+    let mut text = String::new();
+    // 当前正在收集的标题、图片或代码块
+    let mut heading: Option<(HeadingLevel, Option<CowStr>, Vec<Event>)> = None;
+    let mut image: Option<(&Image, String, String, String)> = None;
+    let mut code: Option<(String, String)> = None;
+    let mut trusted = Trusted::new();
+
+    for event in Parser::new_ext(markdown, Options::all()) {
+        if let Event::Text(t) | Event::Code(t) = &event {
+            text.push_str(t);
+            text.push(' ');
+        }
+
+        if let Some((known, url, title, alt)) = image.as_mut() {
+            match event {
+                Event::Text(t) | Event::Code(t) => alt.push_str(&t),
+                Event::End(TagEnd::Image) => {
+                    let html = image_tag(known, url, alt, title);
+                    image = None;
+                    push_event(&mut heading, &mut events, Event::InlineHtml(html.into()));
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        if let Some((lang, body)) = code.as_mut() {
+            match event {
+                Event::Text(t) => body.push_str(&t),
+                Event::End(TagEnd::CodeBlock) => {
+                    let html = match lang.split_whitespace().next().unwrap_or_default().to_ascii_lowercase().as_str() {
+                        "mermaid" => diagram::mermaid(body),
+                        "dot" | "graphviz" => match diagram::graphviz(body, trusted.len()) {
+                            Some(svg) => trusted.insert(svg),
+                            None => trusted.insert(highlight::highlight(body, lang)),
+                        },
+                        // 高亮输出的类名随语法定义变化，无法逐个列入白名单
+                        _ => trusted.insert(highlight::highlight(body, lang)),
+                    };
+                    code = None;
+                    push_event(&mut heading, &mut events, Event::Html(html.into()));
+                }
+                _ => {}
+            }
+            continue;
+        }
+
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let lang = match kind {
+                    CodeBlockKind::Fenced(lang) => lang.to_string(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                code = Some((lang, String::new()));
+            }
+            Event::InlineMath(source) => {
+                let html = trusted.insert(math::math(&source, false));
+                push_event(&mut heading, &mut events, Event::InlineHtml(html.into()));
+            }
+            Event::DisplayMath(source) => {
+                let html = trusted.insert(math::math(&source, true));
+                push_event(&mut heading, &mut events, Event::InlineHtml(html.into()));
+            }
+            Event::Start(Tag::Heading { level, id, .. }) => heading = Some((level, id, Vec::new())),
+            Event::End(TagEnd::Heading(_)) => {
+                let Some((level, id, inner)) = heading.take() else { continue };
+                let title = plain(&inner);
+                let id = unique_id(&mut ids, id.map_or_else(|| anchor_id(&title), |id| id.to_string()));
+
+                events.push(Event::Start(Tag::Heading {
+                    level,
+                    id: Some(id.clone().into()),
+                    classes: Vec::new(),
+                    attrs: Vec::new(),
+                }));
+                events.extend(inner);
+                events.push(Event::InlineHtml(format!(r##"<a class="anchor" href="#{id}" aria-hidden="true">#</a>"##).into()));
+                events.push(Event::End(TagEnd::Heading(level)));
+                toc.push(Heading { level: level as u8, id, text: title });
+            }
+            Event::Start(Tag::Image { ref dest_url, ref title, .. }) => {
+                match images.iter().find(|i| last_segment(dest_url) == i.key) {
+                    Some(known) => image = Some((known, dest_url.to_string(), title.to_string(), String::new())),
+                    None => push_event(&mut heading, &mut events, event),
+                }
+            }
+            event => push_event(&mut heading, &mut events, event),
+        }
+    }
+
+    let mut html = String::with_capacity(markdown.len() * 3 / 2);
+    push_html(&mut html, events.into_iter());
+    let html = trusted.restore(sanitize::clean(&html));
+
+    Rendered {
+        html,
+        toc,
+        word_count: word_count(&text),
+        reading_minutes: reading_minutes(&text),
+    }
+}
+
+/// 公式、图表和代码高亮由渲染器生成，无法用白名单描述；先以占位符代替，过滤之后再放回
+struct Trusted {
+    /// 每次渲染随机生成，正文无法伪造占位符
+    nonce: u64,
+    fragments: Vec<String>,
+}
+
+impl Trusted {
+    fn new() -> Self {
+        Self { nonce: RandomState::new().hash_one(0u8), fragments: Vec::new() }
+    }
+
+    fn len(&self) -> usize {
+        self.fragments.len()
+    }
+
+    fn placeholder(&self, index: usize) -> String {
+        format!("\u{E000}{:x}-{index}\u{E000}", self.nonce)
+    }
+
+    fn insert(&mut self, html: String) -> String {
+        let placeholder = self.placeholder(self.fragments.len());
+        self.fragments.push(html);
+        placeholder
+    }
+
+    fn restore(&self, mut html: String) -> String {
+        for (index, fragment) in self.fragments.iter().enumerate() {
+            html = html.replacen(&self.placeholder(index), fragment, 1);
+        }
+        html
+    }
+}
+
+fn push_event<'a>(heading: &mut Option<(HeadingLevel, Option<CowStr<'a>>, Vec<Event<'a>>)>, events: &mut Vec<Event<'a>>, event: Event<'a>) {
+    match heading {
+        Some((_, _, inner)) => inner.push(event),
+        None => events.push(event),
+    }
+}
+
+fn plain(events: &[Event]) -> String {
+    events
+        .iter()
+        .filter_map(|e| match e {
+            Event::Text(t) | Event::Code(t) => Some(t.as_ref()),
+            _ => None,
+        })
+        .collect::<String>()
+        .trim()
+        .to_owned()
+}
+
+/// 与分类 slug 规则一致：字母数字（含中文）保留并转小写，其余字符折叠为单个 `-`
+fn anchor_id(text: &str) -> String {
+    match slugify(text) {
+        id if id.is_empty() => "section".to_owned(),
+        id => id,
+    }
+}
+
+/// 重复的标题依次追加 `-1`、`-2`
+fn unique_id(ids: &mut HashSet<String>, id: String) -> String {
+    let mut candidate = id.clone();
+    let mut n = 0;
+    while !ids.insert(candidate.clone()) {
+        n += 1;
+        candidate = format!("{id}-{n}");
+    }
+    candidate
+}
+
+fn url_path(url: &str) -> &str {
+    url.split(['?', '#']).next().unwrap_or(url)
+}
+
+fn last_segment(url: &str) -> &str {
+    let path = url_path(url);
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// 变体与原图位于同一目录下，直接替换地址的最后一段
+fn image_tag(image: &Image, url: &str, alt: &str, title: &str) -> String {
+    let path = url_path(url);
+    let base = &path[..path.len() - last_segment(path).len()];
+    let srcset = image
+        .variants
+        .iter()
+        .map(|(width, key)| format!("{base}{key} {width}w"))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut tag = format!(
+        r#"<img src="{}" srcset="{}" sizes="{IMAGE_SIZES}" width="{}" height="{}" alt="{}" loading="lazy" decoding="async""#,
+        escape_attr(url),
+        escape_attr(&srcset),
+        image.width,
+        image.height,
+        escape_attr(alt),
+    );
+    if !title.is_empty() {
+        tag.push_str(&format!(r#" title="{}""#, escape_attr(title)));
+    }
+    tag.push_str(" />");
+    tag
+}
+
+fn escape_attr(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
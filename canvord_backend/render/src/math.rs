@@ -0,0 +1,19 @@
+use katex::{Opts, OutputType};
+
+/// 公式输出为 MathML，浏览器原生显示，不依赖 KaTeX 的样式和字体；语法错误时按原文输出
+pub(crate) fn math(source: &str, display: bool) -> String {
+    // trust 关闭时 \href、\htmlClass 等命令不会生效
+    let rendered = Opts::builder()
+        .display_mode(display)
+        .output_type(OutputType::Mathml)
+        .trust(false)
+        .build()
+        .ok()
+        .and_then(|opts| katex::render_with_opts(source, &opts).ok());
+
+    match rendered {
+        Some(html) => html,
+        None if display => format!(r#"<code class="math-error">$${}$$</code>"#, crate::escape_attr(source)),
+        None => format!(r#"<code class="math-error">${}$</code>"#, crate::escape_attr(source)),
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{LazyLock, OnceLock};
+
+use ammonia::Builder;
+
+static ALLOWLIST: OnceLock<Allowlist> = OnceLock::new();
+
+/// 按全局白名单构建的过滤器，只在首次渲染时构建一次
+static BUILDER: LazyLock<Builder<'static>> = LazyLock::new(|| allowlist().builder());
+
+/// 连同内容一起删除的标签，配置中出现也不会放行
+const NEVER_ALLOWED: &[&str] = &["script", "style"];
+
+/// 渲染结果中允许保留的标签、属性和链接协议，其余内容在保存前移除
+#[derive(Debug, Clone)]
+pub struct Allowlist {
+    tags: HashSet<String>,
+    /// 标签为 `*` 时对所有标签生效
+    attributes: HashMap<String, HashSet<String>>,
+    /// `class` 只能取这里列出的值，其余类名会被移除
+    classes: HashMap<String, HashSet<String>>,
+    url_schemes: HashSet<String>,
+}
+
+impl Default for Allowlist {
+    /// ammonia 的默认规则，加上 Markdown 渲染需要的标题锚点、响应式图片和任务列表
+    fn default() -> Self {
+        let builder = Builder::default();
+        let mut allowlist = Self {
+            tags: builder.clone_tags().into_iter().map(str::to_owned).collect(),
+            attributes: builder
+                .clone_tag_attributes()
+                .into_iter()
+                .map(|(tag, attrs)| (tag.to_owned(), attrs.into_iter().map(str::to_owned).collect()))
+                .collect(),
+            classes: HashMap::new(),
+            url_schemes: builder.clone_url_schemes().into_iter().map(str::to_owned).collect(),
+        };
+        allowlist
+            .allow_attributes("*", builder.clone_generic_attributes())
+            .allow_attributes("a", ["aria-hidden"])
+            // 只放行渲染器自己输出的类名，作者不能借用站点样式
+            .allow_classes("a", ["anchor"])
+            .allow_classes("pre", ["mermaid"])
+            .allow_classes("sup", ["footnote-reference", "footnote-definition-label"])
+            .allow_classes("div", ["footnote-definition"])
+            .allow_classes("blockquote", [
+                "markdown-alert-note",
+                "markdown-alert-tip",
+                "markdown-alert-important",
+                "markdown-alert-warning",
+                "markdown-alert-caution",
+            ])
+            .allow_attributes("img", ["srcset", "sizes", "loading", "decoding"])
+            .allow_attributes("div", ["id"])
+            .allow_tags(["input"])
+            .allow_attributes("input", ["checked", "disabled"]);
+        for heading in ["h1", "h2", "h3", "h4", "h5", "h6"] {
+            allowlist.allow_attributes(heading, ["id"]);
+        }
+        allowlist
+    }
+}
+
+impl Allowlist {
+    pub fn allow_tags<I, S>(&mut self, tags: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let allowed = tags
+            .into_iter()
+            .map(|t| t.into().to_ascii_lowercase())
+            .filter(|t| !NEVER_ALLOWED.contains(&t.as_str()));
+        self.tags.extend(allowed);
+        self
+    }
+
+    pub fn deny_tags<I, S>(&mut self, tags: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for tag in tags {
+            self.tags.remove(&tag.as_ref().to_ascii_lowercase());
+        }
+        self
+    }
+
+    /// 事件属性（`on*`）和 `style` 始终不会保留，`class` 需通过 [`Self::allow_classes`] 按值放行
+    pub fn allow_attributes<I, S>(&mut self, tag: &str, attributes: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let tag = tag.to_ascii_lowercase();
+        if NEVER_ALLOWED.contains(&tag.as_str()) {
+            return self;
+        }
+
+        let allowed = attributes
+            .into_iter()
+            .map(|a| a.into().to_ascii_lowercase())
+            // rel 由 ammonia 统一设置为 noopener noreferrer
+            .filter(|a| !a.starts_with("on") && a != "style" && a != "rel" && a != "class");
+        self.attributes.entry(tag).or_default().extend(allowed);
+        self
+    }
+
+    pub fn allow_classes<I, S>(&mut self, tag: &str, classes: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let tag = tag.to_ascii_lowercase();
+        if NEVER_ALLOWED.contains(&tag.as_str()) {
+            return self;
+        }
+        self.classes.entry(tag).or_default().extend(classes.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn allow_url_schemes<I, S>(&mut self, schemes: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        // 这两种协议可以直接执行脚本
+        let allowed = schemes
+            .into_iter()
+            .map(|s| s.into().to_ascii_lowercase())
+            .filter(|s| s != "javascript" && s != "vbscript");
+        self.url_schemes.extend(allowed);
+        self
+    }
+
+    /// 按配置格式追加规则：标签以逗号分隔，属性写作 `标签:属性`，类名写作 `标签.类名`，例如 `details,summary` 和 `details:open,*:dir,div.note`
+    pub fn extend_from_config(&mut self, tags: &str, attributes: &str) -> &mut Self {
+        self.allow_tags(split(tags));
+        for spec in split(attributes) {
+            if let Some((tag, attribute)) = spec.split_once(':') {
+                self.allow_attributes(tag.trim(), [attribute.trim()]);
+            } else if let Some((tag, class)) = spec.split_once('.') {
+                self.allow_classes(tag.trim(), [class.trim()]);
+            }
+        }
+        self
+    }
+
+    pub fn clean(&self, html: &str) -> String {
+        self.builder().clean(html).to_string()
+    }
+
+    fn builder(&self) -> Builder<'_> {
+        let mut generic = HashSet::new();
+        let mut tag_attributes = HashMap::new();
+        for (tag, attrs) in &self.attributes {
+            let attrs: HashSet<&str> = attrs.iter().map(String::as_str).collect();
+            if tag == "*" {
+                generic = attrs;
+            } else {
+                tag_attributes.insert(tag.as_str(), attrs);
+            }
+        }
+
+        let classes = self
+            .classes
+            .iter()
+            .map(|(tag, classes)| (tag.as_str(), classes.iter().map(String::as_str).collect()))
+            .collect();
+
+        let mut builder = Builder::default();
+        builder
+            .tags(self.tags.iter().map(String::as_str).collect())
+            .generic_attributes(generic)
+            .tag_attributes(tag_attributes)
+            .allowed_classes(classes)
+            // 任务列表只需要只读的复选框：不论原来的类型，一律改写为禁用的复选框，避免伪造表单控件
+            .set_tag_attribute_value("input", "type", "checkbox")
+            .set_tag_attribute_value("input", "disabled", "")
+            .url_schemes(self.url_schemes.iter().map(String::as_str).collect());
+        builder
+    }
+}
+
+fn split(list: &str) -> impl Iterator<Item = &str> {
+    list.split(',').map(str::trim).filter(|s| !s.is_empty())
+}
+
+/// 需在首次渲染前调用，已经设置过时返回 `false`；未设置时使用默认规则
+pub fn set_allowlist(allowlist: Allowlist) -> bool {
+    ALLOWLIST.set(allowlist).is_ok()
+}
+
+pub fn allowlist() -> &'static Allowlist {
+    ALLOWLIST.get_or_init(Allowlist::default)
+}
+
+/// 按全局白名单过滤
+pub(crate) fn clean(html: &str) -> String {
+    BUILDER.clean(html).to_string()
+}
@@ -0,0 +1,12 @@
+/// 由名称生成 URL 友好的 slug：字母数字（含中文）保留并转小写，其余字符折叠为单个 `-`
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_owned()
+}
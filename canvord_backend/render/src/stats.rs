@@ -0,0 +1,52 @@
+/// 中文按字计数
+const CJK_CHARS_PER_MINUTE: usize = 300;
+const WORDS_PER_MINUTE: usize = 200;
+
+/// 中日韩文字每个字算一个词，其余按连续的字母数字算一个词
+pub fn word_count(text: &str) -> usize {
+    let (cjk, words) = count(text);
+    cjk + words
+}
+
+/// 预计阅读分钟数，非空正文至少为 1
+pub fn reading_minutes(text: &str) -> usize {
+    let (cjk, words) = count(text);
+    if cjk + words == 0 {
+        return 0;
+    }
+    let seconds = cjk * 60 / CJK_CHARS_PER_MINUTE + words * 60 / WORDS_PER_MINUTE;
+    seconds.div_ceil(60).max(1)
+}
+
+fn count(text: &str) -> (usize, usize) {
+    let mut cjk = 0;
+    let mut words = 0;
+    let mut in_word = false;
+    for c in text.chars() {
+        if is_cjk(c) {
+            cjk += 1;
+            in_word = false;
+        } else if c.is_alphanumeric() {
+            if !in_word {
+                words += 1;
+            }
+            in_word = true;
+        } else if !(in_word && matches!(c, '\'' | '-')) {
+            // 撇号和连字符不打断单词，例如 don't、state-of-the-art
+            in_word = false;
+        }
+    }
+    (cjk, words)
+}
+
+/// 按单字计数和分词的中日韩文字，字数统计和搜索分词共用
+pub fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'     // 平假名、片假名
+        | '\u{3400}'..='\u{4DBF}'   // CJK 扩展 A
+        | '\u{4E00}'..='\u{9FFF}'   // CJK 基本区
+        | '\u{AC00}'..='\u{D7AF}'   // 谚文音节
+        | '\u{F900}'..='\u{FAFF}'   // CJK 兼容
+        | '\u{20000}'..='\u{2FFFF}' // CJK 扩展 B 至 F 及兼容补充
+    )
+}
@@ -0,0 +1,243 @@
+use render::{render, Allowlist, Image};
+
+fn html(markdown: &str) -> String {
+    render(markdown, &[]).html
+}
+
+/// 解析输出中的真实标签，文本中被转义的 `<`、`"` 不会被当作标签或属性
+fn elements(html: &str) -> Vec<(String, Vec<(String, String)>)> {
+    let mut elements = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        let name_end = rest.find(|c: char| c.is_whitespace() || c == '>' || c == '/').unwrap_or(rest.len());
+        let name = rest[..name_end].to_ascii_lowercase();
+        rest = &rest[name_end..];
+
+        let mut attributes = Vec::new();
+        loop {
+            rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+            if rest.is_empty() || rest.starts_with('>') {
+                break;
+            }
+            let attr_end = rest.find(|c: char| c == '=' || c.is_whitespace() || c == '>').unwrap_or(rest.len());
+            let attr = rest[..attr_end].to_ascii_lowercase();
+            rest = &rest[attr_end..];
+            let mut value = String::new();
+            if let Some(quoted) = rest.strip_prefix("=\"") {
+                let end = quoted.find('"').unwrap_or(quoted.len());
+                value = quoted[..end].replace("&amp;", "&");
+                rest = &quoted[(end + 1).min(quoted.len())..];
+            } else if let Some(bare) = rest.strip_prefix('=') {
+                let end = bare.find(|c: char| c.is_whitespace() || c == '>').unwrap_or(bare.len());
+                value = bare[..end].to_owned();
+                rest = &bare[end..];
+            }
+            attributes.push((attr, value));
+        }
+        if !name.starts_with('/') {
+            elements.push((name, attributes));
+        }
+    }
+    elements
+}
+
+fn assert_no_script(html: &str) {
+    for (name, attributes) in elements(html) {
+        assert!(
+            !["script", "style", "iframe", "object", "embed", "svg", "math", "form", "button", "base", "meta", "link"].contains(&name.as_str()),
+            "`<{name}>` survived sanitization: {html}"
+        );
+        for (attr, value) in attributes {
+            assert!(!attr.starts_with("on"), "`{attr}` survived sanitization: {html}");
+            assert!(!["style", "srcdoc", "formaction"].contains(&attr.as_str()), "`{attr}` survived sanitization: {html}");
+            let value = value.trim().to_ascii_lowercase();
+            assert!(
+                !value.starts_with("javascript:") && !value.starts_with("vbscript:") && !value.starts_with("data:"),
+                "`{attr}={value}` survived sanitization: {html}"
+            );
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "survived sanitization")]
+fn detects_unsanitized_payload() {
+    assert_no_script(r#"<p>safe</p><img src=x onerror=alert(1)>"#);
+}
+
+#[test]
+fn strips_script_tags() {
+    let out = html("hello\n\n<script>alert(1)</script>\n\nworld");
+    assert_no_script(&out);
+    assert!(out.contains("hello") && out.contains("world"));
+    assert!(!out.contains("alert"), "{out}");
+}
+
+#[test]
+fn strips_inline_script_tags() {
+    assert_no_script(&html("text <script>alert(1)</script> more"));
+    assert_no_script(&html("<SCRIPT SRC=//evil.example/x.js></SCRIPT>"));
+}
+
+#[test]
+fn strips_event_handler_attributes() {
+    assert_no_script(&html(r#"<img src="x" onerror="alert(1)">"#));
+    assert_no_script(&html(r#"<p onclick="alert(1)">click</p>"#));
+    assert_no_script(&html(r#"<details open ontoggle="alert(1)"><summary>x</summary></details>"#));
+    assert_no_script(&html(r#"<a href="/" onmouseover="alert(1)">x</a>"#));
+}
+
+#[test]
+fn strips_javascript_urls() {
+    assert_no_script(&html("[click](javascript:alert(1))"));
+    assert_no_script(&html("![img](javascript:alert(1))"));
+    assert_no_script(&html(r#"<a href="JaVaScRiPt:alert(1)">x</a>"#));
+    assert_no_script(&html(r#"<a href="&#106;avascript:alert(1)">x</a>"#));
+    assert_no_script(&html(r#"<a href=" javascript:alert(1)">x</a>"#));
+    assert_no_script(&html(r#"<a href="vbscript:msgbox(1)">x</a>"#));
+    assert_no_script(&html("[ref]\n\n[ref]: javascript:alert(1)"));
+}
+
+#[test]
+fn strips_data_urls_in_links() {
+    assert_no_script(&html(r#"<a href="data:text/html;base64,PHNjcmlwdD5hbGVydCgxKTwvc2NyaXB0Pg==">x</a>"#));
+}
+
+#[test]
+fn strips_embedding_elements() {
+    assert_no_script(&html(r#"<iframe src="https://evil.example"></iframe>"#));
+    assert_no_script(&html(r#"<iframe srcdoc="<script>alert(1)</script>"></iframe>"#));
+    assert_no_script(&html(r#"<object data="evil.swf"></object><embed src="evil.swf">"#));
+    assert_no_script(&html(r#"<svg onload="alert(1)"><circle r="1"/></svg>"#));
+    assert_no_script(&html(r#"<math><mtext><table><mglyph><style><img src=x onerror=alert(1)>"#));
+}
+
+#[test]
+fn strips_forms_and_styles() {
+    assert_no_script(&html(r#"<form action="https://evil.example"><button formaction="javascript:alert(1)">x</button></form>"#));
+    assert_no_script(&html("<style>body { display: none }</style>"));
+    assert_no_script(&html(r#"<p style="position:fixed;inset:0">x</p>"#));
+}
+
+#[test]
+fn only_checkbox_inputs_are_kept() {
+    let out = html(r#"<input type="text" name="password" value="x">"#);
+    assert!(!out.contains(r#"type="text""#) && !out.contains("name="), "{out}");
+
+    let out = html("- [x] done\n- [ ] todo");
+    assert!(out.contains(r#"type="checkbox""#), "{out}");
+}
+
+#[test]
+fn inputs_become_disabled_checkboxes() {
+    // 没有 type 的 input 默认是文本框，同样改写
+    for input in ["<input>", r#"<input type="text" value="x">"#, r#"<input type="checkbox">"#, "- [ ] todo"] {
+        let inputs: Vec<_> = elements(&html(input)).into_iter().filter(|(tag, _)| tag == "input").collect();
+        assert_eq!(inputs.len(), 1, "{input}");
+        let attrs = &inputs[0].1;
+        assert!(attrs.contains(&("type".into(), "checkbox".into())), "{input}: {attrs:?}");
+        assert!(attrs.iter().any(|(name, _)| name == "disabled"), "{input}: {attrs:?}");
+        assert!(!attrs.iter().any(|(name, _)| name == "value"), "{input}: {attrs:?}");
+    }
+}
+
+#[test]
+fn broken_markup_cannot_escape_attributes() {
+    assert_no_script(&html(r#"<img src="x" alt="a"onerror="alert(1)">"#));
+    assert_no_script(&html(r#"<img src=x alt=`"` onerror=alert(1)>"#));
+    assert_no_script(&html("<<script>script>alert(1)<</script>/script>"));
+    assert_no_script(&html("<!--><script>alert(1)</script>-->"));
+}
+
+#[test]
+fn image_alt_and_title_are_escaped() {
+    let images = [Image {
+        key: "a.jpg".into(),
+        width: 800,
+        height: 600,
+        variants: vec![(320, "a-320.webp".into())],
+    }];
+    let out = render(r#"![" onerror="alert(1)](/media/a.jpg "x\" onload=\"alert(1)")"#, &images).html;
+    assert_no_script(&out);
+    assert!(out.contains(r#"srcset="/media/a-320.webp 320w""#), "{out}");
+}
+
+#[test]
+fn keeps_rendering_features() {
+    let out = html("# Title\n\n```rust\nfn main() {}\n```\n\n[link](https://example.com)\n\nfoot[^1]\n\n[^1]: note");
+    assert!(out.contains(r#"<h1 id="title">"#), "{out}");
+    assert!(out.contains(r##"href="#title""##), "{out}");
+    assert!(out.contains(r#"class="language-rust""#), "{out}");
+    assert!(out.contains(r#"<span class="hl-storage hl-type hl-function hl-rust">fn</span>"#), "{out}");
+    assert!(out.contains(r#"href="https://example.com""#), "{out}");
+    assert!(out.contains(r#"rel="noopener noreferrer""#), "{out}");
+}
+
+#[test]
+fn code_blocks_are_escaped() {
+    for lang in ["html", "js", "unknown", ""] {
+        let out = html(&format!("```{lang}\n<script>alert(1)</script><img src=x onerror=alert(1)>\n```"));
+        assert_no_script(&out);
+        assert!(out.contains("&lt;script&gt;") || out.contains("&lt;</span>"), "{out}");
+    }
+    assert_no_script(&html("```\"><script>alert(1)</script>\nx\n```"));
+}
+
+#[test]
+fn math_and_diagrams_are_escaped() {
+    let out = html(concat!(
+        "$<img src=x onerror=alert(1)>$\n\n",
+        "$$\\href{javascript:alert(1)}{x}$$\n\n",
+        "```dot\ndigraph { a [label=\"<script>alert(1)</script>\"] }\n```\n\n",
+        "```mermaid\ngraph TD\n  A[\"<img src=x onerror=alert(1)>\"]\n```\n\n",
+        "<svg><script>alert(1)</script></svg><math><mi>x</mi></math>",
+    ));
+    // 公式和图表会输出 <math>、<svg>，这里只检查脚本
+    for (name, attributes) in elements(&out) {
+        assert!(!["script", "img", "a"].contains(&name.as_str()), "`<{name}>` survived sanitization: {out}");
+        for (attr, value) in attributes {
+            assert!(!attr.starts_with("on"), "`{attr}` survived sanitization: {out}");
+            assert!(!value.to_ascii_lowercase().contains("javascript:"), "`{attr}={value}` survived sanitization: {out}");
+        }
+    }
+    assert_eq!(out.matches("<math").count(), 2, "{out}");
+    assert_eq!(out.matches("<svg").count(), 1, "{out}");
+    assert!(out.contains(r#"<pre class="mermaid">"#), "{out}");
+}
+
+#[test]
+fn allowlist_is_configurable() {
+    let mut allowlist = Allowlist::default();
+    allowlist.deny_tags(["img"]);
+    assert!(!allowlist.clean(r#"<img src="/a.png">"#).contains("<img"));
+
+    let mut allowlist = Allowlist::default();
+    allowlist.extend_from_config("video", "video:controls,*:dir");
+    let out = allowlist.clean(r#"<video controls src="/a.mp4" onplay="alert(1)"></video><p dir="rtl">x</p>"#);
+    assert!(out.contains("<video controls") && out.contains(r#"dir="rtl""#), "{out}");
+    assert_no_script(&out);
+}
+
+#[test]
+fn only_renderer_classes_survive() {
+    let out = html("<p class=\"fixed inset-0 z-50\">x</p>\n\n<pre class=\"mermaid hidden\">y</pre>\n\n# Title\n\nfoot[^1]\n\n[^1]: note");
+    assert!(!out.contains("inset-0") && !out.contains("mermaid hidden"), "{out}");
+    assert!(out.contains(r#"<pre class="mermaid">"#), "{out}");
+    assert!(out.contains(r#"<a class="anchor""#), "{out}");
+    assert!(out.contains(r#"<sup class="footnote-reference">"#), "{out}");
+
+    let mut allowlist = Allowlist::default();
+    allowlist.extend_from_config("", "div.note,*:class");
+    let out = allowlist.clean(r#"<div class="note fixed">x</div><p class="note">y</p>"#);
+    assert!(out.contains(r#"<div class="note">"#) && out.contains("<p>y</p>"), "{out}");
+}
+
+#[test]
+fn configuration_cannot_reenable_scripts() {
+    let mut allowlist = Allowlist::default();
+    allowlist
+        .extend_from_config("script,style,svg", "*:onclick,*:style,img:onerror")
+        .allow_url_schemes(["javascript"]);
+    assert_no_script(&allowlist.clean(r#"<script>alert(1)</script><p onclick="x" style="x">y</p><img src=x onerror=alert(1)><a href="javascript:alert(1)">z</a>"#));
+}
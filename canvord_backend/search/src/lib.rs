@@ -0,0 +1,312 @@
+mod tokenizer;
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use entity::article;
+use entity::article::Status;
+use pulldown_cmark::{Event, Parser};
+use tantivy::collector::{Count, TopDocs};
+use tantivy::directory::MmapDirectory;
+use tantivy::query::{BooleanQuery, Occur, Query, QueryParser, TermQuery};
+use tantivy::schema::{
+    Field, IndexRecordOption, Schema, TextFieldIndexing, TextOptions, Value, FAST, INDEXED, STORED, STRING,
+};
+use tantivy::snippet::{Snippet, SnippetGenerator};
+use tantivy::tokenizer::{LowerCaser, RemoveLongFilter, TextAnalyzer};
+use tantivy::{doc, Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument, Term};
+
+use crate::tokenizer::CjkTokenizer;
+
+pub use tantivy::TantivyError as SearchError;
+
+const TOKENIZER: &str = "cjk";
+const WRITER_MEMORY_BYTES: usize = 50_000_000;
+const SNIPPET_MAX_CHARS: usize = 160;
+/// 索引的字段或分词规则变化时递增，启动时发现版本不同会清空索引并重建
+const SCHEMA_VERSION: u32 = 1;
+const VERSION_FILE: &str = "schema_version";
+/// 翻页最多能取到第几条结果（`page * per`）。tantivy 按 `offset + limit` 分配候选集，
+/// 不限制时一次深翻页的请求就能耗尽内存
+pub const MAX_RESULTS: usize = 10_000;
+
+/// 第 `page` 页（从 1 开始）的偏移量，页码为 0 或超出 [`MAX_RESULTS`] 时返回 `None`
+pub fn result_offset(page: usize, per: usize) -> Option<usize> {
+    page.checked_mul(per)
+        .filter(|&end| page > 0 && end <= MAX_RESULTS)
+        .map(|end| end - per)
+}
+
+#[derive(Clone, Copy)]
+struct Fields {
+    id: Field,
+    title: Field,
+    description: Field,
+    content: Field,
+    status: Field,
+}
+
+/// 单条命中结果，`title_html` 与 `snippet_html` 已转义，仅包含 `<mark>` 高亮标签
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub id: i64,
+    pub score: f32,
+    pub title_html: String,
+    pub snippet_html: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    pub hits: Vec<SearchHit>,
+    pub total: usize,
+}
+
+/// 基于 tantivy 的本地全文索引，索引标题、描述和正文。
+///
+/// 提交和重新加载索引会阻塞线程，各方法在 blocking 线程池中执行。
+pub struct SearchIndex {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    index: Index,
+    reader: IndexReader,
+    writer: Mutex<IndexWriter>,
+    fields: Fields,
+}
+
+impl SearchIndex {
+    pub fn open<P: AsRef<Path>>(dir: P) -> tantivy::Result<Self> {
+        let dir = dir.as_ref();
+        let version_file = dir.join(VERSION_FILE);
+        let version = std::fs::read_to_string(&version_file).ok().and_then(|v| v.trim().parse::<u32>().ok());
+        if version != Some(SCHEMA_VERSION) && dir.exists() {
+            std::fs::remove_dir_all(dir)?;
+        }
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(&version_file, SCHEMA_VERSION.to_string())?;
+
+        let (schema, fields) = Inner::schema();
+        let index = Index::open_or_create(MmapDirectory::open(dir)?, schema)?;
+        index.tokenizers().register(
+            TOKENIZER,
+            TextAnalyzer::builder(CjkTokenizer::default())
+                .filter(RemoveLongFilter::limit(40))
+                .filter(LowerCaser)
+                .build(),
+        );
+
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+        let writer = index.writer_with_num_threads(1, WRITER_MEMORY_BYTES)?;
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                index,
+                reader,
+                writer: Mutex::new(writer),
+                fields,
+            }),
+        })
+    }
+
+    /// 索引为空（首次启动或刚因版本变化被清空）时需要由数据库重建
+    pub fn is_empty(&self) -> bool {
+        self.inner.reader.searcher().num_docs() == 0
+    }
+
+    /// 清空索引后重新写入全部文章
+    pub async fn rebuild(&self, articles: Vec<article::Model>) -> tantivy::Result<()> {
+        self.blocking(move |inner| inner.rebuild(&articles)).await
+    }
+
+    pub async fn upsert(&self, article: article::Model) -> tantivy::Result<()> {
+        self.blocking(move |inner| inner.upsert(&article)).await
+    }
+
+    pub async fn delete(&self, id: i64) -> tantivy::Result<()> {
+        self.blocking(move |inner| inner.delete(id)).await
+    }
+
+    /// 按相关度分页搜索，`page` 从 1 开始；`published_only` 为真时只返回已发布文章
+    pub async fn search(&self, text: String, page: usize, per: usize, published_only: bool) -> tantivy::Result<SearchResult> {
+        self.blocking(move |inner| inner.search(&text, page, per, published_only)).await
+    }
+
+    async fn blocking<T, F>(&self, f: F) -> tantivy::Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Inner) -> tantivy::Result<T> + Send + 'static,
+    {
+        let inner = self.inner.clone();
+        tokio::task::spawn_blocking(move || f(&inner))
+            .await
+            .map_err(|e| SearchError::ErrorInThread(e.to_string()))?
+    }
+}
+
+impl Inner {
+    fn schema() -> (Schema, Fields) {
+        let text = TextOptions::default()
+            .set_indexing_options(
+                TextFieldIndexing::default()
+                    .set_tokenizer(TOKENIZER)
+                    .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+            )
+            .set_stored();
+
+        let mut builder = Schema::builder();
+        let fields = Fields {
+            id: builder.add_i64_field("id", INDEXED | STORED | FAST),
+            title: builder.add_text_field("title", text.clone()),
+            description: builder.add_text_field("description", text.clone()),
+            content: builder.add_text_field("content", text),
+            status: builder.add_text_field("status", STRING),
+        };
+        (builder.build(), fields)
+    }
+
+    fn rebuild(&self, articles: &[article::Model]) -> tantivy::Result<()> {
+        let mut writer = self.writer.lock()?;
+        writer.delete_all_documents()?;
+        for article in articles {
+            writer.add_document(self.document(article))?;
+        }
+        writer.commit()?;
+        self.reader.reload()
+    }
+
+    fn upsert(&self, article: &article::Model) -> tantivy::Result<()> {
+        let mut writer = self.writer.lock()?;
+        writer.delete_term(Term::from_field_i64(self.fields.id, article.id));
+        writer.add_document(self.document(article))?;
+        writer.commit()?;
+        self.reader.reload()
+    }
+
+    fn delete(&self, id: i64) -> tantivy::Result<()> {
+        let mut writer = self.writer.lock()?;
+        writer.delete_term(Term::from_field_i64(self.fields.id, id));
+        writer.commit()?;
+        self.reader.reload()
+    }
+
+    fn search(&self, text: &str, page: usize, per: usize, published_only: bool) -> tantivy::Result<SearchResult> {
+        let f = self.fields;
+        let mut parser = QueryParser::for_index(&self.index, vec![f.title, f.description, f.content]);
+        parser.set_field_boost(f.title, 3.0);
+        parser.set_field_boost(f.description, 1.5);
+        parser.set_conjunction_by_default();
+        // 用户输入不保证符合查询语法，忽略无法解析的部分
+        let (query, _) = parser.parse_query_lenient(text);
+
+        let filtered: Box<dyn Query> = if published_only {
+            let status = TermQuery::new(
+                Term::from_field_text(f.status, status_key(&Status::Published)),
+                IndexRecordOption::Basic,
+            );
+            Box::new(BooleanQuery::new(vec![
+                (Occur::Must, query.box_clone()),
+                (Occur::Must, Box::new(status)),
+            ]))
+        } else {
+            query.box_clone()
+        };
+
+        let searcher = self.reader.searcher();
+        let offset = result_offset(page, per)
+            .ok_or_else(|| SearchError::InvalidArgument(format!("page {page} is out of range")))?;
+        let (top_docs, total) = searcher.search(
+            &filtered,
+            &(TopDocs::with_limit(per.max(1)).and_offset(offset), Count),
+        )?;
+
+        let mut title_snippets = SnippetGenerator::create(&searcher, &*query, f.title)?;
+        title_snippets.set_max_num_chars(usize::MAX);
+        let mut content_snippets = SnippetGenerator::create(&searcher, &*query, f.content)?;
+        content_snippets.set_max_num_chars(SNIPPET_MAX_CHARS);
+
+        let mut hits = Vec::with_capacity(top_docs.len());
+        for (score, address) in top_docs {
+            let doc: TantivyDocument = searcher.doc(address)?;
+            let field_text = |field: Field| doc.get_first(field).and_then(|v| v.as_str()).unwrap_or_default().to_owned();
+
+            let Some(id) = doc.get_first(f.id).and_then(|v| v.as_i64()) else {
+                continue;
+            };
+            let title = field_text(f.title);
+            let content = field_text(f.content);
+
+            hits.push(SearchHit {
+                id,
+                score,
+                title_html: highlight(title_snippets.snippet(&title), &title, usize::MAX),
+                snippet_html: highlight(content_snippets.snippet(&content), &content, SNIPPET_MAX_CHARS),
+            });
+        }
+
+        Ok(SearchResult { hits, total })
+    }
+
+    fn document(&self, article: &article::Model) -> TantivyDocument {
+        let f = self.fields;
+        doc!(
+            f.id => article.id,
+            f.title => article.title.as_str(),
+            f.description => article.description.as_str(),
+            f.content => plain_text(&article.content_md),
+            f.status => status_key(&article.status),
+        )
+    }
+}
+
+fn status_key(status: &Status) -> &'static str {
+    match status {
+        Status::Published => "published",
+        Status::Unpublished => "unpublished",
+        Status::Hidden => "hidden",
+        Status::Scheduled => "scheduled",
+    }
+}
+
+/// 去掉 Markdown 标记，只保留可见文字用于索引和摘要
+fn plain_text(markdown: &str) -> String {
+    let mut text = String::with_capacity(markdown.len());
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Text(t) | Event::Code(t) => text.push_str(&t),
+            Event::SoftBreak | Event::HardBreak | Event::End(_) if !text.ends_with(' ') => text.push(' '),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// 没有命中词时退回到原文开头
+fn highlight(mut snippet: Snippet, fallback: &str, max_chars: usize) -> String {
+    if snippet.is_empty() {
+        let head: String = fallback.chars().take(max_chars).collect();
+        return escape_html(&head);
+    }
+
+    snippet.set_snippet_prefix_postfix("<mark>", "</mark>");
+    // 逐字切分的中文会产生相邻的高亮片段，合并后更易读
+    snippet.to_html().replace("</mark><mark>", "")
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,64 @@
+use render::is_cjk;
+use tantivy::tokenizer::{Token, TokenStream, Tokenizer};
+
+/// 中日韩文字逐字切分，其余字母数字按连续片段切分。
+/// 查询时同一片段内的多个字会组成短语查询，因此能匹配连续的中文词语
+#[derive(Clone, Default)]
+pub struct CjkTokenizer {
+    token: Token,
+}
+
+pub struct CjkTokenStream<'a> {
+    text: &'a str,
+    cursor: usize,
+    token: &'a mut Token,
+}
+
+impl Tokenizer for CjkTokenizer {
+    type TokenStream<'a> = CjkTokenStream<'a>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> CjkTokenStream<'a> {
+        self.token.reset();
+        CjkTokenStream {
+            text,
+            cursor: 0,
+            token: &mut self.token,
+        }
+    }
+}
+
+impl TokenStream for CjkTokenStream<'_> {
+    fn advance(&mut self) -> bool {
+        let rest = &self.text[self.cursor..];
+        let Some((skip, first)) = rest.char_indices().find(|(_, c)| c.is_alphanumeric()) else {
+            self.cursor = self.text.len();
+            return false;
+        };
+
+        let start = self.cursor + skip;
+        let end = if is_cjk(first) {
+            start + first.len_utf8()
+        } else {
+            self.text[start..]
+                .char_indices()
+                .find(|(_, c)| !c.is_alphanumeric() || is_cjk(*c))
+                .map_or(self.text.len(), |(i, _)| start + i)
+        };
+
+        self.cursor = end;
+        self.token.text.clear();
+        self.token.text.push_str(&self.text[start..end]);
+        self.token.offset_from = start;
+        self.token.offset_to = end;
+        self.token.position = self.token.position.wrapping_add(1);
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.token
+    }
+}
@@ -0,0 +1,14 @@
+use search::{result_offset, MAX_RESULTS};
+
+#[test]
+fn offsets_stay_within_the_result_window() {
+    assert_eq!(result_offset(1, 10), Some(0));
+    assert_eq!(result_offset(3, 10), Some(20));
+    assert_eq!(result_offset(100, 100), Some(MAX_RESULTS - 100));
+
+    assert_eq!(result_offset(0, 10), None);
+    assert_eq!(result_offset(101, 100), None);
+    // 乘法溢出时不会 panic 或回绕
+    assert_eq!(result_offset(usize::MAX, 100), None);
+    assert_eq!(result_offset(100_000_000, 100), None);
+}
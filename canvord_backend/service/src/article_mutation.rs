@@ -1,73 +1,338 @@
 use command::create_article_command::CreateArticleCommand;
 use command::delete_article_command::DeleteArticleCommand;
+use command::cancel_schedule_command::CancelScheduleCommand;
 use command::hide_article_command::HideArticleCommand;
 use command::publish_article_command::PublishArticleCommand;
 use command::publish_draft_command::PublishDraftCommand;
+use command::restore_article_revision_command::RestoreArticleRevisionCommand;
 use command::save_article_command::SaveArticleCommand;
+use command::schedule_article_command::ScheduleArticleCommand;
 use command::update_article_command::UpdateArticleCommand;
 use command::IntoActiveModel;
 use entity::article::Status;
 use entity::{article, article::Entity as Article};
-use sea_orm::sqlx::types::chrono::Utc;
-use sea_orm::{ActiveModelTrait, DbConn, DbErr, DeleteResult, Set};
+use entity::{article_revision, article_revision::Entity as ArticleRevision};
+use entity::{article_media, article_media::Entity as ArticleMedia};
+use entity::{article_tag, article_tag::Entity as ArticleTag};
+use entity::{tag, tag::Entity as Tag};
+use sea_orm::sqlx::types::chrono::{NaiveDateTime, Utc};
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseTransaction, DbConn, DbErr, DeleteResult, EntityTrait, QueryFilter, QuerySelect, Set, SqlErr, TransactionTrait, TryIntoModel};
+use crate::article_render::ArticleRender;
+use crate::error::MutationError;
+use crate::media_query::MediaQuery;
+use crate::slug::slugify;
+
+/// 同名 slug 冲突时最多尝试的序号
+const MAX_TAG_SLUG_ATTEMPTS: usize = 100;
 
 pub struct ArticleMutation;
 
 impl ArticleMutation {
-    pub async fn create(db: &DbConn, cmd: CreateArticleCommand) -> Result<article::ActiveModel, DbErr> {
+    pub async fn create(db: &DbConn, mut cmd: CreateArticleCommand, author_id: i64) -> Result<article::ActiveModel, DbErr> {
         let now = Utc::now().naive_utc();
-        cmd.into_active_model(now).save(db).await
+        let tags = std::mem::take(&mut cmd.tags);
+        let txn = db.begin().await?;
+        let mut article = cmd.into_active_model(now);
+        article.author_id = Set(Some(author_id));
+        ArticleRender::apply(&txn, &mut article).await?;
+        let article = article.save(&txn).await?;
+        let model = article.clone().try_into_model()?;
+        Self::snapshot(&txn, &model).await?;
+        Self::replace_media(&txn, &model).await?;
+        Self::replace_tags(&txn, model.id, tags, now).await?;
+        txn.commit().await?;
+        Ok(article)
     }
 
-    pub async fn save_draft(db: &DbConn, cmd: SaveArticleCommand) -> Result<article::ActiveModel, DbErr> {
+    pub async fn save_draft(db: &DbConn, mut cmd: SaveArticleCommand, author_id: i64) -> Result<article::ActiveModel, DbErr> {
         let now = Utc::now().naive_utc();
-        cmd.into_active_model(now).save(db).await
+        let tags = std::mem::take(&mut cmd.tags);
+        let txn = db.begin().await?;
+        let mut article = cmd.into_active_model(now);
+        article.author_id = Set(Some(author_id));
+        ArticleRender::apply(&txn, &mut article).await?;
+        let article = article.save(&txn).await?;
+        let model = article.clone().try_into_model()?;
+        Self::snapshot(&txn, &model).await?;
+        Self::replace_media(&txn, &model).await?;
+        Self::replace_tags(&txn, model.id, tags, now).await?;
+        txn.commit().await?;
+        Ok(article)
     }
 
     pub async fn delete(db: &DbConn, cmd: DeleteArticleCommand) -> Result<DeleteResult, DbErr> {
-        let article = Self::find_active_model(db, cmd.id).await?;
-        article.delete(db).await
+        let txn = db.begin().await?;
+        let article = Self::find_active_model(&txn, cmd.id).await?;
+        let result = article.delete(&txn).await?;
+        txn.commit().await?;
+        Ok(result)
     }
 
-    pub async fn update(db: &DbConn, cmd: UpdateArticleCommand) -> Result<article::Model, DbErr> {
+    /// `owner` 不为空时只允许修改该用户创建的草稿，且不能改变草稿状态，用于没有编辑权限的作者
+    pub async fn update(db: &DbConn, mut cmd: UpdateArticleCommand, owner: Option<i64>) -> Result<article::Model, MutationError> {
         let now = Utc::now().naive_utc();
-        cmd.into_active_model(now).update(db).await
+        let tags = cmd.tags.take();
+        let txn = db.begin().await?;
+        // 锁住该行，检查之后状态不会再被定时任务改掉
+        let current = Self::find_active_model(&txn, cmd.id).await?;
+        let status = current.status.as_ref();
+        if let Some(owner) = owner
+            && (current.author_id.as_ref() != &Some(owner) || status != &Status::Unpublished || cmd.status != Status::Unpublished)
+        {
+            return Err(MutationError::Forbidden("Authors can only edit their own drafts".to_owned()));
+        }
+        // 没有发布时间的文章不能直接改为定时发布
+        if cmd.status == Status::Scheduled && status != &Status::Scheduled {
+            return Err(MutationError::Invalid("Use schedule to set publish time".to_owned()));
+        }
+        let mut article = cmd.into_active_model(now);
+        ArticleRender::apply(&txn, &mut article).await?;
+        let article = article.update(&txn).await?;
+        Self::snapshot(&txn, &article).await?;
+        Self::replace_media(&txn, &article).await?;
+        if let Some(tags) = tags {
+            Self::replace_tags(&txn, article.id, tags, now).await?;
+        }
+        txn.commit().await?;
+        Ok(article)
     }
 
     pub async fn publish(db: &DbConn, cmd: PublishArticleCommand) -> Result<article::Model, DbErr> {
-        let article = Self::find_active_model(db, cmd.id).await?;
-        Self::apply_status(article, Status::Published).update(db).await
+        let txn = db.begin().await?;
+        let article = Self::find_active_model(&txn, cmd.id).await?;
+        let article = Self::apply_status(article, Status::Published).update(&txn).await?;
+        txn.commit().await?;
+        Ok(article)
     }
 
-    pub async fn publish_draft(db: &DbConn, cmd: PublishDraftCommand) -> Result<article::Model, DbErr> {
-        // 先获取当前文章 ActiveModel
-        let article = Self::find_active_model(db, cmd.id).await?;
+    pub async fn publish_draft(db: &DbConn, mut cmd: PublishDraftCommand) -> Result<article::Model, MutationError> {
+        let now = Utc::now().naive_utc();
+        let tags = cmd.tags.take();
+        let txn = db.begin().await?;
 
         // 业务约束：仅允许草稿状态的文章发布草稿
-        if article.status.as_ref().ne(&Status::Unpublished){
-            return Err(DbErr::Custom("Only draft articles can be published".to_owned()));
+        if Self::lock_status(&txn, cmd.id).await? != Status::Unpublished {
+            return Err(MutationError::Conflict("Only draft articles can be published".to_owned()));
         }
 
-        let now = Utc::now().naive_utc();
-        cmd.into_active_model(now).update(db).await
+        let mut article = cmd.into_active_model(now);
+        ArticleRender::apply(&txn, &mut article).await?;
+        let article = article.update(&txn).await?;
+        Self::snapshot(&txn, &article).await?;
+        Self::replace_media(&txn, &article).await?;
+        if let Some(tags) = tags {
+            Self::replace_tags(&txn, article.id, tags, now).await?;
+        }
+        txn.commit().await?;
+        Ok(article)
     }
 
     pub async fn hide(db: &DbConn, cmd: HideArticleCommand) -> Result<article::Model, DbErr> {
-        let article = Self::find_active_model(db, cmd.id).await?;
-        Self::apply_status(article, Status::Hidden).update(db).await
+        let txn = db.begin().await?;
+        let article = Self::find_active_model(&txn, cmd.id).await?;
+        let article = Self::apply_status(article, Status::Hidden).update(&txn).await?;
+        txn.commit().await?;
+        Ok(article)
+    }
+
+    /// 设置定时发布，已定时的文章重新设置时间；已发布的文章不能定时
+    pub async fn schedule(db: &DbConn, cmd: ScheduleArticleCommand) -> Result<article::Model, MutationError> {
+        let txn = db.begin().await?;
+        let article = Self::find_active_model(&txn, cmd.id).await?;
+        if article.status.as_ref() == &Status::Published {
+            return Err(MutationError::Conflict("Article is already published".to_owned()));
+        }
+
+        let mut article = Self::apply_status(article, Status::Scheduled);
+        article.publish_at = Set(Some(cmd.publish_at));
+        let article = article.update(&txn).await?;
+        txn.commit().await?;
+        Ok(article)
+    }
+
+    pub async fn cancel_schedule(db: &DbConn, cmd: CancelScheduleCommand) -> Result<article::Model, MutationError> {
+        let txn = db.begin().await?;
+        let article = Self::find_active_model(&txn, cmd.id).await?;
+        if article.status.as_ref() != &Status::Scheduled {
+            return Err(MutationError::Conflict("Article is not scheduled".to_owned()));
+        }
+        let article = Self::apply_status(article, Status::Unpublished).update(&txn).await?;
+        txn.commit().await?;
+        Ok(article)
+    }
+
+    /// 发布 `publish_at` 不晚于 `now` 的定时文章，返回本次发布的文章。
+    ///
+    /// 按状态和时间条件更新，多个实例同时执行或文章刚被取消时不会重复发布
+    pub async fn publish_due(db: &DbConn, now: NaiveDateTime) -> Result<Vec<article::Model>, DbErr> {
+        let due = Article::list_scheduled()
+            .filter(article::Column::PublishAt.lte(now))
+            .all(db)
+            .await?;
+
+        let mut published = Vec::with_capacity(due.len());
+        for model in due {
+            let result = Article::update_many()
+                .col_expr(article::Column::Status, Expr::value(Status::Published))
+                .col_expr(article::Column::PublishAt, Expr::value(Option::<NaiveDateTime>::None))
+                .col_expr(article::Column::LastUpdate, Expr::value(now))
+                .filter(article::Column::Id.eq(model.id))
+                .filter(article::Column::Status.eq(Status::Scheduled))
+                .filter(article::Column::PublishAt.lte(now))
+                .exec(db)
+                .await?;
+            if result.rows_affected == 1 {
+                published.push(article::Model {
+                    status: Status::Published,
+                    publish_at: None,
+                    last_update: now,
+                    ..model
+                });
+            }
+        }
+        Ok(published)
+    }
+
+    /// 将文章的标题、描述和正文恢复为指定修订，并记录为一条新修订
+    pub async fn restore_revision(db: &DbConn, cmd: RestoreArticleRevisionCommand) -> Result<article::Model, DbErr> {
+        let txn = db.begin().await?;
+        let revision = ArticleRevision::find_by_id(cmd.revision_id)
+            .one(&txn)
+            .await?
+            .ok_or(DbErr::RecordNotFound("Cannot find revision".to_owned()))?;
+        let mut article = Self::find_active_model(&txn, revision.article_id).await?;
+
+        article.title = Set(revision.title);
+        article.description = Set(revision.description);
+        article.content_md = Set(revision.content_md);
+        article.last_update = Set(Utc::now().naive_utc());
+
+        ArticleRender::apply(&txn, &mut article).await?;
+        let article = article.update(&txn).await?;
+        Self::snapshot(&txn, &article).await?;
+        Self::replace_media(&txn, &article).await?;
+        txn.commit().await?;
+        Ok(article)
     }
 
+    /// 重新渲染 `render_version` 低于当前渲染规则的文章，不记录修订也不更新修改时间
+    pub async fn render_stale(db: &DbConn) -> Result<u64, DbErr> {
+        let stale = Article::find()
+            .filter(article::Column::RenderVersion.lt(render::VERSION))
+            .all(db)
+            .await?;
+
+        for model in &stale {
+            let rendered = ArticleRender::render(db, &model.content_md).await?;
+            let mut article: article::ActiveModel = model.clone().into();
+            ArticleRender::fill(&mut article, rendered);
+            article.update(db).await?;
+        }
+        Ok(stale.len() as u64)
+    }
+
+    /// 状态变化时同时清除定时发布时间
     fn apply_status(mut model: article::ActiveModel, status: Status) -> article::ActiveModel {
         model.status = Set(status);
+        model.publish_at = Set(None);
         model.last_update = Set(Utc::now().naive_utc());
         model
     }
 
-    async fn find_active_model(db: &DbConn, id: i64) -> Result<article::ActiveModel, DbErr> {
+    async fn snapshot<C: ConnectionTrait>(db: &C, article: &article::Model) -> Result<(), DbErr> {
+        article_revision::ActiveModel::from_article(article, article.last_update)
+            .insert(db)
+            .await
+            .map(|_| ())
+    }
+
+    /// 按正文中出现的文件 key 重新记录文章引用的媒体
+    async fn replace_media<C: ConnectionTrait>(db: &C, article: &article::Model) -> Result<(), DbErr> {
+        ArticleMedia::delete_by_article(article.id).exec(db).await?;
+        let links: Vec<article_media::ActiveModel> = MediaQuery::find_media_in_content(db, &article.content_md)
+            .await?
+            .into_iter()
+            .map(|m| article_media::ActiveModel {
+                article_id: Set(article.id),
+                media_id: Set(m.id),
+            })
+            .collect();
+        if links.is_empty() {
+            return Ok(());
+        }
+        ArticleMedia::insert_many(links).exec(db).await.map(|_| ())
+    }
+
+    /// 用给定的标签名替换文章的全部标签，不存在的标签会被创建
+    async fn replace_tags<C: ConnectionTrait>(db: &C, article_id: i64, names: Vec<String>, now: NaiveDateTime) -> Result<(), DbErr> {
+        let mut names: Vec<String> = names
+            .into_iter()
+            .map(|n| n.trim().to_owned())
+            .filter(|n| !n.is_empty())
+            .collect();
+        names.sort_by_key(|n| n.to_lowercase());
+        names.dedup_by(|a, b| a.to_lowercase() == b.to_lowercase());
+
+        ArticleTag::delete_by_article(article_id).exec(db).await?;
+        if names.is_empty() {
+            return Ok(());
+        }
+
+        // 数据库排序规则不区分大小写，已有标签按名称忽略大小写匹配
+        let existing = Tag::list_by_names(&names).all(db).await?;
+        let mut links = Vec::with_capacity(names.len());
+        for name in names {
+            let tag_id = match existing.iter().find(|t| t.name.to_lowercase() == name.to_lowercase()) {
+                Some(t) => t.id,
+                None => Self::create_tag(db, name, now).await?.id,
+            };
+            links.push(article_tag::ActiveModel {
+                article_id: Set(article_id),
+                tag_id: Set(tag_id),
+            });
+        }
+
+        ArticleTag::insert_many(links).exec(db).await.map(|_| ())
+    }
+
+    async fn create_tag<C: ConnectionTrait>(db: &C, name: String, now: NaiveDateTime) -> Result<tag::Model, DbErr> {
+        let base = match slugify(&name) {
+            s if s.is_empty() => "tag".to_owned(),
+            s => s,
+        };
+
+        // 不同名称可能生成相同 slug（如 `C` 与 `C++`），由唯一索引判断冲突并追加序号重试，
+        // 并发保存时也不会写入重复的 slug
+        for n in 1..=MAX_TAG_SLUG_ATTEMPTS {
+            let slug = if n == 1 { base.clone() } else { format!("{base}-{n}") };
+            let result = tag::ActiveModel {
+                name: Set(name.clone()),
+                slug: Set(slug),
+                created_at: Set(now),
+                ..Default::default()
+            }
+            .insert(db)
+            .await;
+            match result {
+                Err(e) if matches!(e.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => continue,
+                result => return result,
+            }
+        }
+        Err(DbErr::Custom(format!("Cannot find a free slug for tag {name}")))
+    }
+
+    /// 锁住该行（`SELECT ... FOR UPDATE`）直到事务结束；只接受事务，在事务外加锁会随语句立即释放
+    async fn find_active_model(txn: &DatabaseTransaction, id: i64) -> Result<article::ActiveModel, DbErr> {
         Article::find_by_id(id)
-            .one(db)
+            .lock_exclusive()
+            .one(txn)
             .await?
-            .ok_or(DbErr::Custom("Cannot find article".to_owned()))
+            .ok_or(DbErr::RecordNotFound("Cannot find article".to_owned()))
             .map(Into::into)
     }
+
+    async fn lock_status(txn: &DatabaseTransaction, id: i64) -> Result<Status, DbErr> {
+        Ok(Self::find_active_model(txn, id).await?.status.unwrap())
+    }
 }
@@ -1,8 +1,12 @@
+use std::collections::HashMap;
 use entity::article::{Column, Status};
 use entity::{article, article::Entity as Article};
+use entity::{category, category::Entity as Category};
+use entity::{tag, tag::Entity as Tag};
+use sea_orm::sqlx::types::chrono::NaiveDateTime;
 use sea_orm::ColumnTrait;
 use sea_orm::QueryFilter;
-use sea_orm::{DbConn, DbErr, EntityTrait, PaginatorTrait, QueryOrder};
+use sea_orm::{Condition, DbConn, DbErr, EntityTrait, ModelTrait, Order, PaginatorTrait, QueryOrder, QuerySelect};
 use entity::article::Status::Published;
 
 pub struct ArticleQuery;
@@ -15,18 +19,22 @@ impl ArticleQuery {
     pub async fn find_article_by_slug(db: &DbConn, slug: String) -> Result<Option<article::Model>, DbErr> {
         Article::find_by_slug(&slug).one(db).await
     }
-    
-    pub async fn list_article_by_title(db: &DbConn, title: String) -> Result<Vec<article::Model>, DbErr> {
-        Article::list_by_title(&title).order_by_asc(Column::CreatedAt).all(db).await
+
+    pub async fn list_article_by_title(db: &DbConn, title: String) -> Result<Vec<(article::Model, Option<category::Model>)>, DbErr> {
+        Article::list_by_title(&title)
+            .find_also_related(Category)
+            .order_by_asc(Column::CreatedAt)
+            .all(db)
+            .await
     }
-    
-    /// If ok, returns (article models, num pages).
+
+    /// If ok, returns (article models with category, num pages).
     pub async fn list_articles_in_page_by_status(
         db: &DbConn,
         page: u64,
         posts_per_page: u64,
         status: Option<Status>,
-    ) -> Result<(Vec<article::Model>, u64), DbErr> {
+    ) -> Result<(Vec<(article::Model, Option<category::Model>)>, u64), DbErr> {
         let mut query = Article::find();
 
         if let Some(s) = status {
@@ -34,18 +42,215 @@ impl ArticleQuery {
         }
 
         let paginator = query
+            .find_also_related(Category)
+            .order_by_asc(Column::CreatedAt)
+            .paginate(db, posts_per_page);
+        let num_pages = paginator.num_pages().await?;
+
+        paginator.fetch_page(page.saturating_sub(1)).await.map(|p| (p, num_pages))
+    }
+
+    /// 按 `(created_at, id)` 翻页，不统计总数，页数再深也只读取一页的数据。
+    ///
+    /// `position` 为 `None` 时返回第一页；否则 `forward` 为 `true` 时返回该位置之后的一页，为 `false` 时返回之前的一页。
+    /// 结果总是按创建时间正序，同时返回该方向上是否还有更多文章。
+    pub async fn list_articles_by_cursor(
+        db: &DbConn,
+        status: Option<Status>,
+        position: Option<(NaiveDateTime, i64, bool)>,
+        forward: bool,
+        limit: u64,
+    ) -> Result<(Vec<(article::Model, Option<category::Model>)>, bool), DbErr> {
+        let mut query = Article::find();
+
+        if let Some(s) = status {
+            query = query.filter(Column::Status.eq(s));
+        }
+        // position 的第三项表示是否包含该位置上的文章
+        if let Some((created_at, id, inclusive)) = position {
+            let same = match (forward, inclusive) {
+                (true, false) => Column::Id.gt(id),
+                (true, true) => Column::Id.gte(id),
+                (false, false) => Column::Id.lt(id),
+                (false, true) => Column::Id.lte(id),
+            };
+            let beyond = match forward {
+                true => Column::CreatedAt.gt(created_at),
+                false => Column::CreatedAt.lt(created_at),
+            };
+            query = query.filter(Condition::any().add(beyond).add(Column::CreatedAt.eq(created_at).and(same)));
+        }
+
+        // 多取一条，用来判断是否还有下一页
+        let order = if forward { Order::Asc } else { Order::Desc };
+        let mut articles = query
+            .find_also_related(Category)
+            .order_by(Column::CreatedAt, order.clone())
+            .order_by(Column::Id, order)
+            .limit(limit + 1)
+            .all(db)
+            .await?;
+        let has_more = articles.len() as u64 > limit;
+        articles.truncate(limit as usize);
+        if !forward {
+            articles.reverse();
+        }
+
+        Ok((articles, has_more))
+    }
+
+    /// If ok, returns (article models with category, num pages).
+    pub async fn list_publish_articles_in_page_by_categories(
+        db: &DbConn,
+        category_ids: Vec<i64>,
+        page: u64,
+        posts_per_page: u64,
+    ) -> Result<(Vec<(article::Model, Option<category::Model>)>, u64), DbErr> {
+        let paginator = Article::find()
+            .filter(Column::CategoryId.is_in(category_ids))
+            .filter(Column::Status.eq(Published))
+            .find_also_related(Category)
             .order_by_asc(Column::CreatedAt)
             .paginate(db, posts_per_page);
         let num_pages = paginator.num_pages().await?;
 
-        paginator.fetch_page(page - 1).await.map(|p| (p, num_pages))
+        paginator.fetch_page(page.saturating_sub(1)).await.map(|p| (p, num_pages))
+    }
+
+    /// 最近发布的文章，按创建时间倒序；`category_ids` 为 `None` 时不限分类
+    pub async fn list_latest_publish_articles(
+        db: &DbConn,
+        category_ids: Option<Vec<i64>>,
+        limit: u64,
+    ) -> Result<Vec<article::Model>, DbErr> {
+        let mut query = Article::find().filter(Column::Status.eq(Published));
+
+        if let Some(ids) = category_ids {
+            query = query.filter(Column::CategoryId.is_in(ids));
+        }
+
+        query
+            .order_by_desc(Column::CreatedAt)
+            .limit(limit)
+            .all(db)
+            .await
     }
 
     pub async fn find_publish_article_by_slug(db: &DbConn, slug: String) -> Result<Option<article::Model>, DbErr> {
         Article::find().filter(Column::Slug.eq(slug).and(Column::Status.eq(Published))).one(db).await
     }
 
-    pub async fn list_publish_article_by_title(db: &DbConn, title: String) -> Result<Vec<article::Model>, DbErr> {
-        Article::find().filter(Column::Title.like(title).and(Column::Status.eq(Published))).all(db).await
+    pub async fn list_publish_article_by_title(db: &DbConn, title: String) -> Result<Vec<(article::Model, Option<category::Model>)>, DbErr> {
+        Article::find()
+            .filter(Column::Title.like(title).and(Column::Status.eq(Published)))
+            .find_also_related(Category)
+            .all(db)
+            .await
+    }
+
+    pub async fn list_articles_by_ids(db: &DbConn, ids: Vec<i64>) -> Result<Vec<(article::Model, Option<category::Model>)>, DbErr> {
+        Article::find()
+            .filter(Column::Id.is_in(ids))
+            .find_also_related(Category)
+            .all(db)
+            .await
+    }
+
+    /// 已发布文章连同分类和标签（按名称排序），最新的在前；
+    /// 文章和标签一次联表查出，分类表较小，整表读取后按 id 对应，用于预渲染和静态导出
+    pub async fn list_publish_articles_with_relations(
+        db: &DbConn,
+    ) -> Result<Vec<(article::Model, Option<category::Model>, Vec<tag::Model>)>, DbErr> {
+        let categories: HashMap<i64, category::Model> = Category::find()
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|c| (c.id, c))
+            .collect();
+        let mut articles: Vec<_> = Article::list_by_status(Published)
+            .find_with_related(Tag)
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|(article, mut tags)| {
+                tags.sort_by(|a, b| a.name.cmp(&b.name));
+                let category = article.category_id.and_then(|id| categories.get(&id).cloned());
+                (article, category, tags)
+            })
+            .collect();
+        articles.sort_by_key(|(a, ..)| std::cmp::Reverse(a.created_at));
+        Ok(articles)
+    }
+
+    /// 已发布文章的 (slug, 修改时间)，用于生成 sitemap
+    pub async fn list_publish_article_slugs(db: &DbConn) -> Result<Vec<(String, NaiveDateTime)>, DbErr> {
+        Article::find()
+            .select_only()
+            .column(Column::Slug)
+            .column(Column::LastUpdate)
+            .filter(Column::Status.eq(Published))
+            .order_by_asc(Column::CreatedAt)
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
+    /// 已发布文章的 (slug, 标题, 摘要)，最新的在前，用于预渲染首页的文章列表
+    pub async fn list_publish_article_links(db: &DbConn) -> Result<Vec<(String, String, String)>, DbErr> {
+        Article::find()
+            .select_only()
+            .column(Column::Slug)
+            .column(Column::Title)
+            .column(Column::Description)
+            .filter(Column::Status.eq(Published))
+            .order_by_desc(Column::CreatedAt)
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
+    /// 分类下（不含子分类）已发布文章的 slug，分类修改后据此更新预渲染的文章页
+    pub async fn list_publish_article_slugs_by_category(db: &DbConn, category_id: i64) -> Result<Vec<String>, DbErr> {
+        Article::find()
+            .select_only()
+            .column(Column::Slug)
+            .filter(Column::CategoryId.eq(category_id))
+            .filter(Column::Status.eq(Published))
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
+    /// 等待定时发布的文章，最早发布的在前
+    pub async fn list_scheduled_articles(db: &DbConn) -> Result<Vec<article::Model>, DbErr> {
+        Article::list_scheduled().all(db).await
+    }
+
+    /// 最近一篇待发布文章的发布时间，后台任务据此决定下次检查的时间
+    pub async fn next_publish_at(db: &DbConn) -> Result<Option<NaiveDateTime>, DbErr> {
+        Article::list_scheduled()
+            .select_only()
+            .column(Column::PublishAt)
+            .into_tuple::<Option<NaiveDateTime>>()
+            .one(db)
+            .await
+            .map(Option::flatten)
+    }
+
+    /// 重建搜索索引时使用
+    pub async fn list_all_articles(db: &DbConn) -> Result<Vec<article::Model>, DbErr> {
+        Article::find().all(db).await
+    }
+
+    /// 查询文章详情需要的分类和标签
+    pub async fn find_relations(db: &DbConn, article: &article::Model) -> Result<(Option<category::Model>, Vec<tag::Model>), DbErr> {
+        let category = article.find_related(Category).one(db).await?;
+        let tags = article
+            .find_related(Tag)
+            .order_by_asc(tag::Column::Name)
+            .all(db)
+            .await?;
+
+        Ok((category, tags))
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,52 @@
+use entity::article::{self, Toc, TocEntry};
+use render::Rendered;
+use sea_orm::{ActiveValue, ConnectionTrait, DbErr, Set};
+use crate::media_query::MediaQuery;
+
+pub struct ArticleRender;
+
+impl ArticleRender {
+    /// 渲染 Markdown，正文引用的已上传图片会带上各宽度 WebP 版本的 `srcset`
+    pub async fn render<C: ConnectionTrait>(db: &C, markdown: &str) -> Result<Rendered, DbErr> {
+        let images: Vec<render::Image> = MediaQuery::find_images_in_content(db, markdown)
+            .await?
+            .into_iter()
+            .filter_map(|(m, variants)| {
+                Some(render::Image {
+                    width: m.width?,
+                    height: m.height?,
+                    key: m.storage_key,
+                    variants: variants.into_iter().map(|v| (v.width, v.storage_key)).collect(),
+                })
+            })
+            .collect();
+
+        Ok(render::render(markdown, &images))
+    }
+
+    /// 正文有改动时重新渲染并写入渲染结果
+    pub async fn apply<C: ConnectionTrait>(db: &C, model: &mut article::ActiveModel) -> Result<(), DbErr> {
+        let ActiveValue::Set(markdown) = &model.content_md else {
+            return Ok(());
+        };
+
+        let rendered = Self::render(db, markdown).await?;
+        Self::fill(model, rendered);
+        Ok(())
+    }
+
+    pub fn fill(model: &mut article::ActiveModel, rendered: Rendered) {
+        model.content_html = Set(Some(rendered.html));
+        model.toc = Set(Some(Self::toc(rendered.toc)));
+        model.word_count = Set(rendered.word_count as i32);
+        model.reading_minutes = Set(rendered.reading_minutes as i32);
+        model.render_version = Set(render::VERSION);
+    }
+
+    pub fn toc(headings: Vec<render::Heading>) -> Toc {
+        Toc(headings
+            .into_iter()
+            .map(|h| TocEntry { level: h.level, id: h.id, text: h.text })
+            .collect())
+    }
+}
@@ -0,0 +1,19 @@
+use entity::article_revision::Column;
+use entity::{article_revision, article_revision::Entity as ArticleRevision};
+use sea_orm::{DbConn, DbErr, QueryOrder};
+
+pub struct ArticleRevisionQuery;
+
+impl ArticleRevisionQuery {
+    pub async fn find_revision_by_id(db: &DbConn, id: i64) -> Result<Option<article_revision::Model>, DbErr> {
+        ArticleRevision::find_by_id(id).one(db).await
+    }
+
+    /// 按时间倒序返回文章的所有修订
+    pub async fn list_revisions_by_article(db: &DbConn, article_id: i64) -> Result<Vec<article_revision::Model>, DbErr> {
+        ArticleRevision::list_by_article(article_id)
+            .order_by_desc(Column::Id)
+            .all(db)
+            .await
+    }
+}
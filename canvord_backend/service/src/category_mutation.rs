@@ -0,0 +1,59 @@
+use command::create_category_command::CreateCategoryCommand;
+use command::delete_category_command::DeleteCategoryCommand;
+use command::update_category_command::UpdateCategoryCommand;
+use entity::{category, category::Entity as Category};
+use sea_orm::prelude::Expr;
+use sea_orm::sqlx::types::chrono::Utc;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DbConn, DbErr, DeleteResult, EntityTrait, ModelTrait, QueryFilter, Set, TransactionTrait};
+
+pub struct CategoryMutation;
+
+impl CategoryMutation {
+    pub async fn create(db: &DbConn, cmd: CreateCategoryCommand) -> Result<category::Model, DbErr> {
+        let now = Utc::now().naive_utc();
+
+        category::ActiveModel {
+            parent_id: Set(cmd.parent_id),
+            name: Set(cmd.name),
+            slug: Set(cmd.slug),
+            description: Set(cmd.description),
+            created_at: Set(now),
+            last_update: Set(now),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+    }
+
+    pub async fn update(db: &DbConn, cmd: UpdateCategoryCommand) -> Result<category::Model, DbErr> {
+        category::ActiveModel {
+            id: Set(cmd.id),
+            parent_id: Set(cmd.parent_id),
+            name: Set(cmd.name),
+            slug: Set(cmd.slug),
+            description: Set(cmd.description),
+            last_update: Set(Utc::now().naive_utc()),
+            ..Default::default()
+        }
+        .update(db)
+        .await
+    }
+
+    /// 删除分类，其子分类挂到被删除分类的父级下，文章的分类置空
+    pub async fn delete(db: &DbConn, cmd: DeleteCategoryCommand) -> Result<DeleteResult, DbErr> {
+        let category = Category::find_by_id(cmd.id)
+            .one(db)
+            .await?
+            .ok_or(DbErr::Custom("Cannot find category".to_owned()))?;
+
+        let txn = db.begin().await?;
+        Category::update_many()
+            .col_expr(category::Column::ParentId, Expr::value(category.parent_id))
+            .filter(category::Column::ParentId.eq(category.id))
+            .exec(&txn)
+            .await?;
+        let result = category.delete(&txn).await?;
+        txn.commit().await?;
+        Ok(result)
+    }
+}
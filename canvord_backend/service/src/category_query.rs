@@ -0,0 +1,19 @@
+use entity::category::Column;
+use entity::{category, category::Entity as Category};
+use sea_orm::{DbConn, DbErr, EntityTrait, QueryOrder};
+
+pub struct CategoryQuery;
+
+impl CategoryQuery {
+    pub async fn find_category_by_id(db: &DbConn, id: i64) -> Result<Option<category::Model>, DbErr> {
+        Category::find_by_id(id).one(db).await
+    }
+
+    pub async fn find_category_by_slug(db: &DbConn, slug: &str) -> Result<Option<category::Model>, DbErr> {
+        Category::find_by_slug(slug).one(db).await
+    }
+
+    pub async fn list_categories(db: &DbConn) -> Result<Vec<category::Model>, DbErr> {
+        Category::find().order_by_asc(Column::Name).all(db).await
+    }
+}
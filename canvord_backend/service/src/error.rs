@@ -0,0 +1,32 @@
+use std::fmt;
+
+use sea_orm::DbErr;
+
+/// 修改文章时的错误，区分业务规则不允许的请求和数据库错误
+#[derive(Debug)]
+pub enum MutationError {
+    /// 请求本身不合法
+    Invalid(String),
+    /// 与文章当前的状态冲突
+    Conflict(String),
+    /// 没有修改该文章的权限
+    Forbidden(String),
+    Db(DbErr),
+}
+
+impl fmt::Display for MutationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MutationError::Invalid(msg) | MutationError::Conflict(msg) | MutationError::Forbidden(msg) => f.write_str(msg),
+            MutationError::Db(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for MutationError {}
+
+impl From<DbErr> for MutationError {
+    fn from(err: DbErr) -> Self {
+        MutationError::Db(err)
+    }
+}
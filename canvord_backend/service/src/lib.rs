@@ -1,2 +1,14 @@
 pub mod article_mutation;
 pub mod article_query;
+pub mod article_render;
+pub mod article_revision_query;
+pub mod category_mutation;
+pub mod category_query;
+pub mod error;
+pub mod media_mutation;
+pub mod media_query;
+pub mod password;
+pub mod slug;
+pub mod tag_query;
+pub mod user_mutation;
+pub mod user_query;
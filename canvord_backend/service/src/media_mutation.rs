@@ -0,0 +1,52 @@
+use entity::{media, media::Entity as Media};
+use entity::{media_variant, media_variant::Entity as MediaVariant};
+use sea_orm::sqlx::types::chrono::Utc;
+use sea_orm::{ActiveModelTrait, DbConn, DbErr, DeleteResult, EntityTrait, Set};
+
+pub struct MediaMutation;
+
+impl MediaMutation {
+    pub async fn create(
+        db: &DbConn,
+        owner_id: i64,
+        storage_key: String,
+        file_name: String,
+        content_type: String,
+        size: i64,
+        dimensions: Option<(i32, i32)>,
+    ) -> Result<media::Model, DbErr> {
+        media::ActiveModel {
+            owner_id: Set(Some(owner_id)),
+            storage_key: Set(storage_key),
+            file_name: Set(file_name),
+            content_type: Set(content_type),
+            size: Set(size),
+            width: Set(dimensions.map(|(w, _)| w)),
+            height: Set(dimensions.map(|(_, h)| h)),
+            created_at: Set(Utc::now().naive_utc()),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+    }
+
+    /// `variants` 为 (宽度, 存储 key, 文件大小)
+    pub async fn create_variants(db: &DbConn, media_id: i64, variants: Vec<(i32, String, i64)>) -> Result<(), DbErr> {
+        if variants.is_empty() {
+            return Ok(());
+        }
+
+        let models = variants.into_iter().map(|(width, storage_key, size)| media_variant::ActiveModel {
+            media_id: Set(media_id),
+            width: Set(width),
+            storage_key: Set(storage_key),
+            size: Set(size),
+            ..Default::default()
+        });
+        MediaVariant::insert_many(models).exec(db).await.map(|_| ())
+    }
+
+    pub async fn delete(db: &DbConn, id: i64) -> Result<DeleteResult, DbErr> {
+        Media::delete_by_id(id).exec(db).await
+    }
+}
@@ -0,0 +1,102 @@
+use entity::{article_media, article_media::Entity as ArticleMedia};
+use entity::{media, media::Entity as Media};
+use entity::{media_variant, media_variant::Entity as MediaVariant};
+use sea_orm::prelude::Expr;
+use sea_orm::{ColumnTrait, ConnectionTrait, DbConn, DbErr, EntityTrait, FromQueryResult, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect};
+
+pub struct MediaQuery;
+
+/// 媒体文件及引用它的文章数量
+#[derive(Debug, FromQueryResult)]
+pub struct MediaUsage {
+    pub id: i64,
+    pub owner_id: Option<i64>,
+    pub storage_key: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub created_at: sea_orm::prelude::DateTime,
+    pub usage_count: i64,
+}
+
+impl MediaQuery {
+    pub async fn find_media_by_id(db: &DbConn, id: i64) -> Result<Option<media::Model>, DbErr> {
+        Media::find_by_id(id).one(db).await
+    }
+
+    pub async fn find_media_by_key(db: &DbConn, key: &str) -> Result<Option<media::Model>, DbErr> {
+        Media::find_by_key(key).one(db).await
+    }
+
+    pub async fn find_variant_by_key(db: &DbConn, key: &str) -> Result<Option<media_variant::Model>, DbErr> {
+        MediaVariant::find_by_key(key).one(db).await
+    }
+
+    /// 按宽度从小到大返回给定媒体的全部变体
+    pub async fn list_variants(db: &DbConn, media_ids: Vec<i64>) -> Result<Vec<media_variant::Model>, DbErr> {
+        MediaVariant::find()
+            .filter(media_variant::Column::MediaId.is_in(media_ids))
+            .order_by_asc(media_variant::Column::Width)
+            .all(db)
+            .await
+    }
+
+    /// 正文中出现的文件 key 对应的媒体
+    pub async fn find_media_in_content<C: ConnectionTrait>(db: &C, content: &str) -> Result<Vec<media::Model>, DbErr> {
+        let keys = ::media::keys_in(content);
+        if keys.is_empty() {
+            return Ok(vec![]);
+        }
+        Media::find()
+            .filter(media::Column::StorageKey.is_in(keys))
+            .all(db)
+            .await
+    }
+
+    /// 正文中引用到的、已生成变体的图片
+    pub async fn find_images_in_content<C: ConnectionTrait>(
+        db: &C,
+        content: &str,
+    ) -> Result<Vec<(media::Model, Vec<media_variant::Model>)>, DbErr> {
+        let keys = ::media::keys_in(content);
+        if keys.is_empty() {
+            return Ok(vec![]);
+        }
+        Media::find()
+            .filter(media::Column::Width.is_not_null())
+            .filter(media::Column::StorageKey.is_in(keys))
+            .find_with_related(MediaVariant)
+            .order_by_asc(media_variant::Column::Width)
+            .all(db)
+            .await
+    }
+
+    /// If ok, returns (media with usage, num pages).
+    /// 引用数取自保存文章时写入的 `article_media`
+    pub async fn list_media_in_page(
+        db: &DbConn,
+        page: u64,
+        per_page: u64,
+    ) -> Result<(Vec<MediaUsage>, u64), DbErr> {
+        let paginator = Media::find()
+            .column_as(
+                Expr::cust("(SELECT COUNT(*) FROM article_media WHERE article_media.media_id = media.id)"),
+                "usage_count",
+            )
+            .order_by_desc(media::Column::CreatedAt)
+            .into_model::<MediaUsage>()
+            .paginate(db, per_page);
+        let num_pages = paginator.num_pages().await?;
+
+        paginator.fetch_page(page.saturating_sub(1)).await.map(|p| (p, num_pages))
+    }
+
+    pub async fn count_usage(db: &DbConn, media_id: i64) -> Result<u64, DbErr> {
+        ArticleMedia::find()
+            .filter(article_media::Column::MediaId.eq(media_id))
+            .count(db)
+            .await
+    }
+}
@@ -0,0 +1,27 @@
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use std::sync::LazyLock;
+
+/// 用户不存在时用来校验的哈希，参数与真实哈希相同
+static DUMMY_HASH: LazyLock<String> = LazyLock::new(|| hash_password("canvord-dummy-password").expect("hash dummy password"));
+
+/// 使用 Argon2id（默认参数）生成 PHC 格式的密码哈希
+pub fn hash_password(password: &str) -> Result<String, argon2::password_hash::Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+}
+
+/// 校验明文密码是否与存储的哈希匹配，哈希格式错误时视为不匹配
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .map(|parsed| Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+        .unwrap_or(false)
+}
+
+/// 用户不存在时调用，耗时与 [`verify_password`] 相同，避免从响应时间判断用户名是否存在
+pub fn verify_dummy_password(password: &str) {
+    verify_password(password, &DUMMY_HASH);
+}
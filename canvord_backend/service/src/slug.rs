@@ -0,0 +1,2 @@
+/// 与标题锚点使用同一套规则，定义在 render 中
+pub use render::slugify;
@@ -0,0 +1,81 @@
+use entity::article::Status::Published;
+use entity::{article, article::Entity as Article};
+use entity::{article_tag, tag, tag::Entity as Tag};
+use entity::{category, category::Entity as Category};
+use sea_orm::{
+    ColumnTrait, DbConn, DbErr, EntityTrait, FromQueryResult, JoinType, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, RelationTrait,
+};
+
+pub struct TagQuery;
+
+/// 标签及其已发布文章数量
+#[derive(Debug, FromQueryResult)]
+pub struct TagCount {
+    pub id: i64,
+    pub name: String,
+    pub slug: String,
+    pub article_count: i64,
+}
+
+impl TagQuery {
+    pub async fn find_tag_by_slug(db: &DbConn, slug: &str) -> Result<Option<tag::Model>, DbErr> {
+        Tag::find_by_slug(slug).one(db).await
+    }
+
+    /// 只统计已发布文章，没有已发布文章的标签不返回
+    pub async fn list_tags_with_count(db: &DbConn) -> Result<Vec<TagCount>, DbErr> {
+        Tag::find()
+            .select_only()
+            .column(tag::Column::Id)
+            .column(tag::Column::Name)
+            .column(tag::Column::Slug)
+            .column_as(article_tag::Column::ArticleId.count(), "article_count")
+            .join(JoinType::InnerJoin, tag::Relation::ArticleTag.def())
+            .join(JoinType::InnerJoin, article_tag::Relation::Article.def())
+            .filter(article::Column::Status.eq(Published))
+            .group_by(tag::Column::Id)
+            .group_by(tag::Column::Name)
+            .group_by(tag::Column::Slug)
+            .order_by_desc(article_tag::Column::ArticleId.count())
+            .order_by_asc(tag::Column::Name)
+            .into_model::<TagCount>()
+            .all(db)
+            .await
+    }
+
+    /// If ok, returns (article models with category, num pages).
+    pub async fn list_publish_articles_in_page_by_tag(
+        db: &DbConn,
+        tag_id: i64,
+        page: u64,
+        posts_per_page: u64,
+    ) -> Result<(Vec<(article::Model, Option<category::Model>)>, u64), DbErr> {
+        let paginator = Article::find()
+            .inner_join(Tag)
+            .filter(tag::Column::Id.eq(tag_id))
+            .filter(article::Column::Status.eq(Published))
+            .find_also_related(Category)
+            .order_by_asc(article::Column::CreatedAt)
+            .paginate(db, posts_per_page);
+        let num_pages = paginator.num_pages().await?;
+
+        paginator.fetch_page(page.saturating_sub(1)).await.map(|p| (p, num_pages))
+    }
+
+    /// 该标签下最近发布的文章，按创建时间倒序
+    pub async fn list_latest_publish_articles_by_tag(
+        db: &DbConn,
+        tag_id: i64,
+        limit: u64,
+    ) -> Result<Vec<article::Model>, DbErr> {
+        Article::find()
+            .inner_join(Tag)
+            .filter(tag::Column::Id.eq(tag_id))
+            .filter(article::Column::Status.eq(Published))
+            .order_by_desc(article::Column::CreatedAt)
+            .limit(limit)
+            .all(db)
+            .await
+    }
+}
@@ -0,0 +1,30 @@
+use crate::password::hash_password;
+use entity::user;
+use entity::user::Role;
+use sea_orm::sqlx::types::chrono::Utc;
+use sea_orm::{ActiveModelTrait, DbConn, DbErr, Set};
+
+pub struct UserMutation;
+
+impl UserMutation {
+    pub async fn create(db: &DbConn, username: &str, password: &str, role: Role) -> Result<user::Model, DbErr> {
+        // 与登录校验一样，Argon2 在阻塞线程池中执行
+        let password = password.to_owned();
+        let password_hash = tokio::task::spawn_blocking(move || hash_password(&password))
+            .await
+            .map_err(|e| DbErr::Custom(format!("Failed to hash password: {e}")))?
+            .map_err(|e| DbErr::Custom(format!("Failed to hash password: {e}")))?;
+        let now = Utc::now().naive_utc();
+
+        user::ActiveModel {
+            username: Set(username.to_owned()),
+            password_hash: Set(password_hash),
+            role: Set(role),
+            created_at: Set(now),
+            last_update: Set(now),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+    }
+}
@@ -0,0 +1,18 @@
+use entity::{user, user::Entity as User};
+use sea_orm::{DbConn, DbErr, EntityTrait, PaginatorTrait};
+
+pub struct UserQuery;
+
+impl UserQuery {
+    pub async fn find_user_by_id(db: &DbConn, id: i64) -> Result<Option<user::Model>, DbErr> {
+        User::find_by_id(id).one(db).await
+    }
+
+    pub async fn find_user_by_username(db: &DbConn, username: &str) -> Result<Option<user::Model>, DbErr> {
+        User::find_by_username(username).one(db).await
+    }
+
+    pub async fn count_users(db: &DbConn) -> Result<u64, DbErr> {
+        User::find().count(db).await
+    }
+}
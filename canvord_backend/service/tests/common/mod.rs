@@ -0,0 +1,60 @@
+// 每个测试文件只用到其中一部分
+#![allow(dead_code)]
+
+use entity::article::{self, Status};
+use sea_orm::sqlx::types::chrono::{NaiveDate, NaiveDateTime};
+use sea_orm::{ActiveModelTrait, ConnectOptions, ConnectionTrait, Database, DbConn, Schema, Set};
+
+pub fn at(hour: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2025, 8, 9).unwrap().and_hms_opt(hour, 0, 0).unwrap()
+}
+
+/// 内存中的 SQLite，只有一个连接，表结构由实体生成
+pub async fn db() -> DbConn {
+    let mut options = ConnectOptions::new("sqlite::memory:");
+    options.max_connections(1);
+    let db = Database::connect(options).await.unwrap();
+    let schema = Schema::new(db.get_database_backend());
+    macro_rules! create {
+        ($($entity:path),*) => {$(
+            db.execute(db.get_database_backend().build(&schema.create_table_from_entity($entity))).await.unwrap();
+        )*};
+    }
+    create!(
+        entity::category::Entity,
+        entity::article::Entity,
+        entity::article_revision::Entity,
+        entity::tag::Entity,
+        entity::article_tag::Entity,
+        entity::media::Entity,
+        entity::article_media::Entity
+    );
+    db
+}
+
+pub async fn insert(db: &DbConn, slug: &str, status: Status, publish_at: Option<NaiveDateTime>) -> article::Model {
+    article::ActiveModel {
+        title: Set(slug.to_owned()),
+        slug: Set(slug.to_owned()),
+        description: Set(String::new()),
+        content_md: Set("正文".to_owned()),
+        content_html: Set(None),
+        toc: Set(None),
+        word_count: Set(0),
+        reading_minutes: Set(0),
+        render_version: Set(0),
+        category_id: Set(None),
+        created_at: Set(at(0)),
+        last_update: Set(at(0)),
+        status: Set(status),
+        publish_at: Set(publish_at),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .unwrap()
+}
+
+pub async fn find(db: &DbConn, id: i64) -> article::Model {
+    article::Entity::find_by_id(id).one(db).await.unwrap().unwrap()
+}
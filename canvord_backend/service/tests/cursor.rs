@@ -0,0 +1,34 @@
+use canvord_service::article_query::ArticleQuery;
+use entity::article::Status;
+
+mod common;
+use common::{at, db, insert};
+
+#[tokio::test]
+async fn inclusive_position_keeps_the_boundary_article() {
+    let db = db().await;
+    // 发布时间相同，按 id 排序
+    let mut ids = Vec::new();
+    for slug in ["a", "b", "c"] {
+        ids.push(insert(&db, slug, Status::Published, None).await.id);
+    }
+    let page = |position, forward| {
+        let db = &db;
+        async move {
+            let (articles, has_more) = ArticleQuery::list_articles_by_cursor(db, None, position, forward, 2).await.unwrap();
+            (articles.into_iter().map(|(m, _)| m.id).collect::<Vec<_>>(), has_more)
+        }
+    };
+
+    assert_eq!(page(None, true).await, (vec![ids[0], ids[1]], true));
+    assert_eq!(page(Some((at(0), ids[1], false)), true).await, (vec![ids[2]], false));
+
+    // 最后一篇之后是空页，从该位置反向翻页时需要包含它
+    assert_eq!(page(Some((at(0), ids[2], false)), true).await, (vec![], false));
+    assert_eq!(page(Some((at(0), ids[2], false)), false).await, (vec![ids[0], ids[1]], false));
+    assert_eq!(page(Some((at(0), ids[2], true)), false).await, (vec![ids[1], ids[2]], true));
+
+    // 第一篇之前同理
+    assert_eq!(page(Some((at(0), ids[0], false)), false).await, (vec![], false));
+    assert_eq!(page(Some((at(0), ids[0], true)), true).await, (vec![ids[0], ids[1]], true));
+}
@@ -0,0 +1,65 @@
+use canvord_service::article_mutation::ArticleMutation;
+use canvord_service::error::MutationError;
+use command::save_article_command::SaveArticleCommand;
+use command::update_article_command::UpdateArticleCommand;
+use entity::article::Status;
+use sea_orm::TryIntoModel;
+
+mod common;
+use common::{db, find};
+
+const AUTHOR: i64 = 7;
+const OTHER: i64 = 8;
+
+fn draft(slug: &str) -> SaveArticleCommand {
+    SaveArticleCommand {
+        title: slug.into(),
+        slug: slug.into(),
+        description: String::new(),
+        category_id: None,
+        content_md: "正文".into(),
+        tags: Vec::new(),
+    }
+}
+
+fn update(id: i64, title: &str, status: Status) -> UpdateArticleCommand {
+    UpdateArticleCommand {
+        id,
+        title: title.into(),
+        slug: format!("article-{id}"),
+        description: String::new(),
+        category_id: None,
+        content_md: "正文".into(),
+        tags: None,
+        status,
+    }
+}
+
+#[tokio::test]
+async fn authors_only_edit_their_own_drafts() {
+    let db = db().await;
+    let own = ArticleMutation::save_draft(&db, draft("own"), AUTHOR).await.unwrap().try_into_model().unwrap();
+    let others = ArticleMutation::save_draft(&db, draft("others"), OTHER).await.unwrap().try_into_model().unwrap();
+    assert_eq!(own.author_id, Some(AUTHOR));
+
+    let edited = ArticleMutation::update(&db, update(own.id, "edited", Status::Unpublished), Some(AUTHOR)).await.unwrap();
+    assert_eq!(edited.title, "edited");
+    // 修改后仍记录原来的创建者
+    assert_eq!(edited.author_id, Some(AUTHOR));
+
+    let err = ArticleMutation::update(&db, update(others.id, "stolen", Status::Unpublished), Some(AUTHOR)).await.unwrap_err();
+    assert!(matches!(err, MutationError::Forbidden(_)), "{err:?}");
+    assert_eq!(find(&db, others.id).await.title, "others");
+
+    // 作者不能通过修改接口发布
+    let err = ArticleMutation::update(&db, update(own.id, "edited", Status::Published), Some(AUTHOR)).await.unwrap_err();
+    assert!(matches!(err, MutationError::Forbidden(_)), "{err:?}");
+    assert_eq!(find(&db, own.id).await.status, Status::Unpublished);
+
+    // 有编辑权限时不检查创建者
+    ArticleMutation::update(&db, update(others.id, "edited", Status::Published), None).await.unwrap();
+
+    // 已发布的文章不再是草稿，创建者也不能修改
+    let err = ArticleMutation::update(&db, update(others.id, "edited", Status::Unpublished), Some(OTHER)).await.unwrap_err();
+    assert!(matches!(err, MutationError::Forbidden(_)), "{err:?}");
+}
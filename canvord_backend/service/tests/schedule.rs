@@ -0,0 +1,100 @@
+use canvord_service::article_mutation::ArticleMutation;
+use canvord_service::error::MutationError;
+use command::cancel_schedule_command::CancelScheduleCommand;
+use command::delete_article_command::DeleteArticleCommand;
+use command::hide_article_command::HideArticleCommand;
+use command::publish_article_command::PublishArticleCommand;
+use command::schedule_article_command::ScheduleArticleCommand;
+use command::update_article_command::UpdateArticleCommand;
+use entity::article::Status;
+use sea_orm::DbErr;
+
+mod common;
+use common::{at, db, find, insert};
+
+#[tokio::test]
+async fn publish_due_only_publishes_articles_that_are_due() {
+    let db = db().await;
+    let due = insert(&db, "due", Status::Scheduled, Some(at(9))).await;
+    let later = insert(&db, "later", Status::Scheduled, Some(at(11))).await;
+    let draft = insert(&db, "draft", Status::Unpublished, None).await;
+
+    let published = ArticleMutation::publish_due(&db, at(10)).await.unwrap();
+    assert_eq!(published.iter().map(|m| m.id).collect::<Vec<_>>(), [due.id]);
+    assert_eq!(published[0].status, Status::Published);
+
+    let due = find(&db, due.id).await;
+    assert_eq!((due.status, due.publish_at, due.last_update), (Status::Published, None, at(10)));
+    assert_eq!(find(&db, later.id).await.status, Status::Scheduled);
+    assert_eq!(find(&db, draft.id).await.status, Status::Unpublished);
+
+    // 再次执行不会重复发布
+    assert!(ArticleMutation::publish_due(&db, at(10)).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn schedule_and_cancel() {
+    let db = db().await;
+    let draft = insert(&db, "draft", Status::Unpublished, None).await;
+
+    let scheduled = ArticleMutation::schedule(&db, ScheduleArticleCommand { id: draft.id, publish_at: at(12) }).await.unwrap();
+    assert_eq!((scheduled.status, scheduled.publish_at), (Status::Scheduled, Some(at(12))));
+
+    // 已定时的文章可以修改时间
+    let moved = at(13);
+    let scheduled = ArticleMutation::schedule(&db, ScheduleArticleCommand { id: draft.id, publish_at: moved }).await.unwrap();
+    assert_eq!(scheduled.publish_at, Some(moved));
+
+    let cancelled = ArticleMutation::cancel_schedule(&db, CancelScheduleCommand { id: draft.id }).await.unwrap();
+    assert_eq!((cancelled.status, cancelled.publish_at), (Status::Unpublished, None));
+
+    // 取消之后不会再被发布
+    assert!(ArticleMutation::publish_due(&db, moved).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn invalid_transitions_are_client_errors() {
+    let db = db().await;
+    let published = insert(&db, "published", Status::Published, None).await;
+    let draft = insert(&db, "draft", Status::Unpublished, None).await;
+
+    let err = ArticleMutation::schedule(&db, ScheduleArticleCommand { id: published.id, publish_at: at(12) }).await.unwrap_err();
+    assert!(matches!(err, MutationError::Conflict(_)), "{err:?}");
+
+    let err = ArticleMutation::cancel_schedule(&db, CancelScheduleCommand { id: draft.id }).await.unwrap_err();
+    assert!(matches!(err, MutationError::Conflict(_)), "{err:?}");
+
+    let err = ArticleMutation::cancel_schedule(&db, CancelScheduleCommand { id: 404 }).await.unwrap_err();
+    assert!(matches!(err, MutationError::Db(DbErr::RecordNotFound(_))), "{err:?}");
+
+    let update = UpdateArticleCommand {
+        id: draft.id,
+        title: "draft".into(),
+        slug: "draft".into(),
+        description: String::new(),
+        category_id: None,
+        content_md: "正文".into(),
+        tags: None,
+        status: Status::Scheduled,
+    };
+    let err = ArticleMutation::update(&db, update, None).await.unwrap_err();
+    assert!(matches!(err, MutationError::Invalid(_)), "{err:?}");
+    assert_eq!(find(&db, draft.id).await.status, Status::Unpublished);
+}
+
+#[tokio::test]
+async fn status_changes_clear_schedule() {
+    let db = db().await;
+    let hidden = insert(&db, "hidden", Status::Scheduled, Some(at(12))).await;
+    let published = insert(&db, "published", Status::Scheduled, Some(at(12))).await;
+
+    let hidden = ArticleMutation::hide(&db, HideArticleCommand { id: hidden.id }).await.unwrap();
+    assert_eq!((hidden.status, hidden.publish_at), (Status::Hidden, None));
+    let published = ArticleMutation::publish(&db, PublishArticleCommand { id: published.id }).await.unwrap();
+    assert_eq!((published.status, published.publish_at), (Status::Published, None));
+    assert!(ArticleMutation::publish_due(&db, at(12)).await.unwrap().is_empty());
+
+    assert_eq!(ArticleMutation::delete(&db, DeleteArticleCommand { id: hidden.id }).await.unwrap().rows_affected, 1);
+    let err = ArticleMutation::delete(&db, DeleteArticleCommand { id: hidden.id }).await.unwrap_err();
+    assert!(matches!(err, DbErr::RecordNotFound(_)), "{err:?}");
+}
@@ -3,6 +3,7 @@ use gloo_net::http::Request;
 use crate::model::*;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
+use sycamore::web::js_sys;
 
 const API_BASE: &str = "http://localhost:8000/visitor";
 
@@ -59,10 +60,31 @@ pub async fn get_article_by_slug(slug: &str) -> Result<AppResponse<ArticleDetail
     get_json(&format!("{API_BASE}/slug/{}", slug)).await
 }
 
-pub async fn get_article_by_title(title: &str) -> Result<AppResponse<Vec<ArticleMeta>>, String> {
-    get_json(&format!("{API_BASE}/title/{}", title)).await
+pub async fn search_articles(q: &str, page: u64, per: u64) -> Result<AppResponse<PageResult<SearchHit>>, String> {
+    let q = String::from(js_sys::encode_uri_component(q));
+    get_json(&format!("{API_BASE}/search?q={}&page={}&per={}", q, page, per)).await
 }
 
-pub async fn get_article_page(page: u64, per: u64) -> Result<AppResponse<PageResult<ArticleMeta>>, String> {
-    get_json(&format!("{API_BASE}/page?page={}&per={}", page, per)).await
+/// `cursor` 为上次结果中的 `next` 或 `prev`，为 `None` 时返回第一页
+pub async fn get_article_cursor_page(cursor: Option<&str>, per: u64) -> Result<AppResponse<CursorPage<ArticleMeta>>, String> {
+    match cursor {
+        Some(cursor) => get_json(&format!("{API_BASE}/articles?cursor={}&per={}", cursor, per)).await,
+        None => get_json(&format!("{API_BASE}/articles?per={}", per)).await,
+    }
+}
+
+pub async fn list_tags() -> Result<AppResponse<Vec<TagWithCount>>, String> {
+    get_json(&format!("{API_BASE}/tags")).await
+}
+
+pub async fn get_article_page_by_tag(slug: &str, page: u64, per: u64) -> Result<AppResponse<PageResult<ArticleMeta>>, String> {
+    get_json(&format!("{API_BASE}/tag/{}/page?page={}&per={}", slug, page, per)).await
+}
+
+pub async fn list_categories() -> Result<AppResponse<Vec<CategoryNode>>, String> {
+    get_json(&format!("{API_BASE}/categories")).await
+}
+
+pub async fn get_article_page_by_category(slug: &str, page: u64, per: u64) -> Result<AppResponse<PageResult<ArticleMeta>>, String> {
+    get_json(&format!("{API_BASE}/category/{}/page?page={}&per={}", slug, page, per)).await
 }
@@ -9,5 +9,16 @@ mod component;
 
 fn main() {
     console_error_panic_hook::set_once();
+    remove_prerendered();
     sycamore::render(App);
 }
+
+/// 预渲染的页面内容只给爬虫和不执行 JavaScript 的读者看，客户端启动后由路由重新渲染
+fn remove_prerendered() {
+    if let Some(el) = web_sys::window()
+        .and_then(|win| win.document())
+        .and_then(|doc| doc.get_element_by_id("prerender"))
+    {
+        el.remove();
+    }
+}
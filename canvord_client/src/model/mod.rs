@@ -14,7 +14,7 @@ pub struct ArticleMeta {
     pub title: String,
     pub slug: String,
     pub description: String,
-    pub category: String,
+    pub category: Option<CategoryInfo>,
     pub last_update: NaiveDateTime,
     pub status: Status,
 }
@@ -25,6 +25,60 @@ pub struct ArticleDetail {
     pub meta: ArticleMeta,
     pub content_md: String,
     pub created_at: NaiveDateTime,
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    pub content_html: String,
+    #[serde(default)]
+    pub toc: Vec<TocEntry>,
+    pub word_count: i32,
+    pub reading_minutes: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TocEntry {
+    pub level: u8,
+    pub id: String,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CategoryInfo {
+    pub id: i64,
+    pub name: String,
+    pub slug: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CategoryNode {
+    pub id: i64,
+    pub parent_id: Option<i64>,
+    pub name: String,
+    pub slug: String,
+    pub description: String,
+    pub children: Vec<CategoryNode>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,
+    pub slug: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TagWithCount {
+    #[serde(flatten)]
+    pub tag: Tag,
+    pub article_count: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    #[serde(flatten)]
+    pub meta: ArticleMeta,
+    pub score: f32,
+    pub title_html: String,
+    pub snippet_html: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -35,6 +89,14 @@ pub struct PageResult<T> {
     pub data: Vec<T>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CursorPage<T> {
+    pub size: usize,
+    pub data: Vec<T>,
+    pub next: Option<String>,
+    pub prev: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AppResponse<T> {
     pub code: i32,
@@ -1,9 +1,8 @@
 use std::borrow::Cow;
-use pulldown_cmark::{Options, Parser};
-use pulldown_cmark::html::push_html;
 use sycamore::{component, view};
 use sycamore::futures::spawn_local_scoped;
 use sycamore::prelude::*;
+use sycamore::web::wasm_bindgen::JsCast;
 use crate::api::get_article_by_slug;
 use crate::model::ArticleDetail;
 
@@ -17,23 +16,16 @@ enum LoadStatus {
 #[component]
 pub fn ArticleView(slug: String) -> View {
     let article = create_signal(None::<ArticleDetail>);
-    let html_content = create_signal(String::new());
     let load_status = create_signal(LoadStatus::Loading);
 
     spawn_local_scoped({
         let article = article.clone();
-        let html_content = html_content.clone();
         let load_status = load_status.clone();
 
         async move {
             match get_article_by_slug(&slug).await {
                 Ok(resp) => {
                     if let Some(detail) = resp.data {
-                        let parser = Parser::new_ext(&detail.content_md, Options::all());
-                        let mut html = String::new();
-                        push_html(&mut html, parser);
-
-                        html_content.set(html);
                         article.set(Some(detail));
                         load_status.set(LoadStatus::Success);
                     } else {
@@ -48,22 +40,59 @@ pub fn ArticleView(slug: String) -> View {
     });
 
     view! {
-        div(class="min-h-screen bg-[url('/bg.webp')] bg-cover bg-fixed bg-center bg-no-repeat text-white flex justify-center px-4 py-8") {
+        div(class="min-h-screen bg-[url('/bg-768.webp')] md:bg-[url('/bg.webp')] bg-cover bg-fixed bg-center bg-no-repeat text-white flex justify-center px-4 py-8") {
             (match load_status.get_clone() {
                 LoadStatus::Loading => view! {
                     div(class="text-lg text-neutral-400") { "加载中..." }
                 },
                 LoadStatus::Success => {
                     if let Some(article) = article.get_clone() {
-                        let html: Cow<'static, str> = html_content.get_clone().clone().into();
+                        let html: Cow<'static, str> = with_copy_buttons(&article.content_html).into();
+                        let toc = article.toc.clone();
+                        // 只有一个标题时目录没有意义
+                        let has_toc = toc.len() > 1;
                         view! {
                             div(class="bg-white text-neutral-900 max-w-3xl w-full p-8 rounded-xl shadow-lg bg-opacity-90") {
                                 h1(class="text-3xl font-bold mb-4") { (article.meta.title.clone()) }
                                 p(class="text-xs text-neutral-400 mb-4 italic") {
                                     "最后更新："
                                     (article.meta.last_update.format("%Y-%m-%d %H:%M:%S").to_string())
+                                    (article.meta.category.as_ref().map(|c| format!("　分类：{}", c.name)).unwrap_or_default())
+                                    (format!("　约 {} 字，阅读需 {} 分钟", article.word_count, article.reading_minutes))
+                                }
+                                div(class="flex flex-wrap gap-2 mb-4") {
+                                    Indexed(
+                                        list=article.tags.clone(),
+                                        view=|tag| view! {
+                                            span(class="text-xs bg-neutral-200 text-neutral-700 px-2 py-0.5 rounded") {
+                                                "#" (tag.name)
+                                            }
+                                        },
+                                    )
                                 }
-                                div(class="prose max-w-none", dangerously_set_inner_html=html)
+                                (if has_toc {
+                                    view! {
+                                        nav(class="text-sm bg-neutral-100 rounded p-4 mb-6") {
+                                            p(class="font-semibold mb-2") { "目录" }
+                                            ul {
+                                                Indexed(
+                                                    list=toc,
+                                                    view=|entry| {
+                                                        let indent = format!("padding-left: {}rem", entry.level.saturating_sub(1) as f32 * 0.75);
+                                                        view! {
+                                                            li(style=indent) {
+                                                                a(class="text-neutral-600 hover:text-neutral-900", href=format!("#{}", entry.id)) { (entry.text) }
+                                                            }
+                                                        }
+                                                    },
+                                                )
+                                            }
+                                        }
+                                    }
+                                } else {
+                                    view! {}
+                                })
+                                div(class="prose max-w-none", on:click=copy_code, dangerously_set_inner_html=html)
                             }
                         }
                     } else {
@@ -79,3 +108,26 @@ pub fn ArticleView(slug: String) -> View {
         }
     }
 }
+
+/// 复制按钮只在页面上添加，不写入后端保存的 HTML
+fn with_copy_buttons(html: &str) -> String {
+    html.replace(
+        r#"<pre class="hl-code">"#,
+        r#"<pre class="hl-code"><button type="button" class="copy-code">复制</button>"#,
+    )
+}
+
+/// 正文内容是整体插入的，点击事件统一在外层处理
+fn copy_code(e: web_sys::MouseEvent) {
+    let Some(button) = e.target()
+        .and_then(|t| t.dyn_into::<web_sys::Element>().ok())
+        .and_then(|el| el.closest(".copy-code").ok().flatten()) else { return };
+    let Some(code) = button.parent_element()
+        .and_then(|pre| pre.query_selector("code").ok().flatten())
+        .and_then(|code| code.text_content()) else { return };
+
+    if let Some(win) = web_sys::window() {
+        let _ = win.navigator().clipboard().write_text(&code);
+        button.set_text_content(Some("已复制"));
+    }
+}
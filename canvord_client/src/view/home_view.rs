@@ -5,13 +5,14 @@ use sycamore::web::{console_error, js_sys};
 use sycamore::web::rt::web_sys;
 use sycamore::web::wasm_bindgen::JsCast;
 use sycamore_router::navigate;
-use crate::api::{get_article_by_title, get_article_page};
-use crate::model::{ArticleMeta};
+use crate::api::{get_article_cursor_page, get_article_page_by_category, list_categories, search_articles};
+use crate::model::{ArticleMeta, CategoryNode, SearchHit};
 
 #[derive(Clone, PartialEq, Debug)]
 enum DisplayMode {
     Paginated,
-    Search,
+    Category(String),
+    Search(String),
 }
 
 #[component]
@@ -19,23 +20,65 @@ pub fn HomeView() -> View {
     let current_page = create_signal(1);
     let page_size = create_signal(42);
     let total_pages = create_signal(1);
+    // 全部文章按游标翻页，只能前后翻页，不显示总页数
+    let cursor = create_signal(None::<String>);
+    let next_cursor = create_signal(None::<String>);
+    let prev_cursor = create_signal(None::<String>);
     let search_query = create_signal(String::new());
     let articles = create_signal(Vec::<ArticleMeta>::new());
+    let hits = create_signal(Vec::<SearchHit>::new());
     let display_mode = create_signal(DisplayMode::Paginated);
+    let categories = create_signal(Vec::<CategoryNode>::new());
+
+    spawn_local_scoped(async move {
+        match list_categories().await {
+            Ok(resp) => categories.set(resp.data.unwrap_or_default()),
+            Err(err) => console_error!("获取分类失败: {}", err),
+        }
+    });
 
     // Effect for fetching paginated articles when page or mode changes.
     create_effect( move || {
-        // Only fetch if in paginated mode.
-        if display_mode.get_clone() != DisplayMode::Paginated {
-            return;
-        }
+        let mode = display_mode.get_clone();
 
         // This effect depends on current_page, so it re-runs when the page changes.
         let page = current_page.get();
         let size = page_size.get();
+        let position = cursor.get_clone();
 
         spawn_local_scoped( async move {
-            match get_article_page(page, size).await {
+            if let DisplayMode::Search(query) = mode {
+                match search_articles(&query, page, size).await {
+                    Ok(resp) => {
+                        if let Some(data) = resp.data {
+                            total_pages.set(data.total);
+                            hits.set(data.data);
+                        }
+                    }
+                    Err(err) => {
+                        console_error!("搜索失败: {}", err);
+                        hits.set(vec![]);
+                    }
+                }
+                return;
+            }
+
+            if mode == DisplayMode::Paginated {
+                match get_article_cursor_page(position.as_deref(), size).await {
+                    Ok(resp) => {
+                        if let Some(data) = resp.data {
+                            next_cursor.set(data.next);
+                            prev_cursor.set(data.prev);
+                            articles.set(data.data);
+                        }
+                    }
+                    Err(err) => console_error!("获取文章失败: {}", err),
+                }
+                return;
+            }
+
+            let DisplayMode::Category(slug) = mode else { return };
+            match get_article_page_by_category(&slug, page, size).await {
                 Ok(resp) => {
                     if let Some(data) = resp.data {
                         // Assuming data.total is the number of pages.
@@ -48,28 +91,25 @@ pub fn HomeView() -> View {
         });
     });
 
-    // Action for handling search.
-    let search_articles = move || {
+    // Action for handling search; results are fetched by the effect above.
+    let search = move || {
         let query = search_query.get_clone().trim().to_string();
+        current_page.set(1);
+        cursor.set(None);
         if query.is_empty() {
             display_mode.set(DisplayMode::Paginated);
-            return;
+        } else {
+            display_mode.set(DisplayMode::Search(query));
         }
-
-        display_mode.set(DisplayMode::Search);
-        spawn_local_scoped(async move {
-            match get_article_by_title(&query).await {
-                Ok(resp) => {
-                    articles.set(resp.data.unwrap_or_default());
-                }
-                Err(err) => {
-                    console_error!("搜索失败: {}", err);
-                    articles.set(vec![]);
-                }
-            }
-        });
     };
 
+    // Callback for when a category is selected; `None` shows all articles.
+    let on_category = Rc::new(move |slug: Option<String>| {
+        current_page.set(1);
+        cursor.set(None);
+        display_mode.set(slug.map_or(DisplayMode::Paginated, DisplayMode::Category));
+    });
+
     // Callback for when a card is selected.
     let on_select = Rc::new(move |slug: String| {
         navigate(&format!("/article/{}", slug));
@@ -79,95 +119,136 @@ pub fn HomeView() -> View {
         div(class="grid h-screen w-screen bg-neutral-900 grid-cols-[7fr_25px_1fr] grid-rows-[3fr_15px_1fr]") {
             // Left-Top Block: Article List
             div(class="bg-black p-4 h-full overflow-y-auto") {
-                CardList(articles=articles, on_select=on_select)
+                ({
+                    let on_select = on_select.clone();
+                    if matches!(display_mode.get_clone(), DisplayMode::Search(_)) {
+                        view! { SearchResultList(hits=hits, on_select=on_select) }
+                    } else {
+                        view! { CardList(articles=articles, on_select=on_select) }
+                    }
+                })
             }
 
             // Right-Top Block: Search & Info
-            div(class="grid grid-rows-[1fr_2fr] gap-2 h-full w-full") {
+            div(class="grid grid-rows-[auto_1fr_auto] gap-2 h-full w-full") {
                 // Top part: Search and Pagination
                 div(class="bg-orange-200 p-4 flex flex-col space-y-4") {
                     div(class="flex space-x-2") {
                         input(
-                            placeholder="搜索文章标题...",
+                            placeholder="搜索文章...",
                             bind:value=search_query,
                             class="w-full px-3 py-1 bg-white text-black border-2 border-black shadow-none focus:outline-none focus:ring-0 placeholder-gray-600",
                             on:keypress=move |e: web_sys::KeyboardEvent| {
                                 if e.key() == "Enter" {
-                                    search_articles();
+                                    search();
                                 }
                             }
                         )
-                        button(class="bg-gray-700 text-white px-4 py-1 border-2 border-black hover:bg-gray-800", on:click=move |_| search_articles()) { "搜索" }
+                        button(class="bg-gray-700 text-white px-4 py-1 border-2 border-black hover:bg-gray-800", on:click=move |_| search()) { "搜索" }
                     }
 
-                    (if display_mode.get_clone() == DisplayMode::Paginated {
-                        view! {
-                            div(class="flex flex-wrap items-center gap-3 text-sm text-black") {
-                                // 上一页按钮
-                                button(
-                                    class="bg-red-600 text-white px-4 py-1 border-2 border-black hover:bg-red-700 disabled:opacity-50",
-                                    disabled=current_page.get() <= 1,
-                                    on:click=move |_| {
-                                        current_page.set(current_page.get() - 1);
-                                    }
-                                ) { "上一页" }
-                            
-                                // 下一页按钮
-                                button(
-                                    class="bg-blue-600 text-white px-4 py-1 border-2 border-black hover:bg-blue-700 disabled:opacity-50",
-                                    disabled=current_page.get() >= total_pages.get() as u64,
-                                    on:click=move |_| {
-                                        current_page.set(current_page.get() + 1);
-                                    }
-                                ) { "下一页" }
-                            
-                                // 跳页输入框
-                                input(
-                                    r#type="number",
-                                    min="1",
-                                    max=total_pages.get().to_string(),
-                                    class="w-16 px-2 py-1 border border-black text-black",
-                                    placeholder="页码",
-                                    on:change=move |e: web_sys::Event| {
-                                        if let Some(input) = e.target()
-                                            .and_then(|t| t.dyn_into::<web_sys::HtmlInputElement>().ok()) {
-                                            if let Ok(page) = input.value().parse::<usize>() {
-                                                if page >= 1 && page <= total_pages.get() {
-                                                    current_page.set(page as u64);
-                                                }
-                                            }
-                                        }
-                                    }
-                                )
-                            
-                                // 选择页面大小
-                                select(
-                                    class="px-2 py-1 border border-black text-black bg-white",
-                                    on:change=move |e: web_sys::Event| {
-                                        if let Some(select) = e.target()
-                                            .and_then(|t| t.dyn_into::<web_sys::HtmlSelectElement>().ok()) {
-                                            if let Ok(size) = select.value().parse::<usize>() {
-                                                page_size.set(size as u64);
-                                                current_page.set(1); // 重置到第一页
-                                            }
+                    (if let DisplayMode::Search(query) = display_mode.get_clone() {
+                        view! { div(class="text-sm text-black") { (format!("“{}” 的搜索结果", query)) } }
+                    } else {
+                        view! {}
+                    })
+
+                    div(class="flex flex-wrap items-center gap-3 text-sm text-black") {
+                        // 上一页按钮
+                        button(
+                            class="bg-red-600 text-white px-4 py-1 border-2 border-black hover:bg-red-700 disabled:opacity-50",
+                            disabled=if display_mode.get_clone() == DisplayMode::Paginated {
+                                prev_cursor.with(Option::is_none)
+                            } else {
+                                current_page.get() <= 1
+                            },
+                            on:click=move |_| {
+                                if display_mode.get_clone() == DisplayMode::Paginated {
+                                    cursor.set(prev_cursor.get_clone());
+                                } else {
+                                    current_page.set(current_page.get() - 1);
+                                }
+                            }
+                        ) { "上一页" }
+                    
+                        // 下一页按钮
+                        button(
+                            class="bg-blue-600 text-white px-4 py-1 border-2 border-black hover:bg-blue-700 disabled:opacity-50",
+                            disabled=if display_mode.get_clone() == DisplayMode::Paginated {
+                                next_cursor.with(Option::is_none)
+                            } else {
+                                current_page.get() >= total_pages.get() as u64
+                            },
+                            on:click=move |_| {
+                                if display_mode.get_clone() == DisplayMode::Paginated {
+                                    cursor.set(next_cursor.get_clone());
+                                } else {
+                                    current_page.set(current_page.get() + 1);
+                                }
+                            }
+                        ) { "下一页" }
+                    
+                        // 跳页输入框，游标翻页时没有页码
+                        input(
+                            r#type="number",
+                            min="1",
+                            max=total_pages.get().to_string(),
+                            class=if display_mode.get_clone() == DisplayMode::Paginated {
+                                "hidden"
+                            } else {
+                                "w-16 px-2 py-1 border border-black text-black"
+                            },
+                            placeholder="页码",
+                            on:change=move |e: web_sys::Event| {
+                                if let Some(input) = e.target()
+                                    .and_then(|t| t.dyn_into::<web_sys::HtmlInputElement>().ok()) {
+                                    if let Ok(page) = input.value().parse::<usize>() {
+                                        if page >= 1 && page <= total_pages.get() {
+                                            current_page.set(page as u64);
                                         }
                                     }
-                                ) {
-                                    option(value="10", selected=page_size.get() == 10) { "每页10" }
-                                    option(value="20", selected=page_size.get() == 20) { "每页20" }
-                                    option(value="42", selected=page_size.get() == 42) { "每页42" }
-                                    option(value="100", selected=page_size.get() == 100) { "每页100" }
                                 }
-                            
-                                // 当前页 / 总页数
-                                span(class="text-sm") {
-                                    (format!("第 {} 页 / 共 {} 页", current_page.get(), total_pages.get()))
+                            }
+                        )
+                    
+                        // 选择页面大小
+                        select(
+                            class="px-2 py-1 border border-black text-black bg-white",
+                            on:change=move |e: web_sys::Event| {
+                                if let Some(select) = e.target()
+                                    .and_then(|t| t.dyn_into::<web_sys::HtmlSelectElement>().ok()) {
+                                    if let Ok(size) = select.value().parse::<usize>() {
+                                        page_size.set(size as u64);
+                                        current_page.set(1); // 重置到第一页
+                                        cursor.set(None);
+                                    }
                                 }
                             }
+                        ) {
+                            option(value="10", selected=page_size.get() == 10) { "每页10" }
+                            option(value="20", selected=page_size.get() == 20) { "每页20" }
+                            option(value="42", selected=page_size.get() == 42) { "每页42" }
+                            option(value="100", selected=page_size.get() == 100) { "每页100" }
                         }
-                    } else {
-                        view! { div(class="text-sm text-black") { "搜索结果" } }
-                    })
+                    
+                        // 当前页 / 总页数
+                        span(class=if display_mode.get_clone() == DisplayMode::Paginated { "hidden" } else { "text-sm" }) {
+                            (format!("第 {} 页 / 共 {} 页", current_page.get(), total_pages.get()))
+                        }
+                    }
+                }
+
+                // Middle part: Category Tree
+                div(class="bg-white p-4 overflow-y-auto text-neutral-900") {
+                    h2(class="text-lg font-bold mb-2") { "分类" }
+                    button(
+                        class="text-sm hover:underline",
+                        on:click={
+                            let on_category = on_category.clone();
+                            move |_| on_category(None)
+                        }
+                    ) { "全部" }
+                    (category_tree(categories.get_clone(), on_category.clone()))
                 }
 
                 // Bottom part: Personal Intro
@@ -208,6 +289,29 @@ pub fn HomeView() -> View {
     }
 }
 
+fn category_tree(nodes: Vec<CategoryNode>, on_category: Rc<dyn Fn(Option<String>)>) -> View {
+    let items: Vec<View> = nodes
+        .into_iter()
+        .map(|node| {
+            let slug = node.slug.clone();
+            let on_click = on_category.clone();
+            let children = category_tree(node.children, on_category.clone());
+            view! {
+                li {
+                    button(
+                        class="text-sm hover:underline",
+                        title=node.description,
+                        on:click=move |_| on_click(Some(slug.clone()))
+                    ) { (node.name) }
+                    (children)
+                }
+            }
+        })
+        .collect();
+
+    view! { ul(class="pl-3 space-y-1") { (items) } }
+}
+
 #[component(inline_props)]
 pub fn CardList(
     articles: Signal<Vec<ArticleMeta>>,
@@ -246,4 +350,39 @@ pub fn CardList(
             )
         }
     }
-}
\ No newline at end of file
+}
+#[component(inline_props)]
+pub fn SearchResultList(
+    hits: Signal<Vec<SearchHit>>,
+    on_select: Rc<dyn Fn(String)>
+) -> View {
+    view! {
+        div(class="flex flex-col gap-2") {
+            (if hits.with(Vec::is_empty) {
+                view! { p(class="text-neutral-400") { "没有找到相关文章" } }
+            } else {
+                view! {}
+            })
+            Indexed(
+                list=hits,
+                view=move |hit| {
+                    let slug = hit.meta.slug.clone();
+                    let on_select = on_select.clone();
+
+                    // 高亮片段由后端转义，只包含 <mark> 标签
+                    view! {
+                        div(
+                            class="bg-white shadow-sm p-3 cursor-pointer text-neutral-900",
+                            on:click=move |_| {
+                                on_select(slug.clone());
+                            }
+                        ) {
+                            h3(class="font-semibold text-base", dangerously_set_inner_html=hit.title_html)
+                            p(class="text-sm text-neutral-700", dangerously_set_inner_html=hit.snippet_html)
+                        }
+                    }
+                }
+            )
+        }
+    }
+}
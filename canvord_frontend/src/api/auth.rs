@@ -1,7 +1,13 @@
 // src/api/auth.rs
+use std::cell::RefCell;
+use futures::future::{FutureExt, LocalBoxFuture, Shared};
 use gloo_net::http::Request;
 use serde::{Deserialize, Serialize};
-use crate::model::AppResponse;
+use crate::model::{AppResponse, TokenPair};
+
+const AUTH_BASE: &str = "http://localhost:8000/admin";
+const ACCESS_TOKEN_KEY: &str = "jwt_token";
+const REFRESH_TOKEN_KEY: &str = "refresh_token";
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct LoginRequest {
@@ -9,8 +15,18 @@ pub struct LoginRequest {
     pub password: String,
 }
 
-pub async fn login(data: &LoginRequest) -> Result<AppResponse<String>, String> {
-    Request::post("http://localhost:8000/admin/login")
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct RefreshRequest {
+    refresh_token: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct LogoutRequest {
+    refresh_token: Option<String>,
+}
+
+pub async fn login(data: &LoginRequest) -> Result<AppResponse<TokenPair>, String> {
+    Request::post(&format!("{AUTH_BASE}/login"))
         .header("Content-Type", "application/json")
         .json(data)
         .map_err(|e| e.to_string())?
@@ -22,17 +38,91 @@ pub async fn login(data: &LoginRequest) -> Result<AppResponse<String>, String> {
         .map_err(|e| e.to_string())
 }
 
-pub fn logout() {
+thread_local! {
+    /// 正在进行的刷新，同时收到 401 的请求共用一次刷新
+    static REFRESHING: RefCell<Option<Shared<LocalBoxFuture<'static, bool>>>> = const { RefCell::new(None) };
+}
+
+/// 使用 refresh token 换取新的 token 对，成功返回 true；已有刷新在进行时等待其结果
+pub async fn refresh() -> bool {
+    let refreshing = REFRESHING.with(|r| {
+        r.borrow_mut()
+            .get_or_insert_with(|| refresh_once().boxed_local().shared())
+            .clone()
+    });
+    let ok = refreshing.clone().await;
+    REFRESHING.with(|r| {
+        let mut r = r.borrow_mut();
+        if r.as_ref().is_some_and(|current| current.ptr_eq(&refreshing)) {
+            *r = None;
+        }
+    });
+    ok
+}
+
+async fn refresh_once() -> bool {
+    let Some(refresh_token) = load_refresh_token() else {
+        return false;
+    };
+
+    let resp: Result<AppResponse<TokenPair>, String> = async {
+        Request::post(&format!("{AUTH_BASE}/refresh"))
+            .header("Content-Type", "application/json")
+            .json(&RefreshRequest { refresh_token })
+            .map_err(|e| e.to_string())?
+            .send()
+            .await
+            .map_err(|e| e.to_string())?
+            .json()
+            .await
+            .map_err(|e| e.to_string())
+    }.await;
+
+    match resp {
+        Ok(AppResponse { code: 0, data: Some(tokens), .. }) => {
+            store_tokens(&tokens);
+            true
+        }
+        _ => {
+            clear_tokens();
+            false
+        }
+    }
+}
+
+/// 通知服务端吊销当前 token，无论请求是否成功都清除本地存储
+pub async fn logout() {
+    if let Some(token) = load_token() {
+        let body = LogoutRequest { refresh_token: load_refresh_token() };
+        if let Ok(req) = Request::post(&format!("{AUTH_BASE}/logout"))
+            .header("Authorization", &format!("Bearer {}", token))
+            .header("Content-Type", "application/json")
+            .json(&body)
+        {
+            let _ = req.send().await;
+        }
+    }
+    clear_tokens();
+}
+
+pub fn store_tokens(tokens: &TokenPair) {
     use gloo_storage::{Storage, LocalStorage};
-    let _ = LocalStorage::delete("jwt_token");
+    let _ = LocalStorage::set(ACCESS_TOKEN_KEY, &tokens.access_token);
+    let _ = LocalStorage::set(REFRESH_TOKEN_KEY, &tokens.refresh_token);
 }
 
-pub fn store_token(token: &str) {
+fn clear_tokens() {
     use gloo_storage::{Storage, LocalStorage};
-    let _ = LocalStorage::set("jwt_token", token);
+    LocalStorage::delete(ACCESS_TOKEN_KEY);
+    LocalStorage::delete(REFRESH_TOKEN_KEY);
 }
 
 pub fn load_token() -> Option<String> {
     use gloo_storage::{Storage, LocalStorage};
-    LocalStorage::get("jwt_token").ok()
+    LocalStorage::get(ACCESS_TOKEN_KEY).ok()
+}
+
+fn load_refresh_token() -> Option<String> {
+    use gloo_storage::{Storage, LocalStorage};
+    LocalStorage::get(REFRESH_TOKEN_KEY).ok()
 }
@@ -5,9 +5,11 @@ use gloo_net::http::{Request, RequestBuilder};
 use crate::model::*;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
-use crate::api::auth::load_token;
+use web_sys::{File, FormData};
+use crate::api::auth::{load_token, refresh};
 
 const API_BASE: &str = "http://localhost:8000/articles";
+const CATEGORY_BASE: &str = "http://localhost:8000/categories";
 
 // 加 JWT Header（如果存在）
 fn with_auth(mut req: RequestBuilder) -> RequestBuilder {
@@ -17,53 +19,50 @@ fn with_auth(mut req: RequestBuilder) -> RequestBuilder {
     req
 }
 
+// 发送请求；若 access token 过期（401）则刷新后用新 token 重试一次
+async fn send_with_refresh<R: DeserializeOwned>(build: impl Fn() -> Result<Request, String>) -> Result<AppResponse<R>, String> {
+    let mut resp = build()?.send().await.map_err(|e| e.to_string())?;
+
+    if resp.status() == 401 && refresh().await {
+        resp = build()?.send().await.map_err(|e| e.to_string())?;
+    }
+
+    resp.json().await.map_err(|e| e.to_string())
+}
+
 async fn post_json<T: Serialize, R: DeserializeOwned>(url: &str, body: &T) -> Result<AppResponse<R>, String> {
-    with_auth(Request::post(url))
-        .header("Content-Type", "application/json")
-        .json(body)
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?
-        .json()
-        .await
-        .map_err(|e| e.to_string())
+    send_with_refresh(|| {
+        with_auth(Request::post(url))
+            .header("Content-Type", "application/json")
+            .json(body)
+            .map_err(|e| e.to_string())
+    }).await
 }
 
 async fn get_json<R: DeserializeOwned>(url: &str) -> Result<AppResponse<R>, String> {
-    with_auth(Request::get(url))
-        .send()
-        .await
-        .map_err(|e| e.to_string())?
-        .json()
-        .await
-        .map_err(|e| e.to_string())
+    send_with_refresh(|| {
+        with_auth(Request::get(url))
+            .build()
+            .map_err(|e| e.to_string())
+    }).await
 }
 
 async fn put_json<T: Serialize, R: DeserializeOwned>(url: &str, body: &T) -> Result<AppResponse<R>, String> {
-    with_auth(Request::put(url))
-        .header("Content-Type", "application/json")
-        .json(body)
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?
-        .json()
-        .await
-        .map_err(|e| e.to_string())
+    send_with_refresh(|| {
+        with_auth(Request::put(url))
+            .header("Content-Type", "application/json")
+            .json(body)
+            .map_err(|e| e.to_string())
+    }).await
 }
 
 async fn delete_json<T: Serialize, R: DeserializeOwned>(url: &str, body: &T) -> Result<AppResponse<R>, String> {
-    with_auth(Request::delete(url))
-        .header("Content-Type", "application/json")
-        .json(body)
-        .map_err(|e| e.to_string())?
-        .send()
-        .await
-        .map_err(|e| e.to_string())?
-        .json()
-        .await
-        .map_err(|e| e.to_string())
+    send_with_refresh(|| {
+        with_auth(Request::delete(url))
+            .header("Content-Type", "application/json")
+            .json(body)
+            .map_err(|e| e.to_string())
+    }).await
 }
 
 pub async fn create_article(cmd: &CreateArticleCommand) -> Result<AppResponse<ArticleDetail>, String> {
@@ -113,7 +112,42 @@ pub async fn get_article_page(page: u64, per: u64, status: Option<Status>) -> Re
             Status::Published => "Published",
             Status::Unpublished => "Unpublished",
             Status::Hidden => "Hidden",
+            Status::Scheduled => "Scheduled",
         }));
     }
     get_json(&url).await
 }
+
+pub async fn list_revisions(article_id: i64) -> Result<AppResponse<Vec<RevisionMeta>>, String> {
+    get_json(&format!("{API_BASE}/revision/list/{}", article_id)).await
+}
+
+pub async fn diff_revisions(from: i64, to: i64) -> Result<AppResponse<RevisionDiff>, String> {
+    get_json(&format!("{API_BASE}/revision/diff?from={}&to={}", from, to)).await
+}
+
+pub async fn restore_revision(cmd: &RestoreArticleRevisionCommand) -> Result<AppResponse<ArticleDetail>, String> {
+    put_json(&format!("{API_BASE}/revision/restore"), cmd).await
+}
+
+pub async fn get_category_tree() -> Result<AppResponse<Vec<CategoryNode>>, String> {
+    get_json(&format!("{CATEGORY_BASE}/tree")).await
+}
+
+
+// 以 multipart 表单上传，Content-Type 由浏览器生成
+pub async fn preview_article(cmd: &PreviewArticleCommand) -> Result<AppResponse<RenderedContent>, String> {
+    post_json(&format!("{API_BASE}/preview"), cmd).await
+}
+
+pub async fn upload_media(file: &File) -> Result<AppResponse<MediaInfo>, String> {
+    send_with_refresh(|| {
+        let form = FormData::new().map_err(|_| "无法创建上传表单".to_string())?;
+        form.append_with_blob_and_filename("file", file, &file.name())
+            .map_err(|_| "无法读取文件".to_string())?;
+
+        with_auth(Request::post(&format!("{API_BASE}/media/upload")))
+            .body(form)
+            .map_err(|e| e.to_string())
+    }).await
+}
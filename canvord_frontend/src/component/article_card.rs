@@ -14,7 +14,11 @@ pub fn ArticleCard(article: ArticleMeta, on_click: Box<dyn Fn(i64) + 'static>) -
             h2(class="text-xl font-semibold text-indigo-600") { (article.title.clone()) }
             p(class="text-gray-600 mt-1 text-sm") { (article.description.clone()) }
             div(class="flex justify-between text-sm text-gray-500 mt-2") {
-                span { (format!("分类: {}", article.category)) }
+                span { (format!("分类: {}", article.category.as_ref().map_or("未分类", |c| c.name.as_str()))) }
+                (match article.publish_at {
+                    Some(at) => view! { span { (format!("定时: {} UTC", at.format("%Y-%m-%d %H:%M"))) } },
+                    None => view! {},
+                })
                 span { (format!("更新: {}", article.last_update.format("%Y-%m-%d %H:%M"))) }
             }
         }
@@ -0,0 +1,47 @@
+use sycamore::futures::spawn_local;
+use sycamore::prelude::*;
+use wasm_bindgen::JsCast;
+use crate::api::get_category_tree;
+use crate::model::CategoryNode;
+
+/// 分类下拉框，子分类按层级缩进显示
+#[component(inline_props)]
+pub fn CategorySelect(selected: Signal<Option<i64>>) -> View {
+    let options = create_signal(Vec::<(i64, String)>::new());
+
+    spawn_local(async move {
+        match get_category_tree().await {
+            Ok(resp) => {
+                let mut flat = Vec::new();
+                flatten(&resp.data.unwrap_or_default(), 0, &mut flat);
+                options.set(flat);
+            }
+            Err(err) => gloo_console::error!("加载分类失败:", err),
+        }
+    });
+
+    view! {
+        select(
+            class="border border-gray-300 rounded px-3 py-1 w-48 h-8",
+            on:change=move |e: web_sys::Event| {
+                let value = e.target().unwrap().dyn_into::<web_sys::HtmlSelectElement>().unwrap().value();
+                selected.set(value.parse::<i64>().ok());
+            }
+        ) {
+            option(value="", selected=selected.get().is_none()) { "未分类" }
+            Indexed(
+                list=options,
+                view=move |(id, label)| view! {
+                    option(value=id.to_string(), selected=selected.get() == Some(id)) { (label) }
+                },
+            )
+        }
+    }
+}
+
+fn flatten(nodes: &[CategoryNode], depth: usize, out: &mut Vec<(i64, String)>) {
+    for node in nodes {
+        out.push((node.id, format!("{}{}", "— ".repeat(depth), node.name)));
+        flatten(&node.children, depth + 1, out);
+    }
+}
@@ -5,6 +5,7 @@ use monaco::sys::languages::{set_language_configuration, IndentAction};
 use sycamore::web::wasm_bindgen::JsCast;
 use wasm_bindgen::JsValue;
 use web_sys::HtmlElement;
+use crate::utils::media_upload::{files_of, upload_and_insert};
 
 #[component(inline_props)]
 pub fn Editor(opt: Option<CodeEditorOptions>, editor: Signal<Option<CodeEditor>>) -> View {
@@ -18,8 +19,28 @@ pub fn Editor(opt: Option<CodeEditorOptions>, editor: Signal<Option<CodeEditor>>
         register_enter_rule_for_markdown();
     });
 
+    // 粘贴或拖入文件时上传，并在光标处插入链接；纯文本粘贴交给 monaco 处理
     view! {
-        div(ref=node_ref, class="flex-1", style="height: calc(100vh - 3rem);")
+        div(
+            ref=node_ref,
+            class="flex-1",
+            style="height: calc(100vh - 3rem);",
+            on:paste=move |e: web_sys::ClipboardEvent| {
+                let files = files_of(e.clipboard_data().and_then(|d| d.files()));
+                if !files.is_empty() {
+                    e.prevent_default();
+                    upload_and_insert(editor, files);
+                }
+            },
+            on:dragover=move |e: web_sys::DragEvent| e.prevent_default(),
+            on:drop=move |e: web_sys::DragEvent| {
+                let files = files_of(e.data_transfer().and_then(|d| d.files()));
+                if !files.is_empty() {
+                    e.prevent_default();
+                    upload_and_insert(editor, files);
+                }
+            }
+        )
     }
 }
 
@@ -1,7 +1,7 @@
 use sycamore::futures::spawn_local;
 use sycamore::prelude::*;
 use sycamore_router::navigate;
-use crate::api::auth::{login, LoginRequest, store_token};
+use crate::api::auth::{login, LoginRequest, store_tokens};
 use crate::model::AppResponse;
 
 #[component]
@@ -18,8 +18,8 @@ pub fn LoginScreen() -> View {
         spawn_local(async move {
             let req = LoginRequest { username, password };
             match login(&req).await {
-                Ok(AppResponse { code: 0, data: Some(token), .. }) => {
-                    store_token(&token);
+                Ok(AppResponse { code: 0, data: Some(tokens), .. }) => {
+                    store_tokens(&tokens);
                     navigate("/");
                 }
                 Ok(resp) => error_msg.set(Some(resp.message)),
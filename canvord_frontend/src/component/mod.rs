@@ -6,3 +6,5 @@ pub mod confirm_dialog;
 pub mod pagination;
 pub mod editor;
 pub mod login;
+pub mod revision_panel;
+pub mod category_select;
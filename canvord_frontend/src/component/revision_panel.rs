@@ -0,0 +1,103 @@
+use std::rc::Rc;
+use sycamore::futures::spawn_local;
+use sycamore::prelude::*;
+use crate::api::{diff_revisions, list_revisions, restore_revision};
+use crate::model::{ArticleDetail, DiffTag, RestoreArticleRevisionCommand, RevisionDiff, RevisionMeta};
+use crate::utils::show_browser_notification::show_browser_notification;
+
+/// 修订历史面板：列出文章修订，与最新修订对比，或恢复到某个修订
+#[component(inline_props)]
+pub fn RevisionPanel(article_id: i64, on_restore: Rc<dyn Fn(ArticleDetail)>) -> View {
+    let revisions = create_signal(Vec::<RevisionMeta>::new());
+    let diff = create_signal(None::<RevisionDiff>);
+
+    let reload = move || {
+        spawn_local(async move {
+            match list_revisions(article_id).await {
+                Ok(resp) => revisions.set(resp.data.unwrap_or_default()),
+                Err(err) => gloo_console::error!("加载修订历史失败:", err),
+            }
+        });
+    };
+    reload();
+
+    // 列表按时间倒序，第一条即最新修订
+    let compare = move |id: i64| {
+        let Some(latest) = revisions.with(|list| list.first().map(|r| r.id)) else {
+            return;
+        };
+        spawn_local(async move {
+            match diff_revisions(id, latest).await {
+                Ok(resp) => diff.set(resp.data),
+                Err(err) => gloo_console::error!("对比修订失败:", err),
+            }
+        });
+    };
+
+    let restore = Rc::new(move |id: i64| {
+        let on_restore = on_restore.clone();
+        spawn_local(async move {
+            match restore_revision(&RestoreArticleRevisionCommand { revision_id: id }).await {
+                Ok(resp) => match resp.data {
+                    Some(article) => {
+                        on_restore(article);
+                        diff.set(None);
+                        reload();
+                    }
+                    None => show_browser_notification("恢复失败", &resp.message).await,
+                },
+                Err(err) => show_browser_notification("恢复失败", &err).await,
+            }
+        });
+    });
+
+    view! {
+        div(class="w-96 bg-white shadow p-4 space-y-3 overflow-y-auto", style="height: calc(100vh - 3rem);") {
+            h3(class="text-lg font-semibold") { "修订历史" }
+            Indexed(
+                list=revisions,
+                view=move |rev| {
+                    let id = rev.id;
+                    let restore = restore.clone();
+                    view! {
+                        div(class="border border-gray-200 rounded p-2 text-sm space-y-1") {
+                            p(class="font-medium") { (format!("#{} {}", rev.id, rev.title)) }
+                            p(class="text-gray-500") { (rev.created_at.format("%Y-%m-%d %H:%M:%S").to_string()) }
+                            div(class="flex gap-2") {
+                                button(
+                                    class="px-2 py-1 rounded bg-gray-200 hover:bg-gray-300",
+                                    on:click=move |_| compare(id)
+                                ) { "对比最新" }
+                                button(
+                                    class="px-2 py-1 rounded bg-yellow-500 hover:bg-yellow-600 text-white",
+                                    on:click=move |_| restore(id)
+                                ) { "恢复" }
+                            }
+                        }
+                    }
+                }
+            )
+            (match diff.get_clone() {
+                Some(d) => view! {
+                    div(class="border-t pt-3 space-y-1") {
+                        p(class="text-sm text-gray-600") { (format!("#{} → #{}", d.from.id, d.to.id)) }
+                        pre(class="text-xs font-mono overflow-x-auto") {
+                            Indexed(
+                                list=d.lines.clone(),
+                                view=|line| {
+                                    let (prefix, class) = match line.tag {
+                                        DiffTag::Equal => (" ", "text-gray-600"),
+                                        DiffTag::Insert => ("+", "bg-green-100 text-green-800"),
+                                        DiffTag::Delete => ("-", "bg-red-100 text-red-800"),
+                                    };
+                                    view! { div(class=class) { (format!("{} {}", prefix, line.content)) } }
+                                }
+                            )
+                        }
+                    }
+                },
+                None => view! { span {} },
+            })
+        }
+    }
+}
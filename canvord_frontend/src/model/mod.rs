@@ -6,6 +6,7 @@ pub enum Status {
     Published,
     Unpublished,
     Hidden,
+    Scheduled,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -14,9 +15,11 @@ pub struct ArticleMeta {
     pub title: String,
     pub slug: String,
     pub description: String,
-    pub category: String,
+    pub category: Option<CategoryInfo>,
     pub last_update: NaiveDateTime,
     pub status: Status,
+    #[serde(default)]
+    pub publish_at: Option<NaiveDateTime>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -25,6 +28,32 @@ pub struct ArticleDetail {
     pub meta: ArticleMeta,
     pub content_md: String,
     pub created_at: NaiveDateTime,
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CategoryInfo {
+    pub id: i64,
+    pub name: String,
+    pub slug: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CategoryNode {
+    pub id: i64,
+    pub parent_id: Option<i64>,
+    pub name: String,
+    pub slug: String,
+    pub description: String,
+    pub children: Vec<CategoryNode>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,
+    pub slug: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -42,23 +71,89 @@ pub struct AppResponse<T> {
     pub data: Option<T>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RevisionMeta {
+    pub id: i64,
+    pub article_id: i64,
+    pub title: String,
+    pub description: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum DiffTag {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DiffLine {
+    pub tag: DiffTag,
+    pub content: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RevisionDiff {
+    pub from: RevisionMeta,
+    pub to: RevisionMeta,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MediaInfo {
+    pub id: i64,
+    pub owner_id: Option<i64>,
+    pub key: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i64,
+    pub url: String,
+    pub usage_count: u64,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TocEntry {
+    pub level: u8,
+    pub id: String,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RenderedContent {
+    pub content_html: String,
+    pub toc: Vec<TocEntry>,
+    pub word_count: i32,
+    pub reading_minutes: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TokenPair {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_in: i64,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CreateArticleCommand {
-    pub category: String,
+    pub category_id: Option<i64>,
     pub content_md: String,
     pub description: String,
     pub slug: String,
+    pub tags: Vec<String>,
     pub title: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UpdateArticleCommand {
     pub id: i64,
-    pub category: String,
+    pub category_id: Option<i64>,
     pub content_md: String,
     pub description: String,
     pub slug: String,
     pub status: Status,
+    pub tags: Option<Vec<String>>,
     pub title: String,
 }
 
@@ -80,18 +175,30 @@ pub struct PublishArticleCommand {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PublishDraftCommand {
     pub id: i64,
-    pub category: String,
+    pub category_id: Option<i64>,
     pub content_md: String,
     pub description: String,
     pub slug: String,
+    pub tags: Option<Vec<String>>,
     pub title: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SaveArticleCommand {
-    pub category: String,
+    pub category_id: Option<i64>,
     pub content_md: String,
     pub description: String,
     pub slug: String,
+    pub tags: Vec<String>,
     pub title: String,
-}
\ No newline at end of file
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RestoreArticleRevisionCommand {
+    pub revision_id: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PreviewArticleCommand {
+    pub content_md: String,
+}
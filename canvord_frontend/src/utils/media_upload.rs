@@ -0,0 +1,63 @@
+use js_sys::{Object, Reflect};
+use monaco::api::CodeEditor;
+use sycamore::futures::spawn_local;
+use sycamore::prelude::*;
+use sycamore::web::wasm_bindgen::JsCast;
+use wasm_bindgen::JsValue;
+use web_sys::{File, FileList};
+use crate::api::upload_media;
+use crate::utils::show_browser_notification::show_browser_notification;
+
+pub fn files_of(list: Option<FileList>) -> Vec<File> {
+    let Some(list) = list else {
+        return Vec::new();
+    };
+    (0..list.length()).filter_map(|i| list.get(i)).collect()
+}
+
+// 逐个上传文件，成功后在光标处插入 Markdown 链接，图片使用 ![]() 形式
+pub fn upload_and_insert(editor: Signal<Option<CodeEditor>>, files: Vec<File>) {
+    spawn_local(async move {
+        for file in files {
+            let name = file.name();
+            match upload_media(&file).await {
+                Ok(resp) => match resp.data {
+                    Some(media) => {
+                        let link = if media.content_type.starts_with("image/") {
+                            format!("![{}]({})", media.file_name, media.url)
+                        } else {
+                            format!("[{}]({})", media.file_name, media.url)
+                        };
+                        editor.with(|editor_val| {
+                            if let Some(ed) = editor_val {
+                                insert_text(ed, &link);
+                            }
+                        });
+                    }
+                    None => {
+                        show_browser_notification("上传失败", &format!("{}: {}", name, resp.message)).await;
+                    }
+                },
+                Err(err) => {
+                    show_browser_notification("上传失败", &format!("{}: {}", name, err)).await;
+                }
+            }
+        }
+    });
+}
+
+// 通过 monaco 的 type 命令插入，效果等同于在光标处输入，可以撤销
+fn insert_text(editor: &CodeEditor, text: &str) {
+    let ed: &JsValue = editor.as_ref().as_ref();
+    let payload = Object::new();
+    let _ = Reflect::set(&payload, &JsValue::from_str("text"), &JsValue::from_str(text));
+
+    let _ = Reflect::get(ed, &JsValue::from_str("trigger"))
+        .and_then(|f| {
+            if let Some(func) = f.dyn_ref::<js_sys::Function>() {
+                func.call3(ed, &JsValue::from_str("upload"), &JsValue::from_str("type"), &payload)
+            } else {
+                Err(JsValue::from_str("trigger is not a function"))
+            }
+        });
+}
@@ -1,2 +1,4 @@
+pub mod media_upload;
 pub mod preview_html;
-pub mod show_browser_notification;
\ No newline at end of file
+pub mod show_browser_notification;
+pub mod tags;
\ No newline at end of file
@@ -1,3 +1,63 @@
+use js_sys::Reflect;
+use sycamore::futures::spawn_local;
+use wasm_bindgen::{JsCast, JsValue};
+use crate::api::preview_article;
+use crate::model::PreviewArticleCommand;
+
+const HIGHLIGHT_CSS: &str = "http://localhost:8000/highlight.css";
+
+/// 先同步打开新标签页以免被浏览器拦截，再由后端渲染后写入
+pub fn open_preview(content_md: String) {
+    let Some(win) = web_sys::window() else { return };
+    let Ok(Some(new_tab)) = win.open_with_url("about:blank") else { return };
+    let Some(doc) = new_tab.document() else { return };
+
+    spawn_local(async move {
+        let body = match preview_article(&PreviewArticleCommand { content_md }).await {
+            Ok(resp) => match resp.data {
+                Some(rendered) => format!(
+                    r#"<p class="meta">约 {} 字，阅读需 {} 分钟</p>{}"#,
+                    rendered.word_count, rendered.reading_minutes, with_copy_buttons(&rendered.content_html)
+                ),
+                None => format!("<p>预览失败：{}</p>", escape_html(&resp.message)),
+            },
+            Err(err) => format!("<p>预览失败：{}</p>", escape_html(&err)),
+        };
+        let html = preview_html(body);
+
+        let _ = Reflect::get(&doc, &JsValue::from_str("write"))
+            .and_then(|f| {
+                if let Some(func) = f.dyn_ref::<js_sys::Function>() {
+                    func.call1(&doc, &JsValue::from_str(&html))
+                } else {
+                    Err(JsValue::from_str("write is not a function"))
+                }
+            });
+
+        let _ = Reflect::get(&doc, &JsValue::from_str("close"))
+            .and_then(|f| {
+                if let Some(func) = f.dyn_ref::<js_sys::Function>() {
+                    func.call0(&doc)
+                } else {
+                    Err(JsValue::from_str("close is not a function"))
+                }
+            });
+    });
+}
+
+/// 与访客页面一致，复制按钮只在页面上添加
+fn with_copy_buttons(html: &str) -> String {
+    html.replace(
+        r#"<pre class="hl-code">"#,
+        r#"<pre class="hl-code"><button type="button" class="copy-code">复制</button>"#,
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// `_html_output` 须是后端渲染并过滤过的 HTML，这里不再做处理
 pub fn preview_html(_html_output: String) -> String {
     format!(r#"<!DOCTYPE html>
                <html lang="cn">
@@ -5,6 +65,7 @@ pub fn preview_html(_html_output: String) -> String {
                    <meta charset="utf-8">
                    <title>Markdown Preview</title>
                    <meta name="viewport" content="width=device-width, initial-scale=1">
+                   <link rel="stylesheet" href="{}">
                    <style>
                        body {{
                            max-width: 768px;
@@ -30,6 +91,50 @@ pub fn preview_html(_html_output: String) -> String {
                            padding: 1em;
                            overflow: auto;
                        }}
+                       pre.hl-code {{
+                           position: relative;
+                       }}
+                       pre.hl-code code {{
+                           background: none;
+                           padding: 0;
+                       }}
+                       .copy-code {{
+                           position: absolute;
+                           top: 0.5em;
+                           right: 0.5em;
+                           font-size: 0.75em;
+                           padding: 0.1em 0.6em;
+                           border: 1px solid #d1d5db;
+                           border-radius: 4px;
+                           background-color: #f3f4f6;
+                           color: #4b5563;
+                           cursor: pointer;
+                           opacity: 0;
+                       }}
+                       pre.hl-code:hover .copy-code {{
+                           opacity: 1;
+                       }}
+                       pre.mermaid {{
+                           background: none;
+                           text-align: center;
+                       }}
+                       .diagram {{
+                           margin: 1.5em 0;
+                           overflow-x: auto;
+                       }}
+                       .diagram svg {{
+                           display: block;
+                           margin: 0 auto;
+                           max-width: 100%;
+                           height: auto;
+                       }}
+                       math[display="block"] {{
+                           margin: 1em 0;
+                           overflow-x: auto;
+                       }}
+                       .math-error {{
+                           color: #dc2626;
+                       }}
                        blockquote {{
                            color: #6a737d;
                            padding: 0 1em;
@@ -48,6 +153,21 @@ pub fn preview_html(_html_output: String) -> String {
                        }}
                        img {{
                            max-width: 100%;
+                           height: auto;
+                       }}
+                       .meta {{
+                           color: #6a737d;
+                           font-size: 0.875em;
+                       }}
+                       .anchor {{
+                           margin-left: 0.4em;
+                           color: #9ca3af;
+                           text-decoration: none;
+                           visibility: hidden;
+                       }}
+                       h1:hover .anchor, h2:hover .anchor, h3:hover .anchor,
+                       h4:hover .anchor, h5:hover .anchor, h6:hover .anchor {{
+                           visibility: visible;
                        }}
                        @media (prefers-color-scheme: dark) {{
                            body {{
@@ -59,11 +179,32 @@ pub fn preview_html(_html_output: String) -> String {
                                background-color: #374151;
                                color: #f3f4f6;
                            }}
+                           .diagram svg {{
+                               background-color: #ffffff;
+                               border-radius: 6px;
+                           }}
                        }}
                    </style>
                </head>
                <body>
                    {}
+                   <script>
+                       document.addEventListener('click', function (e) {{
+                           var button = e.target.closest('.copy-code');
+                           if (!button) return;
+                           navigator.clipboard.writeText(button.parentElement.querySelector('code').textContent);
+                           button.textContent = '已复制';
+                       }});
+                   </script>
+                   <script type="module">
+                       import mermaid from "https://cdn.jsdelivr.net/npm/mermaid@11/dist/mermaid.esm.min.mjs";
+                       mermaid.initialize({{
+                           startOnLoad: false,
+                           securityLevel: "strict",
+                           theme: matchMedia("(prefers-color-scheme: dark)").matches ? "dark" : "default",
+                       }});
+                       mermaid.run({{ querySelector: "pre.mermaid" }});
+                   </script>
                </body>
-               </html>"#, _html_output)
+               </html>"#, HIGHLIGHT_CSS, _html_output)
 }
@@ -0,0 +1,18 @@
+use crate::model::Tag;
+
+/// 将逗号分隔（中英文逗号均可）的输入拆分为标签列表
+pub fn parse_tags(input: &str) -> Vec<String> {
+    input
+        .split([',', '，'])
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+pub fn join_tags(tags: &[Tag]) -> String {
+    tags.iter()
+        .map(|t| t.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -85,6 +85,7 @@ pub fn ArticleView() -> View {
                     "Published" => Some(Status::Published),
                     "Unpublished" => Some(Status::Unpublished),
                     "Hidden" => Some(Status::Hidden),
+                    "Scheduled" => Some(Status::Scheduled),
                     _ => None,
                 };
                 on_status_change(status);
@@ -131,6 +132,7 @@ pub fn ArticleView() -> View {
                         option(value="Published") { "已发布" }
                         option(value="Unpublished") { "未发布" }
                         option(value="Hidden") { "已隐藏" }
+                        option(value="Scheduled") { "定时发布" }
                     }
                 }
             }
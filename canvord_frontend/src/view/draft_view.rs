@@ -1,16 +1,14 @@
 use monaco::api::{CodeEditor, CodeEditorOptions};
+use crate::component::category_select::CategorySelect;
 use crate::component::editor::Editor;
 use sycamore::prelude::*;
-use sycamore::web::wasm_bindgen::JsCast;
-use wasm_bindgen::JsValue;
-use js_sys::Reflect;
 use gloo_timers::callback::Interval;
-use pulldown_cmark::Options;
 use sycamore::futures::spawn_local;
 use crate::api::create_article;
 use crate::model::CreateArticleCommand;
-use crate::utils::preview_html::preview_html;
+use crate::utils::preview_html::open_preview;
 use crate::utils::show_browser_notification::show_browser_notification;
+use crate::utils::tags::parse_tags;
 
 #[component]
 pub fn DraftView() -> View {
@@ -31,7 +29,8 @@ pub fn DraftView() -> View {
     let title = create_signal(String::new());
     let slug = create_signal(String::new());
     let description = create_signal(String::new());
-    let category = create_signal(String::new());
+    let category = create_signal(None::<i64>);
+    let tags = create_signal(String::new());
 
     let auto_save_interval = create_signal(None::<Interval>);
 
@@ -84,10 +83,11 @@ pub fn DraftView() -> View {
                         placeholder="Title",
                         bind:value=title
                     )
+                    CategorySelect(selected=category)
                     input(
                         class="border border-gray-300 rounded px-3 py-1 w-64 h-8",
-                        placeholder="Category",
-                        bind:value=category
+                        placeholder="Tags（逗号分隔）",
+                        bind:value=tags
                     )
                     textarea(
                         class="border border-gray-300 rounded px-3 py-1 w-96 h-8",
@@ -100,7 +100,8 @@ pub fn DraftView() -> View {
                             let slug_val = slug.get_clone();
                             let title_val = title.get_clone();
                             let desc_val = description.get_clone();
-                            let category_val = category.get_clone();
+                            let category_val = category.get();
+                            let tags_val = parse_tags(&tags.get_clone());
 
                             editor.with(|editor_val| {
                                 if let Some(ed) = editor_val {
@@ -111,7 +112,8 @@ pub fn DraftView() -> View {
                                             slug: slug_val,
                                             title: title_val,
                                             description: desc_val,
-                                            category: category_val,
+                                            category_id: category_val,
+                                            tags: tags_val,
                                             content_md: content_val,
                                         };
 
@@ -164,36 +166,7 @@ pub fn DraftView() -> View {
                             editor.with(|opt| {
                                 if let Some(ed) = opt {
                                     if let Some(model) = ed.get_model() {
-                                        let content = model.get_value();
-                                        let parser = pulldown_cmark::Parser::new_ext(&*content, Options::all());
-                                        let mut html_output = String::new();
-                                        pulldown_cmark::html::push_html(&mut html_output, parser);
-
-                                        if let Some(win) = web_sys::window() {
-                                            if let Ok(Some(new_tab)) = win.open_with_url("about:blank") {
-                                                if let Some(doc) = new_tab.document() {
-                                                    let html = preview_html(html_output);
-
-                                                    let _ = Reflect::get(&doc, &JsValue::from_str("write"))
-                                                        .and_then(|f| {
-                                                            if let Some(func) = f.dyn_ref::<js_sys::Function>() {
-                                                                func.call1(&doc, &JsValue::from_str(&html))
-                                                            } else {
-                                                                Err(JsValue::from_str("write is not a function"))
-                                                            }
-                                                        });
-
-                                                    let _ = Reflect::get(&doc, &JsValue::from_str("close"))
-                                                        .and_then(|f| {
-                                                            if let Some(func) = f.dyn_ref::<js_sys::Function>() {
-                                                                func.call0(&doc)
-                                                            } else {
-                                                                Err(JsValue::from_str("close is not a function"))
-                                                            }
-                                                        });
-                                                }
-                                            }
-                                        }
+                                        open_preview(model.get_value());
                                     }
                                 }
                             });
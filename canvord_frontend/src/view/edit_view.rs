@@ -1,14 +1,15 @@
-use js_sys::Reflect;
 use monaco::api::{CodeEditor, CodeEditorOptions};
-use pulldown_cmark::Options;
 use sycamore::prelude::*;
 use sycamore::futures::spawn_local;
 use sycamore::web::{create_client_resource, Suspense};
-use wasm_bindgen::{JsCast, JsValue};
 use crate::api::{get_article_by_id, update_article};
+use std::rc::Rc;
+use crate::component::category_select::CategorySelect;
 use crate::component::editor::Editor;
-use crate::model::{Status, UpdateArticleCommand};
-use crate::utils::{preview_html::preview_html, show_browser_notification::show_browser_notification};
+use crate::component::revision_panel::RevisionPanel;
+use crate::model::{ArticleDetail, Status, UpdateArticleCommand};
+use crate::utils::{preview_html::open_preview, show_browser_notification::show_browser_notification};
+use crate::utils::tags::{join_tags, parse_tags};
 
 #[component]
 pub fn ArticleEditView(id: i64) -> View {
@@ -24,11 +25,28 @@ pub fn ArticleEditView(id: i64) -> View {
     let title = create_signal(String::new());
     let slug = create_signal(String::new());
     let description = create_signal(String::new());
-    let category = create_signal(String::new());
+    let category = create_signal(None::<i64>);
+    let tags = create_signal(String::new());
     let status = create_signal(Status::Unpublished);
 
     // 更新操作的加载状态
     let is_updating = create_signal(false);
+    // 是否展示修订历史面板
+    let show_history = create_signal(false);
+
+    // 恢复修订后，用返回的文章刷新表单和编辑器
+    let on_restore: Rc<dyn Fn(ArticleDetail)> = Rc::new(move |article: ArticleDetail| {
+        title.set(article.meta.title.clone());
+        description.set(article.meta.description.clone());
+        tags.set(join_tags(&article.tags));
+        editor.with(|editor_val| {
+            if let Some(ed) = editor_val {
+                if let Some(model) = ed.get_model() {
+                    model.set_value(&article.content_md);
+                }
+            }
+        });
+    });
     
     let resource_for_effect = article_resource.clone();
     // FIXED: 修正 create_effect 的用法
@@ -40,7 +58,8 @@ pub fn ArticleEditView(id: i64) -> View {
                 title.set(article.meta.title.clone());
                 slug.set(article.meta.slug.clone());
                 description.set(article.meta.description.clone());
-                category.set(article.meta.category.clone());
+                category.set(article.meta.category.as_ref().map(|c| c.id));
+                tags.set(join_tags(&article.tags));
                 status.set(article.meta.status);
 
                 editor.with(|editor_val| {
@@ -87,10 +106,11 @@ pub fn ArticleEditView(id: i64) -> View {
                                             placeholder="Title",
                                             bind:value=title
                                         )
+                                        CategorySelect(selected=category)
                                         input(
                                             class="border border-gray-300 rounded px-3 py-1 w-64 h-8",
-                                            placeholder="Category",
-                                            bind:value=category
+                                            placeholder="Tags（逗号分隔）",
+                                            bind:value=tags
                                         )
                                         textarea(
                                             class="border border-gray-300 rounded px-3 py-1 w-96 h-8",
@@ -116,6 +136,8 @@ pub fn ArticleEditView(id: i64) -> View {
                                                 option(value="Published", selected=status.get() == Status::Published) { "已发布" }
                                                 option(value="Unpublished", selected=status.get() == Status::Unpublished) { "未发布" }
                                                 option(value="Hidden", selected=status.get() == Status::Hidden) { "已隐藏" }
+                                                // 定时发布需通过 /articles/schedule 设置发布时间，这里只用于保持原状态
+                                                option(value="Scheduled", disabled=true, selected=status.get() == Status::Scheduled) { "定时发布" }
                                             }
                                         }
                                         button(
@@ -132,7 +154,8 @@ pub fn ArticleEditView(id: i64) -> View {
                                                                 slug: slug.get_clone(),
                                                                 title: title.get_clone(),
                                                                 description: description.get_clone(),
-                                                                category: category.get_clone(),
+                                                                category_id: category.get(),
+                                                                tags: Some(parse_tags(&tags.get_clone())),
                                                                 status: status.get(),
                                                                 content_md: content_val,
                                                             };
@@ -169,36 +192,7 @@ pub fn ArticleEditView(id: i64) -> View {
                                                 editor.with(|opt| {
                                                     if let Some(ed) = opt {
                                                         if let Some(model) = ed.get_model() {
-                                                            let content = model.get_value();
-                                                            let parser = pulldown_cmark::Parser::new_ext(&*content, Options::all());
-                                                            let mut html_output = String::new();
-                                                            pulldown_cmark::html::push_html(&mut html_output, parser);
-                    
-                                                            if let Some(win) = web_sys::window() {
-                                                                if let Ok(Some(new_tab)) = win.open_with_url("about:blank") {
-                                                                    if let Some(doc) = new_tab.document() {
-                                                                        let html = preview_html(html_output);
-                    
-                                                                        let _ = Reflect::get(&doc, &JsValue::from_str("write"))
-                                                                            .and_then(|f| {
-                                                                                if let Some(func) = f.dyn_ref::<js_sys::Function>() {
-                                                                                    func.call1(&doc, &JsValue::from_str(&html))
-                                                                                } else {
-                                                                                    Err(JsValue::from_str("write is not a function"))
-                                                                                }
-                                                                            });
-                    
-                                                                        let _ = Reflect::get(&doc, &JsValue::from_str("close"))
-                                                                            .and_then(|f| {
-                                                                                if let Some(func) = f.dyn_ref::<js_sys::Function>() {
-                                                                                    func.call0(&doc)
-                                                                                } else {
-                                                                                    Err(JsValue::from_str("close is not a function"))
-                                                                                }
-                                                                            });
-                                                                    }
-                                                                }
-                                                            }
+                                                            open_preview(model.get_value());
                                                         }
                                                     }
                                                 });
@@ -206,11 +200,28 @@ pub fn ArticleEditView(id: i64) -> View {
                                         ) {
                                             "预览"
                                         }
+                                        button(
+                                            class="bg-gray-500 hover:bg-gray-600 text-white px-4 py-2 rounded font-semibold h-8",
+                                            on:click=move |_| show_history.set(!show_history.get())
+                                        ) {
+                                            (if show_history.get() { "关闭历史" } else { "历史" })
+                                        }
                                     }
                                 }
 
-                                // 编辑器主体
-                                Editor(opt=opt.clone(), editor=editor)
+                                div(class="flex flex-1") {
+                                    // 编辑器主体
+                                    Editor(opt=opt.clone(), editor=editor)
+
+                                    ({
+                                        let on_restore = on_restore.clone();
+                                        if show_history.get() {
+                                            view! { RevisionPanel(article_id=id, on_restore=on_restore) }
+                                        } else {
+                                            view! { span {} }
+                                        }
+                                    })
+                                }
                             }
                         } else {
                             // API 成功返回，但 data 字段为空
@@ -1,3 +1,4 @@
+use sycamore::futures::spawn_local;
 use sycamore::prelude::*;
 use crate::component::login::LoginScreen;
 use crate::api::auth::{load_token, logout};
@@ -8,11 +9,13 @@ pub fn HomeView() -> View {
     // 是否已登录（根据是否存在 token）
     let is_logged_in = create_signal(load_token().is_some());
 
-    // 登出函数：清除 token 并刷新界面
+    // 登出函数：吊销 token 并刷新界面
     let logout = {
         move |_| {
-            logout();
-            is_logged_in.set(false);
+            spawn_local(async move {
+                logout().await;
+                is_logged_in.set(false);
+            });
         }
     };
 